@@ -0,0 +1,449 @@
+//! Derive macros for the [`z3`](https://docs.rs/z3/) crate.
+
+use std::collections::HashMap;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Type};
+
+/// Map a Rust enum or struct to a Z3 datatype sort.
+///
+/// Each enum variant becomes a constructor of the datatype, and a struct
+/// becomes a datatype with a single constructor. Fields may be of any type
+/// implementing [`DatatypeField`], which includes `bool`, the primitive
+/// integers, `String`, other types deriving `Z3Datatype` and `Box`es of
+/// those, so recursive and mutually recursive types are supported.
+/// Generic types are not.
+///
+/// For a type `Shape`, the derive generates a `ShapeSort<'ctx>` handle
+/// holding the [`DatatypeSort`] in its `sort` field, with:
+///
+/// - `ShapeSort::new(ctx)`, which creates the sort of `Shape` together with
+///   those of the datatypes it refers to, with
+///   [`create_derived_datatypes`];
+/// - `ShapeSort::from_sorts(&sorts)`, which picks the sort of `Shape` out of
+///   the result of [`create_derived_datatypes`] for another type, so that
+///   mutually recursive types share their sorts;
+/// - for each variant `Circle`, a constructor `circle(..)` taking the
+///   fields' ASTs, a tester `is_circle(..)`, and an accessor
+///   `circle_radius(..)` for each field `radius` (`circle_0(..)`, ... for
+///   tuple variants); the names are the same for the variant of a struct,
+///   after the struct's name;
+/// - `decode(model, value)`, which converts the value of a term in a model
+///   back into a `Shape`.
+///
+/// `Shape` also implements [`FromModel`](z3::FromModel), so its values can
+/// be read with [`Model::get()`](z3::Model::get).
+///
+/// The derive fails if two of the generated methods get the same name, e.g.
+/// for variants `Foo` and `IsFoo`, or a variant named `New`. Sorts are named
+/// after their types, so [`create_derived_datatypes`] panics if two of the
+/// types it creates sorts for have the same name.
+///
+/// # Examples
+/// ```
+/// use z3::{ast::{Ast, Int}, Config, Context, SatResult, Solver};
+/// use z3_derive::Z3Datatype;
+///
+/// #[derive(Debug, PartialEq, Z3Datatype)]
+/// enum List {
+///     Nil,
+///     Cons { head: i64, tail: Box<List> },
+/// }
+///
+/// let cfg = Config::new();
+/// let ctx = Context::new(&cfg);
+/// let lists = ListSort::new(&ctx);
+/// let x = Int::new_const(&ctx, "x");
+/// let list = lists.cons(&x, &lists.cons(&Int::from_i64(&ctx, 2), &lists.nil()));
+///
+/// let solver = Solver::new(&ctx);
+/// solver.assert(&lists.cons_head(&list)._eq(&Int::from_i64(&ctx, 1)));
+/// assert_eq!(solver.check(), SatResult::Sat);
+///
+/// let model = solver.get_model().unwrap();
+/// let value = lists.decode(&model, &list).unwrap();
+/// let two = List::Cons { head: 2, tail: Box::new(List::Nil) };
+/// assert_eq!(value, List::Cons { head: 1, tail: Box::new(two) });
+/// ```
+///
+/// [`DatatypeField`]: https://docs.rs/z3/latest/z3/datatype_builder/trait.DatatypeField.html
+/// [`DatatypeSort`]: https://docs.rs/z3/latest/z3/struct.DatatypeSort.html
+/// [`create_derived_datatypes`]: https://docs.rs/z3/latest/z3/datatype_builder/fn.create_derived_datatypes.html
+#[proc_macro_derive(Z3Datatype)]
+pub fn derive_z3_datatype(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// A constructor of the derived datatype.
+struct Variant {
+    /// The Rust path to construct the variant, e.g. `Shape::Circle`.
+    path: TokenStream2,
+    /// The constructor name in Z3, which is the Rust variant name.
+    name: String,
+    /// The prefix of the generated method names, e.g. `circle`.
+    snake: String,
+    span: Span,
+    fields: Vec<Field>,
+    style: Style,
+}
+
+struct Field {
+    /// The Rust field name, for named fields.
+    member: Option<Ident>,
+    ty: Type,
+    /// The accessor name, both in Z3 and for the generated method.
+    accessor: Ident,
+}
+
+enum Style {
+    Named,
+    Unnamed,
+    Unit,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Z3Datatype cannot be derived for generic types",
+        ));
+    }
+    let ident = &input.ident;
+    let variants = match &input.data {
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "Z3Datatype cannot be derived for enums without variants",
+                ));
+            }
+            data.variants
+                .iter()
+                .map(|v| {
+                    let variant = &v.ident;
+                    variant_of(quote!(#ident::#variant), variant, &v.fields)
+                })
+                .collect()
+        }
+        Data::Struct(data) => vec![variant_of(quote!(#ident), ident, &data.fields)],
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "Z3Datatype cannot be derived for unions",
+            ))
+        }
+    };
+    check_method_names(&variants)?;
+    Ok(generate(input, &variants))
+}
+
+/// Check that the methods generated for the variants have distinct names,
+/// which also differ from those of the other methods of the sort handle.
+///
+/// ```compile_fail
+/// #[derive(z3_derive::Z3Datatype)]
+/// enum Clash { Foo, IsFoo }
+/// ```
+/// ```compile_fail
+/// #[derive(z3_derive::Z3Datatype)]
+/// enum Clash { New(i64) }
+/// ```
+/// ```compile_fail
+/// #[derive(z3_derive::Z3Datatype)]
+/// enum Clash { FromSorts, Decode }
+/// ```
+/// ```compile_fail
+/// #[derive(z3_derive::Z3Datatype)]
+/// enum Clash { Pair { left: bool }, PairLeft }
+/// ```
+/// ```
+/// #[derive(z3_derive::Z3Datatype)]
+/// enum NoClash { Foo, IsBar, Newer }
+/// ```
+fn check_method_names(variants: &[Variant]) -> syn::Result<()> {
+    let mut names: HashMap<String, String> = ["new", "from_sorts", "decode"]
+        .iter()
+        .map(|name| (name.to_string(), format!("method `{}`", name)))
+        .collect();
+    for v in variants {
+        let mut methods = vec![
+            (v.snake.clone(), format!("constructor of `{}`", v.name)),
+            (format!("is_{}", v.snake), format!("tester of `{}`", v.name)),
+        ];
+        for f in &v.fields {
+            methods.push((
+                f.accessor.to_string(),
+                format!("accessor `{}` of `{}`", f.accessor, v.name),
+            ));
+        }
+        for (method, origin) in methods {
+            if let Some(other) = names.get(&method) {
+                return Err(syn::Error::new(
+                    v.span,
+                    format!(
+                        "the {} would be named `{}`, like the {}",
+                        origin, method, other
+                    ),
+                ));
+            }
+            names.insert(method, origin);
+        }
+    }
+    Ok(())
+}
+
+fn variant_of(path: TokenStream2, ident: &Ident, fields: &Fields) -> Variant {
+    let name = ident.to_string();
+    let snake = to_snake_case(&name);
+    let style = match fields {
+        Fields::Named(_) => Style::Named,
+        Fields::Unnamed(_) => Style::Unnamed,
+        Fields::Unit => Style::Unit,
+    };
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let suffix = match &f.ident {
+                Some(member) => member.to_string(),
+                None => i.to_string(),
+            };
+            Field {
+                member: f.ident.clone(),
+                ty: f.ty.clone(),
+                accessor: format_ident!("{}_{}", snake, suffix.trim_start_matches("r#")),
+            }
+        })
+        .collect();
+    Variant {
+        path,
+        name,
+        snake,
+        span: ident.span(),
+        fields,
+        style,
+    }
+}
+
+fn generate(input: &DeriveInput, variants: &[Variant]) -> TokenStream2 {
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let name = ident.to_string();
+    let sort_ident = format_ident!("{}Sort", ident);
+    let field_trait = quote!(::z3::datatype_builder::DatatypeField<'ctx>);
+
+    let builder_variants = variants.iter().map(|v| {
+        let vname = &v.name;
+        let fields = v.fields.iter().map(|f| {
+            let ty = &f.ty;
+            let accessor = f.accessor.to_string();
+            quote!((#accessor, <#ty as #field_trait>::accessor(ctx)))
+        });
+        quote!(.variant(#vname, vec![#(#fields),*]))
+    });
+    let field_types = variants.iter().flat_map(|v| v.fields.iter().map(|f| &f.ty));
+
+    let decode_arms = variants.iter().map(|v| {
+        let vname = &v.name;
+        let path = &v.path;
+        let values = v.fields.iter().enumerate().map(|(i, f)| {
+            let ty = &f.ty;
+            quote!(<#ty as #field_trait>::from_value(args.get(#i)?)?)
+        });
+        let value = match v.style {
+            Style::Named => {
+                let members = v.fields.iter().map(|f| &f.member);
+                quote!(#path { #(#members: #values),* })
+            }
+            Style::Unnamed => quote!(#path(#(#values),*)),
+            Style::Unit => quote!(#path),
+        };
+        let arity = v.fields.len();
+        quote!(#vname if args.len() == #arity => ::std::option::Option::Some(#value),)
+    });
+
+    let methods = variants.iter().enumerate().map(|(j, v)| {
+        let ctor = method_ident(&v.snake);
+        let tester = format_ident!("is_{}", v.snake);
+        let args: Vec<Ident> = v
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| match &f.member {
+                Some(member) => member.clone(),
+                None => format_ident!("arg{}", i),
+            })
+            .collect();
+        let arg_types = v.fields.iter().map(|f| {
+            let ty = &f.ty;
+            quote!(&<#ty as #field_trait>::Ast)
+        });
+        let ctor_doc = format!("Construct a `{}` value.", v.name);
+        let tester_doc = format!("Check whether `value` is a `{}` value.", v.name);
+        let accessors = v.fields.iter().enumerate().map(|(k, f)| {
+            let accessor = &f.accessor;
+            let ty = &f.ty;
+            let doc = format!(
+                "Get field `{}` of a `{}` value.",
+                f.member.as_ref().map_or(k.to_string(), |m| m.to_string()),
+                v.name
+            );
+            quote! {
+                #[doc = #doc]
+                pub fn #accessor(&self, value: &::z3::ast::Datatype<'ctx>) -> <#ty as #field_trait>::Ast {
+                    let field = self.sort.variants[#j].accessors[#k].apply(&[value]);
+                    ::std::convert::TryFrom::try_from(field).unwrap()
+                }
+            }
+        });
+        quote! {
+            #[doc = #ctor_doc]
+            pub fn #ctor(&self, #(#args: #arg_types),*) -> ::z3::ast::Datatype<'ctx> {
+                self.sort.variants[#j]
+                    .constructor
+                    .apply(&[#(#args as &dyn ::z3::ast::Ast<'ctx>),*])
+                    .as_datatype()
+                    .unwrap()
+            }
+
+            #[doc = #tester_doc]
+            pub fn #tester(&self, value: &::z3::ast::Datatype<'ctx>) -> ::z3::ast::Bool<'ctx> {
+                self.sort.variants[#j].tester.apply(&[value]).as_bool().unwrap()
+            }
+
+            #(#accessors)*
+        }
+    });
+
+    let sort_doc = format!(
+        "The Z3 datatype sort of [`{}`], with typed constructors, testers and accessors.",
+        name
+    );
+
+    quote! {
+        impl<'ctx> #field_trait for #ident {
+            type Ast = ::z3::ast::Datatype<'ctx>;
+
+            fn accessor(ctx: &'ctx ::z3::Context) -> ::z3::DatatypeAccessor<'ctx> {
+                ::z3::DatatypeAccessor::Datatype(#name.into())
+            }
+
+            fn collect_builders(
+                ctx: &'ctx ::z3::Context,
+                builders: &mut ::std::vec::Vec<::z3::DatatypeBuilder<'ctx>>,
+            ) {
+                if let ::std::option::Option::Some(builder) =
+                    builders.iter().find(|b| *b.name() == ::z3::Symbol::from(#name))
+                {
+                    assert!(
+                        builder.is_derived_from::<Self>(),
+                        "two different types named `{}` used in one Z3 datatype",
+                        #name
+                    );
+                    return;
+                }
+                builders.push(
+                    ::z3::DatatypeBuilder::new(ctx, #name)
+                        .derived_from::<Self>()
+                        #(#builder_variants)*
+                );
+                #(<#field_types as #field_trait>::collect_builders(ctx, builders);)*
+            }
+
+            fn from_value(value: &::z3::ast::Dynamic<'ctx>) -> ::std::option::Option<Self> {
+                use ::z3::ast::Ast;
+                let decl = value.safe_decl().ok()?;
+                let args = value.children();
+                match decl.name().as_str() {
+                    #(#decode_arms)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl<'ctx> ::z3::FromModel<'ctx> for #ident {
+            fn from_model(
+                _model: &::z3::Model<'ctx>,
+                value: &::z3::ast::Dynamic<'ctx>,
+            ) -> ::std::result::Result<Self, ::z3::FromModelError> {
+                <#ident as #field_trait>::from_value(value)
+                    .ok_or_else(|| ::z3::FromModelError::NotALiteral(value.to_string()))
+            }
+        }
+
+        #[doc = #sort_doc]
+        #[derive(Clone, Debug)]
+        #vis struct #sort_ident<'ctx> {
+            pub sort: ::z3::DatatypeSort<'ctx>,
+        }
+
+        impl<'ctx> #sort_ident<'ctx> {
+            /// Create the sort, along with the sorts of the datatypes it
+            /// refers to.
+            pub fn new(ctx: &'ctx ::z3::Context) -> Self {
+                let sorts = ::z3::datatype_builder::create_derived_datatypes::<#ident>(ctx);
+                Self::from_sorts(&sorts).unwrap()
+            }
+
+            /// Pick the sort out of sorts created together, or return `None`
+            /// if it is not among them.
+            pub fn from_sorts(sorts: &[::z3::DatatypeSort<'ctx>]) -> ::std::option::Option<Self> {
+                sorts
+                    .iter()
+                    .find(|s| s.sort.to_string() == #name)
+                    .map(|s| Self { sort: s.clone() })
+            }
+
+            /// Convert the value of `value` in `model` back into a Rust
+            /// value, or return `None` if it is not fully determined.
+            pub fn decode(
+                &self,
+                model: &::z3::Model<'ctx>,
+                value: &::z3::ast::Datatype<'ctx>,
+            ) -> ::std::option::Option<#ident> {
+                let value = model.eval(value, true)?;
+                <#ident as #field_trait>::from_value(&value.into())
+            }
+
+            #(#methods)*
+        }
+    }
+}
+
+/// Make an identifier for a generated method, using a raw identifier if
+/// `name` is a keyword.
+fn method_ident(name: &str) -> Ident {
+    match syn::parse_str::<Ident>(name) {
+        Ok(ident) => ident,
+        Err(_) => Ident::new_raw(name, Span::call_site()),
+    }
+}
+
+/// Convert a `CamelCase` identifier to `snake_case`, keeping acronyms
+/// together, e.g. `HTTPRequest` becomes `http_request`.
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.trim_start_matches("r#").chars().collect();
+    let mut snake = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let after_lower = matches!(prev, Some(p) if p.is_lowercase() || p.is_numeric());
+            let acronym_end = matches!(prev, Some(p) if p.is_uppercase())
+                && matches!(next, Some(n) if n.is_lowercase());
+            if after_lower || acronym_end {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
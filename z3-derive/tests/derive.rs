@@ -0,0 +1,153 @@
+use z3::ast::{Ast, Bool, Int};
+use z3::{Config, Context, SatResult, Solver};
+use z3_derive::Z3Datatype;
+
+#[derive(Clone, Debug, PartialEq, Z3Datatype)]
+struct Point {
+    x: i64,
+    y: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Z3Datatype)]
+enum Shape {
+    Circle { center: Point, radius: u32 },
+    Polygon(Point, Point, Point),
+    Empty,
+}
+
+#[derive(Clone, Debug, PartialEq, Z3Datatype)]
+enum Tree {
+    Leaf(String),
+    Node(Box<Forest>),
+}
+
+#[derive(Clone, Debug, PartialEq, Z3Datatype)]
+enum Forest {
+    Nil,
+    Cons(Box<Tree>, Box<Forest>),
+}
+
+#[derive(Clone, Debug, PartialEq, Z3Datatype)]
+enum HTTPStatus {
+    Ok,
+    NotFound { permanent: bool },
+}
+
+#[test]
+fn test_derive_struct_and_enum() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let sorts = z3::datatype_builder::create_derived_datatypes::<Shape>(&ctx);
+    let shapes = ShapeSort::from_sorts(&sorts).unwrap();
+    let points = PointSort::from_sorts(&sorts).unwrap();
+    let int = |i| Int::from_i64(&ctx, i);
+
+    let x = Int::new_const(&ctx, "x");
+    let p = points.point(&x, &int(3));
+    let origin = points.point(&int(0), &int(0));
+    let circle = shapes.circle(&p, &int(5));
+    let empty = shapes.empty();
+
+    let solver = Solver::new(&ctx);
+    solver.assert(&shapes.is_circle(&circle));
+    solver.assert(&shapes.is_empty(&empty));
+    solver.assert(&shapes.is_polygon(&empty).not());
+    solver.assert(&points.point_x(&shapes.circle_center(&circle))._eq(&int(-7)));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+
+    let expected = Shape::Circle {
+        center: Point { x: -7, y: 3 },
+        radius: 5,
+    };
+    assert_eq!(shapes.decode(&model, &circle), Some(expected.clone()));
+    assert_eq!(model.get::<Shape>(&circle), Ok(expected));
+    assert_eq!(shapes.decode(&model, &empty), Some(Shape::Empty));
+    let triangle = shapes.polygon(&origin, &p, &origin);
+    assert_eq!(
+        shapes.decode(&model, &triangle),
+        Some(Shape::Polygon(
+            Point { x: 0, y: 0 },
+            Point { x: -7, y: 3 },
+            Point { x: 0, y: 0 }
+        ))
+    );
+
+    // A field value that does not fit in the Rust field type.
+    let too_big = points.point(&int(0), &int(256));
+    assert_eq!(points.decode(&model, &too_big), None);
+}
+
+#[test]
+fn test_derive_mutually_recursive() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let sorts = z3::datatype_builder::create_derived_datatypes::<Tree>(&ctx);
+    assert_eq!(sorts.len(), 2);
+    let trees = TreeSort::from_sorts(&sorts).unwrap();
+    let forests = ForestSort::from_sorts(&sorts).unwrap();
+
+    let s = z3::ast::String::new_const(&ctx, "s");
+    let leaf = trees.leaf(&s);
+    let forest = forests.cons(&leaf, &forests.cons(&leaf, &forests.nil()));
+    let tree = trees.node(&forest);
+
+    let solver = Solver::new(&ctx);
+    solver.assert(
+        &trees
+            .leaf_0(&forests.cons_0(&forest))
+            ._eq(&z3::ast::String::from_str(&ctx, "a").unwrap()),
+    );
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+
+    let leaf = Box::new(Tree::Leaf("a".to_string()));
+    let expected = Tree::Node(Box::new(Forest::Cons(
+        leaf.clone(),
+        Box::new(Forest::Cons(leaf, Box::new(Forest::Nil))),
+    )));
+    assert_eq!(trees.decode(&model, &tree), Some(expected));
+}
+
+#[test]
+fn test_derive_names() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let statuses = HTTPStatusSort::new(&ctx);
+    let status = statuses.not_found(&Bool::from_bool(&ctx, true));
+    assert_eq!(statuses.sort.sort.to_string(), "HTTPStatus");
+    assert_eq!(statuses.sort.variants[1].constructor.name(), "NotFound");
+    assert_eq!(
+        statuses.not_found_permanent(&status).simplify().as_bool(),
+        Some(true)
+    );
+    assert_eq!(statuses.is_ok(&status).simplify().as_bool(), Some(false));
+}
+
+mod a {
+    #[derive(Clone, Debug, PartialEq, z3_derive::Z3Datatype)]
+    pub struct Point {
+        pub x: i64,
+    }
+}
+
+mod b {
+    #[derive(Clone, Debug, PartialEq, z3_derive::Z3Datatype)]
+    pub struct Point {
+        pub y: bool,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Z3Datatype)]
+struct Segment {
+    from: a::Point,
+    to: b::Point,
+}
+
+#[test]
+#[should_panic(expected = "two different types named `Point`")]
+fn test_derive_same_name_rejected() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    SegmentSort::new(&ctx);
+}
@@ -295,11 +295,25 @@ pub struct _Z3_rcf_num {
 }
 pub type Z3_rcf_num = *mut _Z3_rcf_num;
 
+#[doc(hidden)]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _Z3_solver_callback {
+    _unused: [u8; 0],
+}
+/// Handle passed to user propagator callbacks, used to register terms and
+/// propagate consequences from within a callback.
+pub type Z3_solver_callback = *mut _Z3_solver_callback;
+
 /// Z3 string type. It is just an alias for `const char *`.
 pub type Z3_string = *const ::std::os::raw::c_char;
 
 pub type Z3_string_ptr = *mut Z3_string;
 
+/// Pointer to a buffer of characters that may contain embedded NUL
+/// characters, as returned by [`Z3_get_lstring`].
+pub type Z3_char_ptr = *const ::std::os::raw::c_char;
+
 pub const Z3_L_FALSE: Z3_lbool = -1;
 pub const Z3_L_UNDEF: Z3_lbool = 0;
 pub const Z3_L_TRUE: Z3_lbool = 1;
@@ -3031,22 +3045,102 @@ extern "C" {
     /// Each character is 8 bits.
     pub fn Z3_mk_string_sort(c: Z3_context) -> Z3_sort;
 
+    /// Create a sort for unicode characters.
+    ///
+    /// The sort for characters can be changed to ASCII by setting
+    /// the global parameter `encoding` to `ascii`, or alternative
+    /// to 16 bit characters by setting `encoding` to `bmp`.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_char_sort(c: Z3_context) -> Z3_sort;
+
+    /// Check if `s` is a character sort.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_is_char_sort(c: Z3_context, s: Z3_sort) -> bool;
+
     /// Check if `s` is a string sort.
     pub fn Z3_is_string_sort(c: Z3_context, s: Z3_sort) -> bool;
 
     /// Create a string constant out of the string that is passed in
+    ///
+    /// The string may contain escape encoding for non-printable characters
+    /// or characters outside of the basic printable ASCII range. For example,
+    /// the escape encoding `\u{0}` represents the character 0 and the encoding
+    /// `\u{100}` represents the character 256.
     pub fn Z3_mk_string(c: Z3_context, s: Z3_string) -> Z3_ast;
 
+    /// Create a string constant out of the string that is passed in
+    /// It takes the length of the string as well to take into account
+    /// 0 characters. The string is treated as if it is unescaped, so
+    /// each byte is one character.
+    pub fn Z3_mk_lstring(c: Z3_context, len: ::std::os::raw::c_uint, s: Z3_string) -> Z3_ast;
+
+    /// Create a string constant out of the array of unicode code points
+    /// `chars`, which has length `len`.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_u32string(
+        c: Z3_context,
+        len: ::std::os::raw::c_uint,
+        chars: *const ::std::os::raw::c_uint,
+    ) -> Z3_ast;
+
     /// Determine if `s` is a string constant.
     pub fn Z3_is_string(c: Z3_context, s: Z3_ast) -> bool;
 
     /// Retrieve the string constant stored in `s`.
     ///
+    /// Characters outside the basic printable ASCII range are escaped
+    /// using the `\u{...}` encoding.
+    ///
     /// # Preconditions:
     ///
     /// - `Z3_is_string(c, s)`
     pub fn Z3_get_string(c: Z3_context, s: Z3_ast) -> Z3_string;
 
+    /// Retrieve the unescaped string constant stored in `s`, whose
+    /// length is written to `length`.
+    ///
+    /// # Preconditions:
+    ///
+    /// - `Z3_is_string(c, s)`
+    pub fn Z3_get_lstring(
+        c: Z3_context,
+        s: Z3_ast,
+        length: *mut ::std::os::raw::c_uint,
+    ) -> Z3_char_ptr;
+
+    /// Return the number of characters of the string constant `s`.
+    ///
+    /// # Preconditions:
+    ///
+    /// - `Z3_is_string(c, s)`
+    ///
+    /// Requires Z3 4.8.16 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_get_string_length(c: Z3_context, s: Z3_ast) -> ::std::os::raw::c_uint;
+
+    /// Retrieve the code points of the string constant `s` into
+    /// `contents`, which has room for `length` of them.
+    ///
+    /// # Preconditions:
+    ///
+    /// - `Z3_is_string(c, s)`
+    /// - `length` is [`Z3_get_string_length`]`(c, s)`
+    ///
+    /// Requires Z3 4.8.16 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_get_string_contents(
+        c: Z3_context,
+        s: Z3_ast,
+        length: ::std::os::raw::c_uint,
+        contents: *mut ::std::os::raw::c_uint,
+    );
+
     /// Create an empty sequence of the sequence sort `seq`.
     ///
     /// # Preconditions:
@@ -3110,6 +3204,73 @@ extern "C" {
     /// The function is under-specified if `offset` is negative or larger than the length of `s`.
     pub fn Z3_mk_seq_index(c: Z3_context, s: Z3_ast, substr: Z3_ast, offset: Z3_ast) -> Z3_ast;
 
+    /// Return index of the last occurrence of `substr` in `s`.
+    /// If `s` does not contain `substr`, then the value is -1.
+    pub fn Z3_mk_seq_last_index(c: Z3_context, s: Z3_ast, substr: Z3_ast) -> Z3_ast;
+
+    /// Replace all occurrences of `src` with `dst` in `s`.
+    ///
+    /// Requires Z3 4.8.16 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_seq_replace_all(c: Z3_context, s: Z3_ast, src: Z3_ast, dst: Z3_ast) -> Z3_ast;
+
+    /// Check if `s1` is lexicographically strictly less than `s2`.
+    pub fn Z3_mk_str_lt(c: Z3_context, s1: Z3_ast, s2: Z3_ast) -> Z3_ast;
+
+    /// Check if `s1` is equal or lexicographically strictly less than `s2`.
+    pub fn Z3_mk_str_le(c: Z3_context, s1: Z3_ast, s2: Z3_ast) -> Z3_ast;
+
+    /// String to code conversion: the code point of a string of length 1,
+    /// or -1 otherwise.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_string_to_code(c: Z3_context, a: Z3_ast) -> Z3_ast;
+
+    /// Code to string conversion: the string of length 1 holding the
+    /// character with code point `a`, or the empty string if `a` is not a
+    /// valid code point.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_string_from_code(c: Z3_context, a: Z3_ast) -> Z3_ast;
+
+    /// Create a character literal.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_char(c: Z3_context, ch: ::std::os::raw::c_uint) -> Z3_ast;
+
+    /// Create less than or equal to between two characters.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_char_le(c: Z3_context, ch1: Z3_ast, ch2: Z3_ast) -> Z3_ast;
+
+    /// Create an integer (code point) from character.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_char_to_int(c: Z3_context, ch: Z3_ast) -> Z3_ast;
+
+    /// Create a bit-vector (code point) from character.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_char_to_bv(c: Z3_context, ch: Z3_ast) -> Z3_ast;
+
+    /// Create a character from a bit-vector (code point).
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_char_from_bv(c: Z3_context, bv: Z3_ast) -> Z3_ast;
+
+    /// Create a check if the character is a digit.
+    ///
+    /// Requires Z3 4.8.13 or later.
+    #[cfg(feature = "unicode")]
+    pub fn Z3_mk_char_is_digit(c: Z3_context, ch: Z3_ast) -> Z3_ast;
+
     /// Convert string to integer.
     pub fn Z3_mk_str_to_int(c: Z3_context, s: Z3_ast) -> Z3_ast;
 
@@ -5688,6 +5849,197 @@ extern "C" {
         vars: Z3_ast_vector,
         backtrack_level: ::std::os::raw::c_uint,
     ) -> Z3_ast_vector;
+}
+// The user propagator bindings follow Z3 4.13 and later, whose callbacks
+// get terms rather than the numeric ids of earlier versions. They are only
+// declared with the `user-propagator` feature, as older Z3 libraries lack
+// some of the functions and use an incompatible ABI for the others.
+
+/// User propagator callback invoked when the solver creates a backtracking point.
+#[cfg(feature = "user-propagator")]
+pub type Z3_push_eh = ::std::option::Option<
+    unsafe extern "C" fn(ctx: *mut ::std::os::raw::c_void, cb: Z3_solver_callback),
+>;
+/// User propagator callback invoked when the solver backtracks `num_scopes` points.
+#[cfg(feature = "user-propagator")]
+pub type Z3_pop_eh = ::std::option::Option<
+    unsafe extern "C" fn(
+        ctx: *mut ::std::os::raw::c_void,
+        cb: Z3_solver_callback,
+        num_scopes: ::std::os::raw::c_uint,
+    ),
+>;
+/// User propagator callback invoked when the solver is copied into
+/// `new_context`. Returns the user context to use for the copy.
+#[cfg(feature = "user-propagator")]
+pub type Z3_fresh_eh = ::std::option::Option<
+    unsafe extern "C" fn(
+        ctx: *mut ::std::os::raw::c_void,
+        new_context: Z3_context,
+    ) -> *mut ::std::os::raw::c_void,
+>;
+/// User propagator callback invoked when a registered term `t` is fixed to `value`.
+#[cfg(feature = "user-propagator")]
+pub type Z3_fixed_eh = ::std::option::Option<
+    unsafe extern "C" fn(
+        ctx: *mut ::std::os::raw::c_void,
+        cb: Z3_solver_callback,
+        t: Z3_ast,
+        value: Z3_ast,
+    ),
+>;
+/// User propagator callback invoked when two registered terms become equal
+/// (or, for [`Z3_solver_propagate_diseq`], distinct).
+#[cfg(feature = "user-propagator")]
+pub type Z3_eq_eh = ::std::option::Option<
+    unsafe extern "C" fn(
+        ctx: *mut ::std::os::raw::c_void,
+        cb: Z3_solver_callback,
+        s: Z3_ast,
+        t: Z3_ast,
+    ),
+>;
+/// User propagator callback invoked on final check, once all registered
+/// terms have been assigned.
+#[cfg(feature = "user-propagator")]
+pub type Z3_final_eh = ::std::option::Option<
+    unsafe extern "C" fn(ctx: *mut ::std::os::raw::c_void, cb: Z3_solver_callback),
+>;
+/// User propagator callback invoked when the solver creates a term whose
+/// head was declared with [`Z3_solver_propagate_declare`].
+#[cfg(feature = "user-propagator")]
+pub type Z3_created_eh = ::std::option::Option<
+    unsafe extern "C" fn(ctx: *mut ::std::os::raw::c_void, cb: Z3_solver_callback, t: Z3_ast),
+>;
+/// User propagator callback invoked when the solver is about to split on
+/// bit `idx` of the registered term `t`, with the given `phase`.
+#[cfg(feature = "user-propagator")]
+pub type Z3_decide_eh = ::std::option::Option<
+    unsafe extern "C" fn(
+        ctx: *mut ::std::os::raw::c_void,
+        cb: Z3_solver_callback,
+        t: Z3_ast,
+        idx: ::std::os::raw::c_uint,
+        phase: bool,
+    ),
+>;
+extern "C" {
+    /// Register a user-propagator with the solver.
+    ///
+    /// `user_context` is passed back to every callback.
+    ///
+    /// # See also:
+    ///
+    /// - [`Z3_solver_propagate_fixed`]
+    /// - [`Z3_solver_propagate_eq`]
+    /// - [`Z3_solver_propagate_final`]
+    /// - [`Z3_solver_propagate_register`]
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_init(
+        c: Z3_context,
+        s: Z3_solver,
+        user_context: *mut ::std::os::raw::c_void,
+        push_eh: Z3_push_eh,
+        pop_eh: Z3_pop_eh,
+        fresh_eh: Z3_fresh_eh,
+    );
+
+    /// Register a callback for when an expression is bound to a fixed value.
+    ///
+    /// The supported expression types are
+    /// - Booleans
+    /// - Bit-vectors
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_fixed(c: Z3_context, s: Z3_solver, fixed_eh: Z3_fixed_eh);
+
+    /// Register a callback on final check.
+    ///
+    /// This provides freedom to the propagator to delay actions or implement
+    /// a branch-and-bound solver. The callback may use the callback context to
+    /// invoke [`Z3_solver_propagate_consequence`]; if it does, the solver
+    /// continues.
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_final(c: Z3_context, s: Z3_solver, final_eh: Z3_final_eh);
+
+    /// Register a callback on expression equalities.
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_eq(c: Z3_context, s: Z3_solver, eq_eh: Z3_eq_eh);
+
+    /// Register a callback on expression dis-equalities.
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_diseq(c: Z3_context, s: Z3_solver, eq_eh: Z3_eq_eh);
+
+    /// Register a callback when a new expression with a registered function
+    /// is used by the solver.
+    ///
+    /// The registered function appears at the top level and is created using
+    /// [`Z3_solver_propagate_declare`].
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_created(c: Z3_context, s: Z3_solver, created_eh: Z3_created_eh);
+
+    /// Register a callback when the solver decides to split on a registered
+    /// expression.
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_decide(c: Z3_context, s: Z3_solver, decide_eh: Z3_decide_eh);
+
+    /// Set the next decision of the solver to bit `idx` of `t` with the given
+    /// `phase`, from within a callback.
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_next_split(
+        c: Z3_context,
+        cb: Z3_solver_callback,
+        t: Z3_ast,
+        idx: ::std::os::raw::c_uint,
+        phase: Z3_lbool,
+    ) -> bool;
+
+    /// Create a function symbol whose applications are handled by the user
+    /// propagator.
+    ///
+    /// Terms built from it are reported through the callback registered
+    /// with [`Z3_solver_propagate_created`].
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_declare(
+        c: Z3_context,
+        name: Z3_symbol,
+        n: ::std::os::raw::c_uint,
+        domain: *mut Z3_sort,
+        range: Z3_sort,
+    ) -> Z3_func_decl;
+
+    /// Register an expression to propagate on with the solver.
+    ///
+    /// Only expressions of type Bool and type Bit-Vector can be registered
+    /// for propagation.
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_register(c: Z3_context, s: Z3_solver, e: Z3_ast);
+
+    /// Register an expression to propagate on with the solver, from within
+    /// a callback.
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_register_cb(c: Z3_context, cb: Z3_solver_callback, e: Z3_ast);
+
+    /// Propagate a consequence based on fixed values and equalities.
+    ///
+    /// This is a callback a client may invoke during the fixed, eq, created
+    /// and final callbacks. It adds the propagation `conseq`, justified by the
+    /// registered terms in `fixed` having their current values and by the
+    /// equalities `eq_lhs[i] = eq_rhs[i]`. Propagating `false` raises a
+    /// conflict.
+    ///
+    /// Returns `true` if the consequence was accepted, and `false` if the
+    /// solver is already in a conflict and the propagator should return.
+    #[cfg(feature = "user-propagator")]
+    pub fn Z3_solver_propagate_consequence(
+        c: Z3_context,
+        cb: Z3_solver_callback,
+        num_fixed: ::std::os::raw::c_uint,
+        fixed: *const Z3_ast,
+        num_eqs: ::std::os::raw::c_uint,
+        eq_lhs: *const Z3_ast,
+        eq_rhs: *const Z3_ast,
+        conseq: Z3_ast,
+    ) -> bool;
 
     /// Retrieve the model for the last [`Z3_solver_check`]
     ///
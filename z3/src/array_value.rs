@@ -0,0 +1,171 @@
+use z3_sys::*;
+
+use crate::ast::{Array, Ast, Dynamic, Set};
+use crate::{FuncDecl, Model, Term};
+
+/// Walk the value of an array in `model`, following `store` chains down to
+/// a constant array or an `as-array` function interpretation.
+///
+/// Returns the default value and the `(index, value)` exceptions to it,
+/// outermost first, or `None` if `array` does not evaluate to one of those
+/// shapes.
+pub(crate) fn decode_array<'ctx>(
+    model: &Model<'ctx>,
+    array: &Dynamic<'ctx>,
+) -> Option<(Dynamic<'ctx>, Vec<(Dynamic<'ctx>, Dynamic<'ctx>)>)> {
+    let ctx = array.get_ctx();
+    let mut entries: Vec<(Dynamic<'ctx>, Dynamic<'ctx>)> = Vec::new();
+    // Inner stores are shadowed by outer ones on the same index.
+    let push = |entries: &mut Vec<(Dynamic<'ctx>, Dynamic<'ctx>)>, index: Dynamic<'ctx>, value| {
+        if !entries.iter().any(|(i, _)| *i == index) {
+            entries.push((index, value));
+        }
+    };
+    let mut term = model.eval(array, true)?;
+    loop {
+        if unsafe { Z3_is_as_array(ctx.z3_ctx, term.get_z3_ast()) } {
+            let f = unsafe {
+                FuncDecl::wrap(
+                    ctx,
+                    Z3_get_as_array_func_decl(ctx.z3_ctx, term.get_z3_ast()),
+                )
+            };
+            let interp = model.get_func_interp(&f)?;
+            for entry in interp.get_entries() {
+                let mut args = entry.get_args();
+                if args.len() != 1 {
+                    return None;
+                }
+                push(&mut entries, args.remove(0), entry.get_value());
+            }
+            return Some((interp.get_else(), entries));
+        }
+        match term.view() {
+            Term::Store(inner, index, value) => {
+                push(&mut entries, index, value);
+                term = inner.into();
+            }
+            Term::ConstArray(default) => return Some((default, entries)),
+            _ => return None,
+        }
+    }
+}
+
+/// The concrete value of an array in a model, as returned by
+/// [`Array::decode()`]: every index maps to `default`, except for those
+/// listed in `entries`.
+///
+/// Each index appears at most once in `entries`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArrayValue<'ctx> {
+    pub default: Dynamic<'ctx>,
+    pub entries: Vec<(Dynamic<'ctx>, Dynamic<'ctx>)>,
+}
+
+impl<'ctx> ArrayValue<'ctx> {
+    /// Return the value at `index`, which should be a value of the array's
+    /// domain sort as found in `entries`.
+    pub fn get(&self, index: &Dynamic<'ctx>) -> &Dynamic<'ctx> {
+        self.entries
+            .iter()
+            .find(|(i, _)| i == index)
+            .map_or(&self.default, |(_, v)| v)
+    }
+}
+
+impl<'ctx> Array<'ctx> {
+    /// Decode the value of this array in `model` into its default value and
+    /// the indices where it differs from it.
+    ///
+    /// `as-array` values are decoded through the [`FuncInterp`](crate::FuncInterp)
+    /// of their function. Values of arrays nested in the range can be
+    /// decoded in turn with [`Dynamic::as_array()`] and `decode`.
+    ///
+    /// Returns `None` if the value is not a finite combination of `store`,
+    /// `const` and `as-array` terms, e.g. a `lambda`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Array, Ast, Dynamic, Int}, Config, Context, SatResult, Solver, Sort};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let int = |i| Int::from_i64(&ctx, i);
+    /// let heap = Array::new_const(&ctx, "heap", &Sort::int(&ctx), &Sort::int(&ctx));
+    /// solver.assert(&heap._eq(&Array::const_array(&ctx, &Sort::int(&ctx), &int(0)).store(&int(8), &int(42))));
+    /// assert_eq!(solver.check(), SatResult::Sat);
+    ///
+    /// let value = heap.decode(&solver.get_model().unwrap()).unwrap();
+    /// assert_eq!(value.default, Dynamic::from(int(0)));
+    /// assert_eq!(value.entries, vec![(int(8).into(), int(42).into())]);
+    /// assert_eq!(value.get(&int(8).into()), &Dynamic::from(int(42)));
+    /// ```
+    pub fn decode(&self, model: &Model<'ctx>) -> Option<ArrayValue<'ctx>> {
+        let (default, entries) = decode_array(model, &Dynamic::from(self))?;
+        Some(ArrayValue { default, entries })
+    }
+}
+
+impl<'ctx> Set<'ctx> {
+    /// Decode the value of this set in `model` into the list of its
+    /// elements.
+    ///
+    /// Returns `None` if the set is co-finite, i.e. contains every element
+    /// of its domain except finitely many (see [`Set::non_elements()`]), or
+    /// if its value is not a finite combination of `store`, `const` and
+    /// `as-array` terms.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int, Set}, Config, Context, SatResult, Solver, Sort};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let s = Set::new_const(&ctx, "s", &Sort::int(&ctx));
+    /// let one = Int::from_i64(&ctx, 1);
+    /// let two = Int::from_i64(&ctx, 2);
+    /// solver.assert(&s._eq(&Set::empty(&ctx, &Sort::int(&ctx)).add(&one).add(&two)));
+    /// assert_eq!(solver.check(), SatResult::Sat);
+    ///
+    /// let model = solver.get_model().unwrap();
+    /// let mut elements = s.elements(&model).unwrap();
+    /// elements.sort_by_key(|e| e.as_int().unwrap().as_i64());
+    /// assert_eq!(elements, vec![one.into(), two.into()]);
+    /// ```
+    pub fn elements(&self, model: &Model<'ctx>) -> Option<Vec<Dynamic<'ctx>>> {
+        self.members_with_default(model, false)
+    }
+
+    /// Decode the value of a co-finite set in `model` into the list of the
+    /// elements of its domain it does not contain.
+    ///
+    /// Returns `None` if the set is finite (see [`Set::elements()`]), or if
+    /// its value is not a finite combination of `store`, `const` and
+    /// `as-array` terms.
+    pub fn non_elements(&self, model: &Model<'ctx>) -> Option<Vec<Dynamic<'ctx>>> {
+        self.members_with_default(model, true)
+    }
+
+    /// Return the indices that map to `!default` if the set's default
+    /// membership is `default`.
+    fn members_with_default(
+        &self,
+        model: &Model<'ctx>,
+        default: bool,
+    ) -> Option<Vec<Dynamic<'ctx>>> {
+        let (else_value, entries) = decode_array(model, &Dynamic::from(self))?;
+        if else_value.as_bool()?.as_bool()? != default {
+            return None;
+        }
+        entries
+            .into_iter()
+            .filter_map(
+                |(index, value)| match value.as_bool().and_then(|b| b.as_bool()) {
+                    Some(member) if member == default => None,
+                    Some(_) => Some(Some(index)),
+                    None => Some(None),
+                },
+            )
+            .collect()
+    }
+}
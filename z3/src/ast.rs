@@ -11,7 +11,7 @@ use std::hash::{Hash, Hasher};
 pub use z3_sys::AstKind;
 use z3_sys::*;
 
-use crate::{Context, FuncDecl, IsNotApp, Pattern, Sort, SortDiffers, Symbol};
+use crate::{Context, Error, FuncDecl, IsNotApp, Pattern, Sort, SortDiffers, Symbol, Term};
 
 use num::{bigint::BigInt, rational::BigRational};
 
@@ -39,12 +39,27 @@ pub struct Float<'ctx> {
     pub(crate) z3_ast: Z3_ast,
 }
 
+/// [`Ast`] node representing an IEEE-754 rounding mode.
+pub struct RoundingMode<'ctx> {
+    pub(crate) ctx: &'ctx Context,
+    pub(crate) z3_ast: Z3_ast,
+}
+
 /// [`Ast`] node representing a string value.
 pub struct String<'ctx> {
     pub(crate) ctx: &'ctx Context,
     pub(crate) z3_ast: Z3_ast,
 }
 
+/// [`Ast`] node representing a Unicode character.
+///
+/// Requires the `unicode` feature.
+#[cfg(feature = "unicode")]
+pub struct Char<'ctx> {
+    pub(crate) ctx: &'ctx Context,
+    pub(crate) z3_ast: Z3_ast,
+}
+
 /// [`Ast`] node representing a bitvector value.
 pub struct BV<'ctx> {
     pub(crate) ctx: &'ctx Context,
@@ -76,6 +91,19 @@ pub struct Datatype<'ctx> {
     pub(crate) z3_ast: Z3_ast,
 }
 
+/// [`Ast`] node representing a quantifier (`forall`, `exists`) or a lambda.
+///
+/// The body of a quantifier refers to its bound variables using de Bruijn
+/// indices: the variable with index `0` is the one bound last. Use
+/// [`Quantifier::instantiate()`] to replace them.
+///
+/// Obtained from [`Dynamic::as_quantifier()`] when the AST's
+/// [`kind()`](Ast::kind) is [`AstKind::Quantifier`].
+pub struct Quantifier<'ctx> {
+    pub(crate) ctx: &'ctx Context,
+    pub(crate) z3_ast: Z3_ast,
+}
+
 /// A dynamically typed [`Ast`] node.
 pub struct Dynamic<'ctx> {
     pub(crate) ctx: &'ctx Context,
@@ -83,6 +111,11 @@ pub struct Dynamic<'ctx> {
 }
 
 /// [`Ast`] node representing a regular expression.
+///
+/// Regular expressions recognize sequences of one sort. Those built from
+/// `&str` and `char` recognize strings; use [`Seq::to_re()`],
+/// [`Regexp::full_for()`] and [`Regexp::empty_for()`] for other sequence
+/// sorts.
 /// ```
 /// use z3::ast;
 /// use z3::{Config, Context, Solver, SatResult};
@@ -131,7 +164,7 @@ macro_rules! unop {
 macro_rules! binop {
     (
         $(
-            $( #[ $attr:meta ] )* $f:ident ( $z3fn:ident, $retty:ty ) ;
+            $( #[ $attr:meta ] )* $f:ident $( , $try_f:ident )? ( $z3fn:ident, $retty:ty ) ;
         )*
     ) => {
         $(
@@ -144,6 +177,18 @@ macro_rules! binop {
                     })
                 }
             }
+
+            $(
+                #[doc = concat!("Fallible version of [`Self::", stringify!($f), "()`].")]
+                ///
+                /// Returns an [`Error`] if Z3 rejects the arguments, e.g. because their sorts differ.
+                pub fn $try_f(&self, other: &Self) -> Result<$retty, Error> {
+                    assert!(self.ctx == other.ctx);
+                    unsafe {
+                        try_wrap(self.ctx, $z3fn(self.ctx.z3_ctx, self.z3_ast, other.z3_ast))
+                    }
+                }
+            )?
         )*
     };
 }
@@ -151,7 +196,7 @@ macro_rules! binop {
 macro_rules! trinop {
     (
         $(
-            $( #[ $attr:meta ] )* $f:ident ( $z3fn:ident, $retty:ty ) ;
+            $( #[ $attr:meta ] )* $f:ident $( , $try_f:ident )? ( $z3fn:ident, $retty:ty ) ;
         )*
     ) => {
         $(
@@ -164,6 +209,18 @@ macro_rules! trinop {
                     })
                 }
             }
+
+            $(
+                #[doc = concat!("Fallible version of [`Self::", stringify!($f), "()`].")]
+                ///
+                /// Returns an [`Error`] if Z3 rejects the arguments, e.g. because their sorts differ.
+                pub fn $try_f(&self, a: &Self, b: &Self) -> Result<$retty, Error> {
+                    assert!((self.ctx == a.ctx) && (a.ctx == b.ctx));
+                    unsafe {
+                        try_wrap(self.ctx, $z3fn(self.ctx.z3_ctx, self.z3_ast, a.z3_ast, b.z3_ast))
+                    }
+                }
+            )?
         )*
     };
 }
@@ -171,7 +228,7 @@ macro_rules! trinop {
 macro_rules! varop {
     (
         $(
-            $( #[ $attr:meta ] )* $f:ident ( $z3fn:ident, $retty:ty ) ;
+            $( #[ $attr:meta ] )* $f:ident $( , $try_f:ident )? ( $z3fn:ident, $retty:ty ) ;
         )*
     ) => {
         $(
@@ -186,10 +243,36 @@ macro_rules! varop {
                     })
                 }
             }
+
+            $(
+                #[doc = concat!("Fallible version of [`Self::", stringify!($f), "()`].")]
+                ///
+                /// Returns an [`Error`] if Z3 rejects the arguments, e.g. because their sorts differ.
+                pub fn $try_f(ctx: &'ctx Context, values: &[impl Borrow<Self>]) -> Result<$retty, Error> {
+                    assert!(values.iter().all(|v| v.borrow().get_ctx().z3_ctx == ctx.z3_ctx));
+                    unsafe {
+                        let tmp: Vec<_> = values.iter().map(|x| x.borrow().z3_ast).collect();
+                        assert!(tmp.len() <= 0xffff_ffff);
+                        try_wrap(ctx, $z3fn(ctx.z3_ctx, tmp.len() as u32, tmp.as_ptr()))
+                    }
+                }
+            )?
         )*
     };
 }
 
+/// Wrap the result of the Z3 API call that was just made on `ctx`, or return
+/// the [`Error`] raised by that call.
+///
+/// # Safety
+///
+/// No other Z3 API call may have been made on `ctx` since the call producing
+/// `ast`, as every call resets Z3's error code.
+unsafe fn try_wrap<'ctx, T: Ast<'ctx>>(ctx: &'ctx Context, ast: Z3_ast) -> Result<T, Error> {
+    ctx.check_error()?;
+    Ok(T::wrap(ctx, ast))
+}
+
 /// Abstract syntax tree (AST) nodes represent terms, constants, or expressions.
 /// The `Ast` trait contains methods common to all AST subtypes.
 pub trait Ast<'ctx>: fmt::Debug {
@@ -332,6 +415,31 @@ pub trait Ast<'ctx>: fmt::Debug {
         }
     }
 
+    /// Replace the free de Bruijn variables of this `Ast` with `to`: the
+    /// variable with index `i` is replaced by `to[i]`.
+    ///
+    /// # See also:
+    ///
+    /// - [`Dynamic::new_bound()`]
+    /// - [`Quantifier::instantiate()`]
+    fn substitute_vars(&self, to: &[&dyn Ast<'ctx>]) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(to.iter().all(|a| a.get_ctx() == self.get_ctx()));
+        let to: Vec<_> = to.iter().map(|a| a.get_z3_ast()).collect();
+        unsafe {
+            Self::wrap(self.get_ctx(), {
+                Z3_substitute_vars(
+                    self.get_ctx().z3_ctx,
+                    self.get_z3_ast(),
+                    to.len().try_into().unwrap(),
+                    to.as_ptr(),
+                )
+            })
+        }
+    }
+
     /// Return the number of children of this `Ast`.
     ///
     /// Leaf nodes (eg `Bool` consts) will return 0.
@@ -410,6 +518,17 @@ pub trait Ast<'ctx>: fmt::Debug {
         }
     }
 
+    /// Return a typed view of this `Ast`'s top-level operator and its
+    /// arguments, suitable for `match`ing.
+    ///
+    /// See [`Term`] for the operators that are covered.
+    fn view(&self) -> Term<'ctx>
+    where
+        Self: Sized,
+    {
+        Term::of(&Dynamic::from_ast(self))
+    }
+
     fn translate<'src_ctx>(&'src_ctx self, dest: &'ctx Context) -> Self
     where
         Self: Sized,
@@ -551,8 +670,14 @@ impl_ast!(Real);
 impl_from_try_into_dynamic!(Real, as_real);
 impl_ast!(Float);
 impl_from_try_into_dynamic!(Float, as_float);
+impl_ast!(RoundingMode);
+impl_from_try_into_dynamic!(RoundingMode, as_rounding_mode);
 impl_ast!(String);
 impl_from_try_into_dynamic!(String, as_string);
+#[cfg(feature = "unicode")]
+impl_ast!(Char);
+#[cfg(feature = "unicode")]
+impl_from_try_into_dynamic!(Char, as_char);
 impl_ast!(BV);
 impl_from_try_into_dynamic!(BV, as_bv);
 impl_ast!(Array);
@@ -562,6 +687,7 @@ impl_from_try_into_dynamic!(Set, as_set);
 impl_ast!(Seq);
 impl_from_try_into_dynamic!(Seq, as_seq);
 impl_ast!(Regexp);
+impl_from_try_into_dynamic!(Regexp, as_regexp);
 
 impl<'ctx> Int<'ctx> {
     pub fn from_big_int(ctx: &'ctx Context, value: &BigInt) -> Int<'ctx> {
@@ -636,6 +762,9 @@ impl_from_try_into_dynamic!(Datatype, as_datatype);
 
 impl_ast!(Dynamic);
 
+impl_ast!(Quantifier);
+impl_from_try_into_dynamic!(Quantifier, as_quantifier);
+
 impl<'ctx> Bool<'ctx> {
     pub fn new_const<S: Into<Symbol>>(ctx: &'ctx Context, name: S) -> Bool<'ctx> {
         let sort = Sort::bool(ctx);
@@ -691,6 +820,21 @@ impl<'ctx> Bool<'ctx> {
         }
     }
 
+    /// Fallible version of [`Bool::ite()`], returning an [`Error`] if the
+    /// sorts of `a` and `b` differ.
+    pub fn try_ite<T>(&self, a: &T, b: &T) -> Result<T, Error>
+    where
+        T: Ast<'ctx>,
+    {
+        assert!(a.get_ctx() == self.ctx && b.get_ctx() == self.ctx);
+        unsafe {
+            try_wrap(
+                self.ctx,
+                Z3_mk_ite(self.ctx.z3_ctx, self.z3_ast, a.get_z3_ast(), b.get_z3_ast()),
+            )
+        }
+    }
+
     varop! {
         and(Z3_mk_and, Self);
         or(Z3_mk_or, Self);
@@ -1142,16 +1286,292 @@ impl<'ctx> Float<'ctx> {
         unary_neg(Z3_mk_fpa_neg, Self);
     }
     binop! {
-        lt(Z3_mk_fpa_lt, Bool<'ctx>);
-        le(Z3_mk_fpa_leq, Bool<'ctx>);
-        gt(Z3_mk_fpa_gt, Bool<'ctx>);
-        ge(Z3_mk_fpa_geq, Bool<'ctx>);
+        lt, try_lt(Z3_mk_fpa_lt, Bool<'ctx>);
+        le, try_le(Z3_mk_fpa_leq, Bool<'ctx>);
+        gt, try_gt(Z3_mk_fpa_gt, Bool<'ctx>);
+        ge, try_ge(Z3_mk_fpa_geq, Bool<'ctx>);
     }
     trinop! {
-        add(Z3_mk_fpa_add, Self);
-        sub(Z3_mk_fpa_sub, Self);
-        mul(Z3_mk_fpa_mul, Self);
-        div(Z3_mk_fpa_div, Self);
+        add, try_add(Z3_mk_fpa_add, Self);
+        sub, try_sub(Z3_mk_fpa_sub, Self);
+        mul, try_mul(Z3_mk_fpa_mul, Self);
+        div, try_div(Z3_mk_fpa_div, Self);
+    }
+}
+
+impl<'ctx> Float<'ctx> {
+    /// Create a NaN of the given floating-point sort.
+    pub fn nan(ctx: &'ctx Context, ebits: u32, sbits: u32) -> Float<'ctx> {
+        let sort = Sort::float(ctx, ebits, sbits);
+        unsafe { Self::wrap(ctx, Z3_mk_fpa_nan(ctx.z3_ctx, sort.z3_sort)) }
+    }
+
+    /// Create a positive or negative infinity of the given floating-point sort.
+    pub fn infinity(ctx: &'ctx Context, ebits: u32, sbits: u32, negative: bool) -> Float<'ctx> {
+        let sort = Sort::float(ctx, ebits, sbits);
+        unsafe { Self::wrap(ctx, Z3_mk_fpa_inf(ctx.z3_ctx, sort.z3_sort, negative)) }
+    }
+
+    /// Create a positive or negative zero of the given floating-point sort.
+    pub fn zero(ctx: &'ctx Context, ebits: u32, sbits: u32, negative: bool) -> Float<'ctx> {
+        let sort = Sort::float(ctx, ebits, sbits);
+        unsafe { Self::wrap(ctx, Z3_mk_fpa_zero(ctx.z3_ctx, sort.z3_sort, negative)) }
+    }
+
+    /// Add two floats of the same sort, rounding according to `rm`.
+    pub fn add_rm(&self, rm: &RoundingMode<'ctx>, other: &Self) -> Float<'ctx> {
+        self.with_rm(rm, other, Z3_mk_fpa_add)
+    }
+
+    /// Subtract `other` from `self`, rounding according to `rm`.
+    pub fn sub_rm(&self, rm: &RoundingMode<'ctx>, other: &Self) -> Float<'ctx> {
+        self.with_rm(rm, other, Z3_mk_fpa_sub)
+    }
+
+    /// Multiply two floats of the same sort, rounding according to `rm`.
+    pub fn mul_rm(&self, rm: &RoundingMode<'ctx>, other: &Self) -> Float<'ctx> {
+        self.with_rm(rm, other, Z3_mk_fpa_mul)
+    }
+
+    /// Divide `self` by `other`, rounding according to `rm`.
+    pub fn div_rm(&self, rm: &RoundingMode<'ctx>, other: &Self) -> Float<'ctx> {
+        self.with_rm(rm, other, Z3_mk_fpa_div)
+    }
+
+    fn with_rm(
+        &self,
+        rm: &RoundingMode<'ctx>,
+        other: &Self,
+        op: unsafe extern "C" fn(Z3_context, Z3_ast, Z3_ast, Z3_ast) -> Z3_ast,
+    ) -> Float<'ctx> {
+        assert!((self.ctx == rm.ctx) && (self.ctx == other.ctx));
+        unsafe {
+            Self::wrap(
+                self.ctx,
+                op(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, other.z3_ast),
+            )
+        }
+    }
+
+    /// Fused multiply-add: compute `self * a + b` with a single rounding
+    /// according to `rm`.
+    pub fn fma(&self, rm: &RoundingMode<'ctx>, a: &Self, b: &Self) -> Float<'ctx> {
+        assert!((self.ctx == rm.ctx) && (self.ctx == a.ctx) && (a.ctx == b.ctx));
+        unsafe {
+            Self::wrap(
+                self.ctx,
+                Z3_mk_fpa_fma(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, a.z3_ast, b.z3_ast),
+            )
+        }
+    }
+
+    /// Square root, rounded according to `rm`.
+    pub fn sqrt(&self, rm: &RoundingMode<'ctx>) -> Float<'ctx> {
+        assert!(self.ctx == rm.ctx);
+        unsafe {
+            Self::wrap(
+                self.ctx,
+                Z3_mk_fpa_sqrt(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast),
+            )
+        }
+    }
+
+    /// Round to an integral value of the same floating-point sort, according to `rm`.
+    pub fn round_to_integral(&self, rm: &RoundingMode<'ctx>) -> Float<'ctx> {
+        assert!(self.ctx == rm.ctx);
+        unsafe {
+            Self::wrap(
+                self.ctx,
+                Z3_mk_fpa_round_to_integral(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast),
+            )
+        }
+    }
+
+    binop! {
+        /// IEEE-754 remainder of `self` divided by `other`.
+        rem, try_rem(Z3_mk_fpa_rem, Self);
+        /// Minimum of two floats. If one argument is NaN the other is returned.
+        min, try_min(Z3_mk_fpa_min, Self);
+        /// Maximum of two floats. If one argument is NaN the other is returned.
+        max, try_max(Z3_mk_fpa_max, Self);
+        /// IEEE-754 equality: NaN is not equal to itself, and `+0` equals `-0`.
+        ///
+        /// This differs from [`Ast::_eq()`], which is structural equality.
+        fp_eq, try_fp_eq(Z3_mk_fpa_eq, Bool<'ctx>);
+    }
+
+    unop! {
+        /// Whether the value is a normal number.
+        is_normal(Z3_mk_fpa_is_normal, Bool<'ctx>);
+        /// Whether the value is a subnormal number.
+        is_subnormal(Z3_mk_fpa_is_subnormal, Bool<'ctx>);
+        /// Whether the value is `+0` or `-0`.
+        is_zero(Z3_mk_fpa_is_zero, Bool<'ctx>);
+        /// Whether the value is `+oo` or `-oo`.
+        is_infinite(Z3_mk_fpa_is_infinite, Bool<'ctx>);
+        /// Whether the value is NaN.
+        is_nan(Z3_mk_fpa_is_nan, Bool<'ctx>);
+        /// Whether the value is negative (and not NaN).
+        is_negative(Z3_mk_fpa_is_negative, Bool<'ctx>);
+        /// Whether the value is positive (and not NaN).
+        is_positive(Z3_mk_fpa_is_positive, Bool<'ctx>);
+        /// Convert to a real number. The result is unspecified for NaN and infinities.
+        to_real(Z3_mk_fpa_to_real, Real<'ctx>);
+    }
+
+    /// Reinterpret the IEEE-754 bit pattern `bv` as a float with `ebits`
+    /// exponent bits and `sbits` significand bits (including the hidden bit).
+    ///
+    /// The size of `bv` must be `ebits + sbits`. This is the inverse of
+    /// [`Float::to_ieee_bv()`].
+    pub fn from_ieee_bv(bv: &BV<'ctx>, ebits: u32, sbits: u32) -> Float<'ctx> {
+        let sort = Sort::float(bv.ctx, ebits, sbits);
+        unsafe {
+            Self::wrap(
+                bv.ctx,
+                Z3_mk_fpa_to_fp_bv(bv.ctx.z3_ctx, bv.z3_ast, sort.z3_sort),
+            )
+        }
+    }
+
+    /// Convert a real number to a float of the given sort, rounding according to `rm`.
+    pub fn from_real(
+        rm: &RoundingMode<'ctx>,
+        real: &Real<'ctx>,
+        ebits: u32,
+        sbits: u32,
+    ) -> Float<'ctx> {
+        assert!(rm.ctx == real.ctx);
+        let sort = Sort::float(rm.ctx, ebits, sbits);
+        unsafe {
+            Self::wrap(
+                rm.ctx,
+                Z3_mk_fpa_to_fp_real(rm.ctx.z3_ctx, rm.z3_ast, real.z3_ast, sort.z3_sort),
+            )
+        }
+    }
+
+    /// Convert a bit-vector, interpreted as a two's complement signed integer,
+    /// to a float of the given sort, rounding according to `rm`.
+    pub fn from_signed_bv(
+        rm: &RoundingMode<'ctx>,
+        bv: &BV<'ctx>,
+        ebits: u32,
+        sbits: u32,
+    ) -> Float<'ctx> {
+        assert!(rm.ctx == bv.ctx);
+        let sort = Sort::float(rm.ctx, ebits, sbits);
+        unsafe {
+            Self::wrap(
+                rm.ctx,
+                Z3_mk_fpa_to_fp_signed(rm.ctx.z3_ctx, rm.z3_ast, bv.z3_ast, sort.z3_sort),
+            )
+        }
+    }
+
+    /// Convert a bit-vector, interpreted as an unsigned integer, to a float
+    /// of the given sort, rounding according to `rm`.
+    pub fn from_unsigned_bv(
+        rm: &RoundingMode<'ctx>,
+        bv: &BV<'ctx>,
+        ebits: u32,
+        sbits: u32,
+    ) -> Float<'ctx> {
+        assert!(rm.ctx == bv.ctx);
+        let sort = Sort::float(rm.ctx, ebits, sbits);
+        unsafe {
+            Self::wrap(
+                rm.ctx,
+                Z3_mk_fpa_to_fp_unsigned(rm.ctx.z3_ctx, rm.z3_ast, bv.z3_ast, sort.z3_sort),
+            )
+        }
+    }
+
+    /// Convert to a float of a different sort, rounding according to `rm`.
+    pub fn to_float(&self, rm: &RoundingMode<'ctx>, ebits: u32, sbits: u32) -> Float<'ctx> {
+        assert!(self.ctx == rm.ctx);
+        let sort = Sort::float(self.ctx, ebits, sbits);
+        unsafe {
+            Self::wrap(
+                self.ctx,
+                Z3_mk_fpa_to_fp_float(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, sort.z3_sort),
+            )
+        }
+    }
+
+    /// Convert to a signed bit-vector of `size` bits, rounding according to `rm`.
+    ///
+    /// The result is unspecified if the value is NaN, infinite, or out of range.
+    pub fn to_sbv(&self, rm: &RoundingMode<'ctx>, size: u32) -> BV<'ctx> {
+        assert!(self.ctx == rm.ctx);
+        unsafe {
+            BV::wrap(
+                self.ctx,
+                Z3_mk_fpa_to_sbv(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, size),
+            )
+        }
+    }
+
+    /// Convert to an unsigned bit-vector of `size` bits, rounding according to `rm`.
+    ///
+    /// The result is unspecified if the value is NaN, infinite, or out of range.
+    pub fn to_ubv(&self, rm: &RoundingMode<'ctx>, size: u32) -> BV<'ctx> {
+        assert!(self.ctx == rm.ctx);
+        unsafe {
+            BV::wrap(
+                self.ctx,
+                Z3_mk_fpa_to_ubv(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, size),
+            )
+        }
+    }
+}
+
+impl<'ctx> RoundingMode<'ctx> {
+    /// Creates a new rounding mode constant.
+    pub fn new_const<S: Into<Symbol>>(ctx: &'ctx Context, name: S) -> RoundingMode<'ctx> {
+        let sort = Sort::rounding_mode(ctx);
+        unsafe {
+            Self::wrap(ctx, {
+                Z3_mk_const(ctx.z3_ctx, name.into().as_z3_symbol(ctx), sort.z3_sort)
+            })
+        }
+    }
+
+    /// Creates a fresh rounding mode constant.
+    pub fn fresh_const(ctx: &'ctx Context, prefix: &str) -> RoundingMode<'ctx> {
+        let sort = Sort::rounding_mode(ctx);
+        unsafe {
+            Self::wrap(ctx, {
+                let pp = CString::new(prefix).unwrap();
+                let p = pp.as_ptr();
+                Z3_mk_fresh_const(ctx.z3_ctx, p, sort.z3_sort)
+            })
+        }
+    }
+
+    /// Round to nearest, ties to even (`RNE`). This is the IEEE-754 default.
+    pub fn nearest_ties_to_even(ctx: &'ctx Context) -> RoundingMode<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_fpa_round_nearest_ties_to_even(ctx.z3_ctx)) }
+    }
+
+    /// Round to nearest, ties away from zero (`RNA`).
+    pub fn nearest_ties_to_away(ctx: &'ctx Context) -> RoundingMode<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_fpa_round_nearest_ties_to_away(ctx.z3_ctx)) }
+    }
+
+    /// Round towards positive infinity (`RTP`).
+    pub fn toward_positive(ctx: &'ctx Context) -> RoundingMode<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_fpa_round_toward_positive(ctx.z3_ctx)) }
+    }
+
+    /// Round towards negative infinity (`RTN`).
+    pub fn toward_negative(ctx: &'ctx Context) -> RoundingMode<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_fpa_round_toward_negative(ctx.z3_ctx)) }
+    }
+
+    /// Round towards zero (`RTZ`).
+    pub fn toward_zero(ctx: &'ctx Context) -> RoundingMode<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_fpa_round_toward_zero(ctx.z3_ctx)) }
     }
 }
 
@@ -1188,22 +1608,108 @@ impl<'ctx> String<'ctx> {
         })
     }
 
+    /// Creates a Z3 constant string holding exactly the characters of
+    /// `string`.
+    ///
+    /// Unlike [`String::from_str()`], which lets Z3 interpret `\u{...}`
+    /// escape sequences, every character of `string`, including non-ASCII
+    /// characters, NUL and backslashes, becomes one character of the Z3
+    /// string.
+    ///
+    /// Returns an error if `string` contains a character above `U+2FFFF`,
+    /// the largest character Z3 supports.
+    ///
+    /// ```
+    /// # use z3::{ast, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let s = ast::String::from_unicode(&ctx, "caf\u{e9} \\u{62}").unwrap();
+    /// assert_eq!(s.as_string().unwrap(), "caf\u{e9} \\u{62}");
+    /// assert!(ast::String::from_unicode(&ctx, "\u{30000}").is_err());
+    /// ```
+    pub fn from_unicode(ctx: &'ctx Context, string: &str) -> Result<String<'ctx>, Error> {
+        if let Some(c) = string.chars().find(|&c| c as u32 > MAX_CHAR) {
+            return Err(Error::invalid_arg(format!(
+                "character U+{:X} is above the largest Z3 character U+{:X}",
+                c as u32, MAX_CHAR
+            )));
+        }
+        #[cfg(feature = "unicode")]
+        let ast = {
+            let chars: Vec<u32> = string.chars().map(|c| c as u32).collect();
+            unsafe { Z3_mk_u32string(ctx.z3_ctx, chars.len() as u32, chars.as_ptr()) }
+        };
+        #[cfg(not(feature = "unicode"))]
+        let ast = {
+            let escaped = CString::new(escape_string(string)).unwrap();
+            unsafe { Z3_mk_string(ctx.z3_ctx, escaped.as_ptr()) }
+        };
+        ctx.check_error()?;
+        Ok(unsafe { Self::wrap(ctx, ast) })
+    }
+
+    /// Creates a Z3 string holding the single character `ch`.
+    #[cfg(feature = "unicode")]
+    pub fn from_char(ch: &Char<'ctx>) -> String<'ctx> {
+        unsafe { Self::wrap(ch.ctx, Z3_mk_seq_unit(ch.ctx.z3_ctx, ch.z3_ast)) }
+    }
+
     /// Retrieves the underlying `std::string::String`
     ///
     /// If this is not a constant `z3::ast::String`, return `None`.
     ///
     /// Note that `to_string()` provided by `std::string::ToString` (which uses
     /// `std::fmt::Display`) returns an escaped string. In contrast,
-    /// `z3::ast::String::from_str(&ctx, s).unwrap().as_string()` returns a
-    /// `String` equal to the original value.
+    /// `z3::ast::String::from_unicode(&ctx, s).unwrap().as_string()` returns
+    /// a `String` equal to the original value, including for characters
+    /// outside of ASCII.
+    ///
+    /// Z3 characters that are not valid Unicode scalar values (surrogate
+    /// code points) are replaced with `U+FFFD`. Without the `unicode`
+    /// feature, Z3 does not escape backslashes in strings holding
+    /// characters above `U+00FF`, so in such strings a literal `\u{...}`
+    /// sequence is read back as the character it names.
+    ///
+    /// ```
+    /// # use z3::{ast, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let s = ast::String::from_unicode(&ctx, "caf\u{e9} \u{1F600} a\\b\0").unwrap();
+    /// assert_eq!(s.as_string().unwrap(), "caf\u{e9} \u{1F600} a\\b\0");
+    /// ```
     pub fn as_string(&self) -> Option<std::string::String> {
         let z3_ctx = self.get_ctx().z3_ctx;
         unsafe {
-            let bytes = Z3_get_string(z3_ctx, self.get_z3_ast());
-            if bytes.is_null() {
-                None
-            } else {
-                Some(CStr::from_ptr(bytes).to_string_lossy().into_owned())
+            if !Z3_is_string(z3_ctx, self.get_z3_ast()) {
+                return None;
+            }
+            #[cfg(feature = "unicode")]
+            {
+                let length = Z3_get_string_length(z3_ctx, self.get_z3_ast());
+                let mut chars = vec![0; length as usize];
+                Z3_get_string_contents(z3_ctx, self.get_z3_ast(), length, chars.as_mut_ptr());
+                Some(
+                    chars
+                        .into_iter()
+                        .map(|c| std::char::from_u32(c).unwrap_or('\u{fffd}'))
+                        .collect(),
+                )
+            }
+            #[cfg(not(feature = "unicode"))]
+            {
+                // Z3 passes characters up to U+00FF through unescaped here,
+                // so if there is one byte per character, none was escaped.
+                let mut length = 0;
+                let bytes = Z3_get_lstring(z3_ctx, self.get_z3_ast(), &mut length);
+                if !bytes.is_null() && self.length().simplify().as_u64() == Some(length.into()) {
+                    let bytes = std::slice::from_raw_parts(bytes as *const u8, length as usize);
+                    return Some(bytes.iter().map(|&b| b as char).collect());
+                }
+                let bytes = Z3_get_string(z3_ctx, self.get_z3_ast());
+                if bytes.is_null() {
+                    return None;
+                }
+                Some(unescape_string(&CStr::from_ptr(bytes).to_string_lossy()))
             }
         }
     }
@@ -1309,37 +1815,224 @@ impl<'ctx> String<'ctx> {
         prefix(Z3_mk_seq_prefix, Bool<'ctx>);
         /// Checks whether `Self` is a suffix of the argument
         suffix(Z3_mk_seq_suffix, Bool<'ctx>);
+        /// Checks whether `Self` is lexicographically strictly less than the argument
+        lt(Z3_mk_str_lt, Bool<'ctx>);
+        /// Checks whether `Self` is lexicographically less than or equal to the argument
+        le(Z3_mk_str_le, Bool<'ctx>);
+        /// Gets the index of the last occurrence of the argument in `Self`,
+        /// or -1 if there is none.
+        last_index_of(Z3_mk_seq_last_index, Int<'ctx>);
     }
-}
 
-macro_rules! bv_overflow_check_signed {
-    (
-        $(
-            $( #[ $attr:meta ] )* $f:ident ( $z3fn:ident ) ;
-        )*
-    ) => {
-        $(
-            $( #[ $attr ] )*
-            pub fn $f(&self, other: &BV<'ctx>, b: bool) -> Bool<'ctx> {
-                unsafe {
-                    Ast::wrap(self.ctx, {
-                        $z3fn(self.ctx.z3_ctx, self.z3_ast, other.z3_ast, b)
-                    })
-                }
-            }
-        )*
-    };
-}
+    /// Checks whether `Self` is lexicographically strictly greater than `other`
+    pub fn gt(&self, other: &Self) -> Bool<'ctx> {
+        other.lt(self)
+    }
 
-impl<'ctx> BV<'ctx> {
-    pub fn from_str(ctx: &'ctx Context, sz: u32, value: &str) -> Option<BV<'ctx>> {
-        let sort = Sort::bitvector(ctx, sz);
-        let ast = unsafe {
-            let bv_cstring = CString::new(value).unwrap();
-            let numeral_ptr = Z3_mk_numeral(ctx.z3_ctx, bv_cstring.as_ptr(), sort.z3_sort);
-            if numeral_ptr.is_null() {
-                return None;
-            }
+    /// Checks whether `Self` is lexicographically greater than or equal to `other`
+    pub fn ge(&self, other: &Self) -> Bool<'ctx> {
+        other.le(self)
+    }
+
+    /// Gets the index of the first occurrence of `substr` in `Self` at or
+    /// after `offset`, or -1 if there is none.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int, String}, Config, Context, Solver, SatResult};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// # let solver = Solver::new(&ctx);
+    /// let s = String::from_str(&ctx, "abcabc").unwrap();
+    /// let b = String::from_str(&ctx, "b").unwrap();
+    /// let i = s.index_of(&b, &Int::from_i64(&ctx, 2));
+    /// assert_eq!(i.simplify().as_i64(), Some(4));
+    /// assert_eq!(s.last_index_of(&b).simplify().as_i64(), Some(4));
+    /// ```
+    pub fn index_of(&self, substr: &Self, offset: &Int<'ctx>) -> Int<'ctx> {
+        assert!(self.ctx == substr.ctx && self.ctx == offset.ctx);
+        unsafe {
+            Int::wrap(
+                self.ctx,
+                Z3_mk_seq_index(self.ctx.z3_ctx, self.z3_ast, substr.z3_ast, offset.z3_ast),
+            )
+        }
+    }
+
+    trinop! {
+        /// Replaces the first occurrence of the first argument in `Self`
+        /// with the second argument.
+        replace(Z3_mk_seq_replace, Self);
+        /// Replaces all occurrences of the first argument in `Self` with the
+        /// second argument.
+        ///
+        /// Requires the `unicode` feature.
+        #[cfg(feature = "unicode")]
+        replace_all(Z3_mk_seq_replace_all, Self);
+    }
+
+    unop! {
+        /// Converts `Self` to an integer, if it is a non-empty string of
+        /// decimal digits, or -1 otherwise.
+        to_int(Z3_mk_str_to_int, Int<'ctx>);
+        /// Gets the code point of `Self` if it has length 1, or -1 otherwise.
+        ///
+        /// Requires the `unicode` feature.
+        #[cfg(feature = "unicode")]
+        to_code(Z3_mk_string_to_code, Int<'ctx>);
+    }
+
+    /// Converts a non-negative integer to its decimal representation, or
+    /// to the empty string if it is negative.
+    pub fn from_int(ast: &Int<'ctx>) -> String<'ctx> {
+        unsafe { Self::wrap(ast.ctx, Z3_mk_int_to_str(ast.ctx.z3_ctx, ast.z3_ast)) }
+    }
+
+    /// Creates the string of length 1 holding the character with the code
+    /// point `code`, or the empty string if `code` is not a valid code point.
+    ///
+    /// Requires the `unicode` feature.
+    #[cfg(feature = "unicode")]
+    pub fn from_code(code: &Int<'ctx>) -> String<'ctx> {
+        unsafe {
+            Self::wrap(
+                code.ctx,
+                Z3_mk_string_from_code(code.ctx.z3_ctx, code.z3_ast),
+            )
+        }
+    }
+}
+
+/// The largest character of the Z3 string sort.
+const MAX_CHAR: u32 = 0x2ffff;
+
+/// Escape `s` for [`Z3_mk_string`], so that every character of `s` is taken
+/// literally.
+///
+/// Z3 reads escapes of at most 5 hex digits, which covers every character
+/// up to [`MAX_CHAR`].
+#[cfg(not(feature = "unicode"))]
+fn escape_string(s: &str) -> std::string::String {
+    let mut escaped = std::string::String::with_capacity(s.len());
+    for c in s.chars() {
+        if c == '\\' || !(' '..='~').contains(&c) {
+            escaped.push_str(&format!("\\u{{{:x}}}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Decode the `\u{...}` escapes in a string returned by [`Z3_get_string`].
+#[cfg(not(feature = "unicode"))]
+fn unescape_string(s: &str) -> std::string::String {
+    let mut unescaped = std::string::String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("\\u{") {
+        unescaped.push_str(&rest[..start]);
+        let after = &rest[start + 3..];
+        let code = after
+            .find('}')
+            .filter(|&end| end > 0 && end <= 5)
+            .and_then(|end| Some((u32::from_str_radix(&after[..end], 16).ok()?, end)));
+        match code {
+            Some((code, end)) => {
+                unescaped.push(std::char::from_u32(code).unwrap_or('\u{fffd}'));
+                rest = &after[end + 1..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+#[cfg(feature = "unicode")]
+impl<'ctx> Char<'ctx> {
+    /// Creates a new constant of the character sort.
+    pub fn new_const<S: Into<Symbol>>(ctx: &'ctx Context, name: S) -> Char<'ctx> {
+        let sort = Sort::char(ctx);
+        unsafe {
+            Self::wrap(ctx, {
+                Z3_mk_const(ctx.z3_ctx, name.into().as_z3_symbol(ctx), sort.z3_sort)
+            })
+        }
+    }
+
+    /// Creates a fresh constant of the character sort.
+    pub fn fresh_const(ctx: &'ctx Context, prefix: &str) -> Char<'ctx> {
+        let sort = Sort::char(ctx);
+        unsafe {
+            Self::wrap(ctx, {
+                let pp = CString::new(prefix).unwrap();
+                let p = pp.as_ptr();
+                Z3_mk_fresh_const(ctx.z3_ctx, p, sort.z3_sort)
+            })
+        }
+    }
+
+    /// Creates a character literal.
+    pub fn from_char(ctx: &'ctx Context, ch: char) -> Char<'ctx> {
+        Self::from_u32(ctx, ch as u32)
+    }
+
+    /// Creates a character literal from its code point.
+    pub fn from_u32(ctx: &'ctx Context, code: u32) -> Char<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_char(ctx.z3_ctx, code)) }
+    }
+
+    /// Creates a character from a bit-vector holding its code point.
+    pub fn from_bv(bv: &BV<'ctx>) -> Char<'ctx> {
+        unsafe { Self::wrap(bv.ctx, Z3_mk_char_from_bv(bv.ctx.z3_ctx, bv.z3_ast)) }
+    }
+
+    binop! {
+        /// Checks whether the code point of `Self` is at most that of the argument.
+        le(Z3_mk_char_le, Bool<'ctx>);
+    }
+
+    unop! {
+        /// Gets the code point of `Self` as an integer.
+        to_int(Z3_mk_char_to_int, Int<'ctx>);
+        /// Gets the code point of `Self` as a bit-vector.
+        to_bv(Z3_mk_char_to_bv, BV<'ctx>);
+        /// Checks whether `Self` is a decimal digit.
+        is_digit(Z3_mk_char_is_digit, Bool<'ctx>);
+    }
+}
+
+macro_rules! bv_overflow_check_signed {
+    (
+        $(
+            $( #[ $attr:meta ] )* $f:ident ( $z3fn:ident ) ;
+        )*
+    ) => {
+        $(
+            $( #[ $attr ] )*
+            pub fn $f(&self, other: &BV<'ctx>, b: bool) -> Bool<'ctx> {
+                unsafe {
+                    Ast::wrap(self.ctx, {
+                        $z3fn(self.ctx.z3_ctx, self.z3_ast, other.z3_ast, b)
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl<'ctx> BV<'ctx> {
+    pub fn from_str(ctx: &'ctx Context, sz: u32, value: &str) -> Option<BV<'ctx>> {
+        let sort = Sort::bitvector(ctx, sz);
+        let ast = unsafe {
+            let bv_cstring = CString::new(value).unwrap();
+            let numeral_ptr = Z3_mk_numeral(ctx.z3_ctx, bv_cstring.as_ptr(), sort.z3_sort);
+            if numeral_ptr.is_null() {
+                return None;
+            }
 
             numeral_ptr
         };
@@ -1450,71 +2143,71 @@ impl<'ctx> BV<'ctx> {
     }
     binop! {
         /// Bitwise and
-        bvand(Z3_mk_bvand, Self);
+        bvand, try_bvand(Z3_mk_bvand, Self);
         /// Bitwise or
-        bvor(Z3_mk_bvor, Self);
+        bvor, try_bvor(Z3_mk_bvor, Self);
         /// Bitwise exclusive-or
-        bvxor(Z3_mk_bvxor, Self);
+        bvxor, try_bvxor(Z3_mk_bvxor, Self);
         /// Bitwise nand
-        bvnand(Z3_mk_bvnand, Self);
+        bvnand, try_bvnand(Z3_mk_bvnand, Self);
         /// Bitwise nor
-        bvnor(Z3_mk_bvnor, Self);
+        bvnor, try_bvnor(Z3_mk_bvnor, Self);
         /// Bitwise xnor
-        bvxnor(Z3_mk_bvxnor, Self);
+        bvxnor, try_bvxnor(Z3_mk_bvxnor, Self);
     }
 
     // Arithmetic ops
     binop! {
         /// Addition
-        bvadd(Z3_mk_bvadd, Self);
+        bvadd, try_bvadd(Z3_mk_bvadd, Self);
         /// Subtraction
-        bvsub(Z3_mk_bvsub, Self);
+        bvsub, try_bvsub(Z3_mk_bvsub, Self);
         /// Multiplication
-        bvmul(Z3_mk_bvmul, Self);
+        bvmul, try_bvmul(Z3_mk_bvmul, Self);
         /// Unsigned division
-        bvudiv(Z3_mk_bvudiv, Self);
+        bvudiv, try_bvudiv(Z3_mk_bvudiv, Self);
         /// Signed division
-        bvsdiv(Z3_mk_bvsdiv, Self);
+        bvsdiv, try_bvsdiv(Z3_mk_bvsdiv, Self);
         /// Unsigned remainder
-        bvurem(Z3_mk_bvurem, Self);
+        bvurem, try_bvurem(Z3_mk_bvurem, Self);
         /// Signed remainder (sign follows dividend)
-        bvsrem(Z3_mk_bvsrem, Self);
+        bvsrem, try_bvsrem(Z3_mk_bvsrem, Self);
         /// Signed remainder (sign follows divisor)
-        bvsmod(Z3_mk_bvsmod, Self);
+        bvsmod, try_bvsmod(Z3_mk_bvsmod, Self);
     }
 
     // Comparison ops
     binop! {
         /// Unsigned less than
-        bvult(Z3_mk_bvult, Bool<'ctx>);
+        bvult, try_bvult(Z3_mk_bvult, Bool<'ctx>);
         /// Signed less than
-        bvslt(Z3_mk_bvslt, Bool<'ctx>);
+        bvslt, try_bvslt(Z3_mk_bvslt, Bool<'ctx>);
         /// Unsigned less than or equal
-        bvule(Z3_mk_bvule, Bool<'ctx>);
+        bvule, try_bvule(Z3_mk_bvule, Bool<'ctx>);
         /// Signed less than or equal
-        bvsle(Z3_mk_bvsle, Bool<'ctx>);
+        bvsle, try_bvsle(Z3_mk_bvsle, Bool<'ctx>);
         /// Unsigned greater or equal
-        bvuge(Z3_mk_bvuge, Bool<'ctx>);
+        bvuge, try_bvuge(Z3_mk_bvuge, Bool<'ctx>);
         /// Signed greater or equal
-        bvsge(Z3_mk_bvsge, Bool<'ctx>);
+        bvsge, try_bvsge(Z3_mk_bvsge, Bool<'ctx>);
         /// Unsigned greater than
-        bvugt(Z3_mk_bvugt, Bool<'ctx>);
+        bvugt, try_bvugt(Z3_mk_bvugt, Bool<'ctx>);
         /// Signed greater than
-        bvsgt(Z3_mk_bvsgt, Bool<'ctx>);
+        bvsgt, try_bvsgt(Z3_mk_bvsgt, Bool<'ctx>);
     }
 
     // Shift ops
     binop! {
         /// Shift left
-        bvshl(Z3_mk_bvshl, Self);
+        bvshl, try_bvshl(Z3_mk_bvshl, Self);
         /// Logical shift right (add zeroes in the high bits)
-        bvlshr(Z3_mk_bvlshr, Self);
+        bvlshr, try_bvlshr(Z3_mk_bvlshr, Self);
         /// Arithmetic shift right (sign-extend in the high bits)
-        bvashr(Z3_mk_bvashr, Self);
+        bvashr, try_bvashr(Z3_mk_bvashr, Self);
         /// Rotate left
-        bvrotl(Z3_mk_ext_rotate_left, Self);
+        bvrotl, try_bvrotl(Z3_mk_ext_rotate_left, Self);
         /// Rotate right
-        bvrotr(Z3_mk_ext_rotate_right, Self);
+        bvrotr, try_bvrotr(Z3_mk_ext_rotate_right, Self);
     }
 
     binop! {
@@ -1537,13 +2230,13 @@ impl<'ctx> BV<'ctx> {
     }
     binop! {
         /// Check if addition underflows
-        bvadd_no_underflow(Z3_mk_bvadd_no_underflow, Bool<'ctx>);
+        bvadd_no_underflow, try_bvadd_no_underflow(Z3_mk_bvadd_no_underflow, Bool<'ctx>);
         /// Check if subtraction overflows
-        bvsub_no_overflow(Z3_mk_bvsub_no_overflow, Bool<'ctx>);
+        bvsub_no_overflow, try_bvsub_no_overflow(Z3_mk_bvsub_no_overflow, Bool<'ctx>);
         /// Check if signed division overflows
-        bvsdiv_no_overflow(Z3_mk_bvsdiv_no_overflow, Bool<'ctx>);
+        bvsdiv_no_overflow, try_bvsdiv_no_overflow(Z3_mk_bvsdiv_no_overflow, Bool<'ctx>);
         /// Check if multiplication underflows
-        bvmul_no_underflow(Z3_mk_bvmul_no_underflow, Bool<'ctx>);
+        bvmul_no_underflow, try_bvmul_no_underflow(Z3_mk_bvmul_no_underflow, Bool<'ctx>);
     }
 
     /// Extract the bits `high` down to `low` from the bitvector.
@@ -1629,24 +2322,46 @@ impl<'ctx> Array<'ctx> {
     ///
     /// Note that the `index` _must be_ of the array's `domain` sort.
     /// The return type will be of the array's `range` sort.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not of the array's `domain` sort; see
+    /// [`Array::try_select()`].
     //
     // We avoid the binop! macro because the argument has a non-Self type
     pub fn select<A>(&self, index: &A) -> Dynamic<'ctx>
     where
         A: Ast<'ctx>,
     {
-        // TODO: We could validate here that the index is of the correct type.
-        // This would require us either to keep around the original `domain` argument
-        // from when the Array was constructed, or to do an additional Z3 query
-        // to find the domain sort first.
-        // But if we did this check ourselves, we'd just panic, so it doesn't seem
-        // like a huge advantage over just letting Z3 panic itself when it discovers the
-        // problem.
-        // This way we also avoid the redundant check every time this method is called.
+        self.try_select(index).unwrap()
+    }
+
+    /// Get the value at a given index in the array, or an [`Error`] if
+    /// `index` is not of the array's `domain` sort.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{Config, Context, ErrorCode, Sort, ast::{Array, Bool, Int}};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let arr = Array::new_const(&ctx, "arr", &Sort::int(&ctx), &Sort::bool(&ctx));
+    /// assert!(arr.try_select(&Int::from_i64(&ctx, 0)).is_ok());
+    ///
+    /// let err = arr.try_select(&Bool::from_bool(&ctx, true)).unwrap_err();
+    /// assert_ne!(err.code(), ErrorCode::OK);
+    /// ```
+    pub fn try_select<A>(&self, index: &A) -> Result<Dynamic<'ctx>, Error>
+    where
+        A: Ast<'ctx>,
+    {
+        assert!(index.get_ctx() == self.ctx);
+        // Rather than looking up the domain sort on every call, we let Z3
+        // validate the index and report any mismatch.
         unsafe {
-            Dynamic::wrap(self.ctx, {
-                Z3_mk_select(self.ctx.z3_ctx, self.z3_ast, index.get_z3_ast())
-            })
+            try_wrap(
+                self.ctx,
+                Z3_mk_select(self.ctx.z3_ctx, self.z3_ast, index.get_z3_ast()),
+            )
         }
     }
 
@@ -1690,6 +2405,66 @@ impl<'ctx> Array<'ctx> {
         }
     }
 
+    /// Fallible version of [`Array::store()`], returning an [`Error`] if
+    /// `index` or `value` are not of the array's `domain` and `range` sorts.
+    pub fn try_store<A1, A2>(&self, index: &A1, value: &A2) -> Result<Self, Error>
+    where
+        A1: Ast<'ctx>,
+        A2: Ast<'ctx>,
+    {
+        assert!(index.get_ctx() == self.ctx && value.get_ctx() == self.ctx);
+        unsafe {
+            try_wrap(
+                self.ctx,
+                Z3_mk_store(
+                    self.ctx.z3_ctx,
+                    self.z3_ast,
+                    index.get_z3_ast(),
+                    value.get_z3_ast(),
+                ),
+            )
+        }
+    }
+
+    /// Apply `f` pointwise to `arrays`, i.e. create the array that maps each
+    /// index `i` to `f(arrays[0][i], ..., arrays[n-1][i])`.
+    ///
+    /// Note that `arrays` _must_ share the same domain sort, and their range
+    /// sorts _must be_ the domain sorts of `f`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Array, Ast, Int}, Config, Context, FuncDecl, Sort};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let int = |i| Int::from_i64(&ctx, i);
+    /// let a = Array::const_array(&ctx, &Sort::int(&ctx), &int(1)).store(&int(0), &int(5));
+    /// let b = Array::const_array(&ctx, &Sort::int(&ctx), &int(2));
+    /// let f = FuncDecl::new(&ctx, "f", &[&Sort::int(&ctx), &Sort::int(&ctx)], &Sort::int(&ctx));
+    /// let sum = Array::map(&ctx, &f, &[&a, &b]);
+    /// assert_eq!(sum.select(&int(0)).simplify(), f.apply(&[&int(5), &int(2)]));
+    /// ```
+    pub fn map(ctx: &'ctx Context, f: &FuncDecl<'ctx>, arrays: &[&Array<'ctx>]) -> Array<'ctx> {
+        assert!(arrays.iter().all(|a| a.ctx == ctx));
+        let arrays: Vec<_> = arrays.iter().map(|a| a.z3_ast).collect();
+        unsafe {
+            Self::wrap(ctx, {
+                Z3_mk_map(
+                    ctx.z3_ctx,
+                    f.z3_func_decl,
+                    arrays.len().try_into().unwrap(),
+                    arrays.as_ptr(),
+                )
+            })
+        }
+    }
+
+    unop! {
+        /// Get the default value of the array, i.e. the value it maps all
+        /// indices to except finitely many.
+        default(Z3_mk_array_default, Dynamic<'ctx>);
+    }
+
     /// Returns true if the array is a const array (i.e. `a.is_const_array() => exists v, forall i. select(a, i) == v`)
     ///
     /// # Examples
@@ -1742,6 +2517,34 @@ impl<'ctx> Set<'ctx> {
         unsafe { Self::wrap(ctx, Z3_mk_empty_set(ctx.z3_ctx, domain.z3_sort)) }
     }
 
+    /// Creates a set that maps the domain to true by default
+    pub fn full(ctx: &'ctx Context, domain: &Sort<'ctx>) -> Set<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_full_set(ctx.z3_ctx, domain.z3_sort)) }
+    }
+
+    /// Creates the set of the given elements, which _must be_ of sort
+    /// `domain`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int, Set}, Config, Context, Sort};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let ints: Vec<Int> = (1..4).map(|i| Int::from_i64(&ctx, i)).collect();
+    /// let s = Set::from_iter(&ctx, &Sort::int(&ctx), &ints);
+    /// assert_eq!(s.member(&ints[2]).simplify().as_bool(), Some(true));
+    /// assert_eq!(s.member(&Int::from_i64(&ctx, 4)).simplify().as_bool(), Some(false));
+    /// ```
+    pub fn from_iter<'a, A, I>(ctx: &'ctx Context, domain: &Sort<'ctx>, elements: I) -> Set<'ctx>
+    where
+        A: Ast<'ctx> + 'a,
+        I: IntoIterator<Item = &'a A>,
+    {
+        elements
+            .into_iter()
+            .fold(Self::empty(ctx, domain), |set, e| set.add(e))
+    }
+
     /// Add an element to the set.
     ///
     /// Note that the `element` _must be_ of the `Set`'s `eltype` sort.
@@ -1792,9 +2595,9 @@ impl<'ctx> Set<'ctx> {
 
     varop! {
         /// Take the intersection of a list of sets.
-        intersect(Z3_mk_set_intersect, Self);
+        intersect, try_intersect(Z3_mk_set_intersect, Self);
         /// Take the union of a list of sets.
-        set_union(Z3_mk_set_union, Self);
+        set_union, try_set_union(Z3_mk_set_union, Self);
     }
     unop! {
         /// Take the complement of the set.
@@ -1802,9 +2605,9 @@ impl<'ctx> Set<'ctx> {
     }
     binop! {
         /// Check if the set is a subset of another set.
-        set_subset(Z3_mk_set_subset, Bool<'ctx>);
+        set_subset, try_set_subset(Z3_mk_set_subset, Bool<'ctx>);
         /// Take the set difference between two sets.
-        difference(Z3_mk_set_difference, Self);
+        difference, try_difference(Z3_mk_set_difference, Self);
     }
 }
 
@@ -1873,13 +2676,88 @@ impl<'ctx> Seq<'ctx> {
         }
     }
 
+    /// Create the empty sequence with elements of sort `eltype`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int, Seq}, Config, Context, Sort};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let empty = Seq::empty(&ctx, &Sort::int(&ctx));
+    /// assert_eq!(empty.length().simplify(), Int::from_u64(&ctx, 0));
+    /// ```
+    pub fn empty(ctx: &'ctx Context, eltype: &Sort<'ctx>) -> Self {
+        let sort = Sort::seq(ctx, eltype);
+        unsafe { Self::wrap(ctx, Z3_mk_seq_empty(ctx.z3_ctx, sort.z3_sort)) }
+    }
+
     pub fn length(&self) -> Int<'ctx> {
         unsafe { Int::wrap(self.ctx, Z3_mk_seq_length(self.ctx.z3_ctx, self.z3_ast)) }
     }
 
+    /// Extract the subsequence of at most `length` elements starting at
+    /// `offset`.
+    pub fn extract(&self, offset: &Int<'ctx>, length: &Int<'ctx>) -> Self {
+        assert!(self.ctx == offset.ctx && self.ctx == length.ctx);
+        unsafe {
+            Self::wrap(
+                self.ctx,
+                Z3_mk_seq_extract(self.ctx.z3_ctx, self.z3_ast, offset.z3_ast, length.z3_ast),
+            )
+        }
+    }
+
+    /// Get the index of the first occurrence of `substr` in `Self`, starting
+    /// the search at `offset`, or -1 if there is none.
+    pub fn index_of(&self, substr: &Self, offset: &Int<'ctx>) -> Int<'ctx> {
+        assert!(self.ctx == substr.ctx && self.ctx == offset.ctx);
+        unsafe {
+            Int::wrap(
+                self.ctx,
+                Z3_mk_seq_index(self.ctx.z3_ctx, self.z3_ast, substr.z3_ast, offset.z3_ast),
+            )
+        }
+    }
+
+    /// Check whether `Self` matches a [`Regexp`] over sequences of the same
+    /// sort.
+    pub fn regex_matches(&self, regex: &Regexp<'ctx>) -> Bool<'ctx> {
+        assert!(self.ctx == regex.ctx);
+        unsafe {
+            Bool::wrap(
+                self.ctx,
+                Z3_mk_seq_in_re(self.ctx.z3_ctx, self.z3_ast, regex.z3_ast),
+            )
+        }
+    }
+
     varop! {
         /// Concatenate sequences.
-        concat(Z3_mk_seq_concat, Self);
+        concat, try_concat(Z3_mk_seq_concat, Self);
+    }
+
+    unop! {
+        /// Create a regular expression that only recognizes `Self`.
+        to_re(Z3_mk_seq_to_re, Regexp<'ctx>);
+    }
+
+    binop! {
+        /// Check whether `Self` contains the argument as a subsequence of
+        /// consecutive elements.
+        contains(Z3_mk_seq_contains, Bool<'ctx>);
+        /// Check whether `Self` is a prefix of the argument.
+        prefix(Z3_mk_seq_prefix, Bool<'ctx>);
+        /// Check whether `Self` is a suffix of the argument.
+        suffix(Z3_mk_seq_suffix, Bool<'ctx>);
+        /// Get the index of the last occurrence of the argument in `Self`,
+        /// or -1 if there is none.
+        last_index_of(Z3_mk_seq_last_index, Int<'ctx>);
+    }
+
+    trinop! {
+        /// Replace the first occurrence of the first argument in `Self`
+        /// with the second argument.
+        replace(Z3_mk_seq_replace, Self);
     }
 }
 
@@ -1907,6 +2785,36 @@ impl<'ctx> Dynamic<'ctx> {
         }
     }
 
+    /// Create a bound variable with de Bruijn `index` and the given sort.
+    ///
+    /// Bound variables are only meaningful inside the body of a quantifier.
+    /// The innermost bound variable has index `0`.
+    ///
+    /// # See also:
+    ///
+    /// - [`quantifier()`]
+    /// - [`Ast::substitute_vars()`]
+    pub fn new_bound(ctx: &'ctx Context, index: u32, sort: &Sort<'ctx>) -> Self {
+        unsafe { Self::wrap(ctx, Z3_mk_bound(ctx.z3_ctx, index, sort.z3_sort)) }
+    }
+
+    /// Returns `None` if the `Dynamic` is not actually a `Quantifier`
+    pub fn as_quantifier(&self) -> Option<Quantifier<'ctx>> {
+        match self.kind() {
+            AstKind::Quantifier => Some(unsafe { Quantifier::wrap(self.ctx, self.z3_ast) }),
+            _ => None,
+        }
+    }
+
+    /// Return the de Bruijn index of a bound variable, or `None` if this is
+    /// not a bound variable.
+    pub fn bound_index(&self) -> Option<u32> {
+        match self.kind() {
+            AstKind::Var => Some(unsafe { Z3_get_index_value(self.ctx.z3_ctx, self.z3_ast) }),
+            _ => None,
+        }
+    }
+
     pub fn sort_kind(&self) -> SortKind {
         unsafe { Z3_get_sort_kind(self.ctx.z3_ctx, Z3_get_sort(self.ctx.z3_ctx, self.z3_ast)) }
     }
@@ -1943,6 +2851,14 @@ impl<'ctx> Dynamic<'ctx> {
         }
     }
 
+    /// Returns `None` if the `Dynamic` is not actually a `RoundingMode`
+    pub fn as_rounding_mode(&self) -> Option<RoundingMode<'ctx>> {
+        match self.sort_kind() {
+            SortKind::RoundingMode => Some(unsafe { RoundingMode::wrap(self.ctx, self.z3_ast) }),
+            _ => None,
+        }
+    }
+
     /// Returns `None` if the `Dynamic` is not actually a `String`
     pub fn as_string(&self) -> Option<String<'ctx>> {
         unsafe {
@@ -1954,6 +2870,18 @@ impl<'ctx> Dynamic<'ctx> {
         }
     }
 
+    /// Returns `None` if the `Dynamic` is not actually a `Char`
+    #[cfg(feature = "unicode")]
+    pub fn as_char(&self) -> Option<Char<'ctx>> {
+        unsafe {
+            if Z3_is_char_sort(self.ctx.z3_ctx, Z3_get_sort(self.ctx.z3_ctx, self.z3_ast)) {
+                Some(Char::wrap(self.ctx, self.z3_ast))
+            } else {
+                None
+            }
+        }
+    }
+
     /// Returns `None` if the `Dynamic` is not actually a `BV`
     pub fn as_bv(&self) -> Option<BV<'ctx>> {
         match self.sort_kind() {
@@ -1999,6 +2927,14 @@ impl<'ctx> Dynamic<'ctx> {
         }
     }
 
+    /// Returns `None` if the `Dynamic` is not actually a `Regexp`.
+    pub fn as_regexp(&self) -> Option<Regexp<'ctx>> {
+        match self.sort_kind() {
+            SortKind::RE => Some(unsafe { Regexp::wrap(self.ctx, self.z3_ast) }),
+            _ => None,
+        }
+    }
+
     /// Returns `None` if the `Dynamic` is not actually a `Datatype`
     pub fn as_datatype(&self) -> Option<Datatype<'ctx>> {
         match self.sort_kind() {
@@ -2122,6 +3058,24 @@ impl<'ctx> Regexp<'ctx> {
         }
     }
 
+    /// Creates a regular expression that recognizes all sequences of the
+    /// sequence sort `seq`, e.g. `Sort::seq(ctx, &Sort::int(ctx))`.
+    ///
+    /// [`Regexp::full()`] is the same for strings.
+    pub fn full_for(seq: &Sort<'ctx>) -> Self {
+        let sort = Sort::regexp(seq.ctx, seq);
+        unsafe { Self::wrap(seq.ctx, Z3_mk_re_full(seq.ctx.z3_ctx, sort.z3_sort)) }
+    }
+
+    /// Creates a regular expression that doesn't recognize any sequences of
+    /// the sequence sort `seq`.
+    ///
+    /// [`Regexp::empty()`] is the same for strings.
+    pub fn empty_for(seq: &Sort<'ctx>) -> Self {
+        let sort = Sort::regexp(seq.ctx, seq);
+        unsafe { Self::wrap(seq.ctx, Z3_mk_re_empty(seq.ctx.z3_ctx, sort.z3_sort)) }
+    }
+
     unop! {
        /// Creates a regular expression that recognizes this regular expression one or more times (e.g. `a+`)
        plus(Z3_mk_re_plus, Self);
@@ -2362,6 +3316,72 @@ pub fn quantifier_const<'ctx>(
     }
 }
 
+/// Create a quantifier over de Bruijn indexed bound variables.
+///
+/// `bounds` lists the names and sorts of the bound variables, outermost
+/// first: the last entry is referred to by [`Dynamic::new_bound()`] with
+/// index `0`. See [`quantifier_const()`] for the remaining arguments.
+///
+/// # Examples
+/// ```
+/// # use z3::{ast, ast::{Ast, Dynamic}, Config, Context, SatResult, Solver, Sort};
+/// # let cfg = Config::new();
+/// # let ctx = Context::new(&cfg);
+/// let int = Sort::int(&ctx);
+/// // forall x y. x + y = y + x
+/// let x = Dynamic::new_bound(&ctx, 1, &int).as_int().unwrap();
+/// let y = Dynamic::new_bound(&ctx, 0, &int).as_int().unwrap();
+/// let body = (&x + &y)._eq(&(&y + &x));
+/// let q = ast::quantifier(&ctx, true, 0, &[("x".into(), &int), ("y".into(), &int)], &[], &[], &body);
+///
+/// let solver = Solver::new(&ctx);
+/// solver.assert(&q.not());
+/// assert_eq!(solver.check(), SatResult::Unsat);
+/// ```
+pub fn quantifier<'ctx>(
+    ctx: &'ctx Context,
+    is_forall: bool,
+    weight: u32,
+    bounds: &[(Symbol, &Sort<'ctx>)],
+    patterns: &[&Pattern<'ctx>],
+    no_patterns: &[&dyn Ast<'ctx>],
+    body: &Bool<'ctx>,
+) -> Bool<'ctx> {
+    assert!(bounds.iter().all(|(_, s)| s.ctx == ctx));
+    assert!(patterns.iter().all(|p| p.ctx == ctx));
+    assert!(no_patterns.iter().all(|p| p.get_ctx() == ctx));
+    assert_eq!(ctx, body.get_ctx());
+
+    if bounds.is_empty() {
+        return body.clone();
+    }
+
+    let names: Vec<_> = bounds.iter().map(|(n, _)| n.as_z3_symbol(ctx)).collect();
+    let sorts: Vec<_> = bounds.iter().map(|(_, s)| s.z3_sort).collect();
+    let patterns: Vec<_> = patterns.iter().map(|p| p.z3_pattern).collect();
+    let no_patterns: Vec<_> = no_patterns.iter().map(|a| a.get_z3_ast()).collect();
+
+    unsafe {
+        Ast::wrap(ctx, {
+            Z3_mk_quantifier_ex(
+                ctx.z3_ctx,
+                is_forall,
+                weight,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                patterns.len().try_into().unwrap(),
+                patterns.as_ptr(),
+                no_patterns.len().try_into().unwrap(),
+                no_patterns.as_ptr(),
+                sorts.len().try_into().unwrap(),
+                sorts.as_ptr(),
+                names.as_ptr(),
+                body.get_z3_ast(),
+            )
+        })
+    }
+}
+
 /// Create a lambda expression.
 ///
 /// - `num_decls`: Number of variables to be bound.
@@ -2421,6 +3441,121 @@ pub fn lambda_const<'ctx>(
     }
 }
 
+impl<'ctx> Quantifier<'ctx> {
+    /// Return `true` if this is a universal quantifier.
+    pub fn is_forall(&self) -> bool {
+        unsafe { Z3_is_quantifier_forall(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return `true` if this is an existential quantifier.
+    pub fn is_exists(&self) -> bool {
+        unsafe { Z3_is_quantifier_exists(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return `true` if this is a lambda expression.
+    pub fn is_lambda(&self) -> bool {
+        unsafe { Z3_is_lambda(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return the weight of the quantifier, used to prioritize instantiations.
+    pub fn weight(&self) -> u32 {
+        unsafe { Z3_get_quantifier_weight(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return the number of variables bound by the quantifier.
+    pub fn num_bound(&self) -> u32 {
+        unsafe { Z3_get_quantifier_num_bound(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return the names of the bound variables, outermost first.
+    pub fn bound_names(&self) -> Vec<Symbol> {
+        (0..self.num_bound())
+            .map(|i| unsafe {
+                Symbol::from_z3_symbol(
+                    self.ctx,
+                    Z3_get_quantifier_bound_name(self.ctx.z3_ctx, self.z3_ast, i),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the sorts of the bound variables, outermost first.
+    pub fn bound_sorts(&self) -> Vec<Sort<'ctx>> {
+        (0..self.num_bound())
+            .map(|i| unsafe {
+                Sort::wrap(
+                    self.ctx,
+                    Z3_get_quantifier_bound_sort(self.ctx.z3_ctx, self.z3_ast, i),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the body of the quantifier.
+    ///
+    /// The bound variables appear in it as de Bruijn indexed variables; see
+    /// [`Dynamic::bound_index()`].
+    pub fn body(&self) -> Dynamic<'ctx> {
+        unsafe {
+            Dynamic::wrap(
+                self.ctx,
+                Z3_get_quantifier_body(self.ctx.z3_ctx, self.z3_ast),
+            )
+        }
+    }
+
+    /// Return the patterns used to instantiate the quantifier.
+    pub fn patterns(&self) -> Vec<Pattern<'ctx>> {
+        let n = unsafe { Z3_get_quantifier_num_patterns(self.ctx.z3_ctx, self.z3_ast) };
+        (0..n)
+            .map(|i| unsafe {
+                Pattern::wrap(
+                    self.ctx,
+                    Z3_get_quantifier_pattern_ast(self.ctx.z3_ctx, self.z3_ast, i),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the subterms excluded from inferred patterns.
+    pub fn no_patterns(&self) -> Vec<Dynamic<'ctx>> {
+        let n = unsafe { Z3_get_quantifier_num_no_patterns(self.ctx.z3_ctx, self.z3_ast) };
+        (0..n)
+            .map(|i| unsafe {
+                Dynamic::wrap(
+                    self.ctx,
+                    Z3_get_quantifier_no_pattern_ast(self.ctx.z3_ctx, self.z3_ast, i),
+                )
+            })
+            .collect()
+    }
+
+    /// Replace the bound variables in the body with `values`, given in the
+    /// same order as [`Quantifier::bound_names()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast, ast::{Ast, Dynamic, Int}, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let x = Int::new_const(&ctx, "x");
+    /// let y = Int::new_const(&ctx, "y");
+    /// let q = ast::forall_const(&ctx, &[&x, &y], &[], &x.lt(&y));
+    /// let q = Dynamic::from_ast(&q).as_quantifier().unwrap();
+    /// assert!(q.is_forall());
+    ///
+    /// let one = Int::from_i64(&ctx, 1);
+    /// let two = Int::from_i64(&ctx, 2);
+    /// let inst = q.instantiate(&[&one, &two]);
+    /// assert_eq!(inst, Dynamic::from_ast(&one.lt(&two)));
+    /// ```
+    pub fn instantiate(&self, values: &[&dyn Ast<'ctx>]) -> Dynamic<'ctx> {
+        assert_eq!(values.len(), self.num_bound() as usize);
+        let reversed: Vec<_> = values.iter().rev().copied().collect();
+        self.body().substitute_vars(&reversed)
+    }
+}
+
 impl IsNotApp {
     pub fn new(kind: AstKind) -> Self {
         Self { kind }
@@ -0,0 +1,209 @@
+use std::ffi::CStr;
+use std::fmt;
+use std::marker::PhantomData;
+
+use z3_sys::*;
+
+use crate::{
+    ast::{Ast, Dynamic},
+    AstMap, AstVector, Context, Translate,
+};
+
+impl<'ctx, K: Ast<'ctx>, V: Ast<'ctx>> AstMap<'ctx, K, V> {
+    /// Wrap a raw [`Z3_ast_map`], managing refcounts.
+    ///
+    /// # Safety
+    ///
+    /// Every key of `z3_map` must be a valid `K` and every value a valid `V`.
+    pub(crate) unsafe fn wrap(ctx: &'ctx Context, z3_map: Z3_ast_map) -> AstMap<'ctx, K, V> {
+        Z3_ast_map_inc_ref(ctx.z3_ctx, z3_map);
+        AstMap {
+            ctx,
+            z3_map,
+            _entry: PhantomData,
+        }
+    }
+
+    /// Create an empty AST map.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Bool, Int}, AstMap, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let x = Int::new_const(&ctx, "x");
+    /// let mut m = AstMap::new(&ctx);
+    /// m.insert(&x, &Bool::from_bool(&ctx, true));
+    /// assert!(m.contains_key(&Int::new_const(&ctx, "x")));
+    /// assert_eq!(m.get(&x), Some(Bool::from_bool(&ctx, true)));
+    /// assert_eq!(m.get(&Int::new_const(&ctx, "y")), None);
+    /// ```
+    pub fn new(ctx: &'ctx Context) -> AstMap<'ctx, K, V> {
+        unsafe { Self::wrap(ctx, Z3_mk_ast_map(ctx.z3_ctx)) }
+    }
+
+    /// Get this map's context.
+    pub fn get_context(&self) -> &'ctx Context {
+        self.ctx
+    }
+
+    /// Return the number of entries in the map.
+    pub fn len(&self) -> u32 {
+        unsafe { Z3_ast_map_size(self.ctx.z3_ctx, self.z3_map) }
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return `true` if the map has an entry for `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        assert_eq!(self.ctx, key.get_ctx());
+        unsafe { Z3_ast_map_contains(self.ctx.z3_ctx, self.z3_map, key.get_z3_ast()) }
+    }
+
+    /// Return the value associated with `key`, if any.
+    pub fn get(&self, key: &K) -> Option<V> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        Some(unsafe {
+            V::wrap(
+                self.ctx,
+                Z3_ast_map_find(self.ctx.z3_ctx, self.z3_map, key.get_z3_ast()),
+            )
+        })
+    }
+
+    /// Associate `value` with `key`, replacing any previous value.
+    pub fn insert(&mut self, key: &K, value: &V) {
+        assert_eq!(self.ctx, key.get_ctx());
+        assert_eq!(self.ctx, value.get_ctx());
+        unsafe {
+            Z3_ast_map_insert(
+                self.ctx.z3_ctx,
+                self.z3_map,
+                key.get_z3_ast(),
+                value.get_z3_ast(),
+            )
+        };
+    }
+
+    /// Remove the entry for `key`, if any.
+    pub fn remove(&mut self, key: &K) {
+        assert_eq!(self.ctx, key.get_ctx());
+        unsafe { Z3_ast_map_erase(self.ctx.z3_ctx, self.z3_map, key.get_z3_ast()) };
+    }
+
+    /// Remove all entries from the map.
+    pub fn clear(&mut self) {
+        unsafe { Z3_ast_map_reset(self.ctx.z3_ctx, self.z3_map) };
+    }
+
+    /// Return the keys of the map.
+    pub fn keys(&self) -> AstVector<'ctx, K> {
+        unsafe { AstVector::wrap(self.ctx, Z3_ast_map_keys(self.ctx.z3_ctx, self.z3_map)) }
+    }
+
+    /// Iterate over the `(key, value)` entries of the map.
+    pub fn iter(&self) -> std::vec::IntoIter<(K, V)> {
+        let entries: Vec<(K, V)> = self
+            .keys()
+            .into_iter()
+            .map(|k| {
+                let v = unsafe {
+                    V::wrap(
+                        self.ctx,
+                        Z3_ast_map_find(self.ctx.z3_ctx, self.z3_map, k.get_z3_ast()),
+                    )
+                };
+                (k, v)
+            })
+            .collect();
+        entries.into_iter()
+    }
+}
+
+impl<'ctx, K, V> AstMap<'ctx, K, V>
+where
+    K: Ast<'ctx> + Translate,
+    V: Ast<'ctx> + Translate,
+{
+    /// Translate the map, and all of its keys and values, to context `dest`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Bool, Int}, AstMap, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let mut m = AstMap::new(&ctx);
+    /// m.insert(&Int::new_const(&ctx, "x"), &Bool::from_bool(&ctx, true));
+    /// let other = Context::new(&cfg);
+    /// let n: AstMap<Int, Bool> = m.translate(&other);
+    /// assert_eq!(n.get(&Int::new_const(&other, "x")), Some(Bool::from_bool(&other, true)));
+    /// ```
+    pub fn translate<'dest_ctx>(
+        &self,
+        dest: &'dest_ctx Context,
+    ) -> AstMap<'dest_ctx, K::Target<'dest_ctx>, V::Target<'dest_ctx>> {
+        // As for `AstVector::translate()`, `AstMap::wrap()` cannot be used
+        // without stating that the targets are ASTs.
+        let z3_map = unsafe {
+            let z3_map = Z3_mk_ast_map(dest.z3_ctx);
+            Z3_ast_map_inc_ref(dest.z3_ctx, z3_map);
+            for (k, v) in self.iter() {
+                let k = Dynamic::wrap(
+                    dest,
+                    Z3_translate(self.ctx.z3_ctx, k.get_z3_ast(), dest.z3_ctx),
+                );
+                let v = Dynamic::wrap(
+                    dest,
+                    Z3_translate(self.ctx.z3_ctx, v.get_z3_ast(), dest.z3_ctx),
+                );
+                Z3_ast_map_insert(dest.z3_ctx, z3_map, k.get_z3_ast(), v.get_z3_ast());
+            }
+            z3_map
+        };
+        AstMap {
+            ctx: dest,
+            z3_map,
+            _entry: PhantomData,
+        }
+    }
+}
+
+impl<'ctx, K: Ast<'ctx>, V: Ast<'ctx>> Clone for AstMap<'ctx, K, V> {
+    fn clone(&self) -> Self {
+        let mut map = Self::new(self.ctx);
+        for (k, v) in self.iter() {
+            map.insert(&k, &v);
+        }
+        map
+    }
+}
+
+impl<K, V> fmt::Display for AstMap<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let p = unsafe { Z3_ast_map_to_string(self.ctx.z3_ctx, self.z3_map) };
+        if p.is_null() {
+            return Result::Err(fmt::Error);
+        }
+        match unsafe { CStr::from_ptr(p) }.to_str() {
+            Ok(s) => write!(f, "{s}"),
+            Err(_) => Result::Err(fmt::Error),
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for AstMap<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl<K, V> Drop for AstMap<'_, K, V> {
+    fn drop(&mut self) {
+        unsafe { Z3_ast_map_dec_ref(self.ctx.z3_ctx, self.z3_map) };
+    }
+}
@@ -0,0 +1,249 @@
+use std::ffi::CStr;
+use std::fmt;
+use std::marker::PhantomData;
+
+use z3_sys::*;
+
+use crate::{ast::Ast, AstVector, Context, Translate};
+
+impl<'ctx, T: Ast<'ctx>> AstVector<'ctx, T> {
+    /// Wrap a raw [`Z3_ast_vector`], managing refcounts.
+    ///
+    /// # Safety
+    ///
+    /// Every element of `z3_vec` must be a valid `T`.
+    pub(crate) unsafe fn wrap(ctx: &'ctx Context, z3_vec: Z3_ast_vector) -> AstVector<'ctx, T> {
+        Z3_ast_vector_inc_ref(ctx.z3_ctx, z3_vec);
+        AstVector {
+            ctx,
+            z3_vec,
+            _elem: PhantomData,
+        }
+    }
+
+    /// Create an empty AST vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::Int, AstVector, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let mut v = AstVector::new(&ctx);
+    /// v.push(&Int::from_i64(&ctx, 1));
+    /// v.push(&Int::from_i64(&ctx, 2));
+    /// assert_eq!(v.len(), 2);
+    /// assert_eq!(v.get(1), Some(Int::from_i64(&ctx, 2)));
+    /// assert_eq!(v.get(2), None);
+    /// ```
+    pub fn new(ctx: &'ctx Context) -> AstVector<'ctx, T> {
+        unsafe { Self::wrap(ctx, Z3_mk_ast_vector(ctx.z3_ctx)) }
+    }
+
+    /// Get this vector's context.
+    pub fn get_context(&self) -> &'ctx Context {
+        self.ctx
+    }
+
+    /// Return the number of elements in the vector.
+    pub fn len(&self) -> u32 {
+        unsafe { Z3_ast_vector_size(self.ctx.z3_ctx, self.z3_vec) }
+    }
+
+    /// Return `true` if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the element at position `i`, or `None` if `i` is out of bounds.
+    pub fn get(&self, i: u32) -> Option<T> {
+        if i >= self.len() {
+            return None;
+        }
+        Some(unsafe { T::wrap(self.ctx, Z3_ast_vector_get(self.ctx.z3_ctx, self.z3_vec, i)) })
+    }
+
+    /// Replace the element at position `i` with `ast`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn set(&mut self, i: u32, ast: &T) {
+        assert!(i < self.len(), "index out of bounds");
+        assert_eq!(self.ctx, ast.get_ctx());
+        unsafe { Z3_ast_vector_set(self.ctx.z3_ctx, self.z3_vec, i, ast.get_z3_ast()) };
+    }
+
+    /// Add `ast` to the end of the vector.
+    pub fn push(&mut self, ast: &T) {
+        assert_eq!(self.ctx, ast.get_ctx());
+        unsafe { Z3_ast_vector_push(self.ctx.z3_ctx, self.z3_vec, ast.get_z3_ast()) };
+    }
+
+    /// Return `true` if the vector contains an element equal to `ast`.
+    pub fn contains(&self, ast: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|a| &a == ast)
+    }
+
+    /// Iterate over the elements of the vector.
+    pub fn iter(&self) -> AstVectorIter<'_, 'ctx, T> {
+        AstVectorIter {
+            vec: self,
+            idx: 0,
+            len: self.len(),
+        }
+    }
+
+    /// Copy the elements into a [`Vec`].
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+
+    /// Create a vector holding the given ASTs.
+    pub fn from_slice(ctx: &'ctx Context, asts: &[T]) -> AstVector<'ctx, T> {
+        let mut v = Self::new(ctx);
+        for ast in asts {
+            v.push(ast);
+        }
+        v
+    }
+}
+
+impl<'ctx, T: Translate> AstVector<'ctx, T> {
+    /// Translate the vector, and all of its elements, to context `dest`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::Int, AstVector, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let v = AstVector::from_slice(&ctx, &[Int::new_const(&ctx, "x")]);
+    /// let other = Context::new(&cfg);
+    /// let w: AstVector<Int> = v.translate(&other);
+    /// assert_eq!(w, vec![Int::new_const(&other, "x")]);
+    /// ```
+    pub fn translate<'dest_ctx>(
+        &self,
+        dest: &'dest_ctx Context,
+    ) -> AstVector<'dest_ctx, T::Target<'dest_ctx>> {
+        // `AstVector::wrap()` needs `T::Target<'dest_ctx>: Ast<'dest_ctx>`,
+        // which holds for all the AST types but cannot be stated here.
+        let z3_vec = unsafe {
+            let z3_vec = Z3_ast_vector_translate(self.ctx.z3_ctx, self.z3_vec, dest.z3_ctx);
+            Z3_ast_vector_inc_ref(dest.z3_ctx, z3_vec);
+            z3_vec
+        };
+        AstVector {
+            ctx: dest,
+            z3_vec,
+            _elem: PhantomData,
+        }
+    }
+}
+
+/// Iterator over the elements of an [`AstVector`].
+///
+/// # See also:
+///
+/// - [`AstVector::iter()`]
+#[derive(Debug)]
+pub struct AstVectorIter<'a, 'ctx, T> {
+    vec: &'a AstVector<'ctx, T>,
+    idx: u32,
+    len: u32,
+}
+
+impl<'ctx, T: Ast<'ctx>> Iterator for AstVectorIter<'_, 'ctx, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.idx < self.len {
+            let item = self.vec.get(self.idx);
+            self.idx += 1;
+            item
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.len - self.idx) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<'ctx, T: Ast<'ctx>> Extend<T> for AstVector<'ctx, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for ast in iter {
+            self.push(&ast);
+        }
+    }
+}
+
+impl<'ctx, T: Ast<'ctx>> From<AstVector<'ctx, T>> for Vec<T> {
+    fn from(v: AstVector<'ctx, T>) -> Vec<T> {
+        v.to_vec()
+    }
+}
+
+impl<'ctx, T: Ast<'ctx>> IntoIterator for AstVector<'ctx, T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.to_vec().into_iter()
+    }
+}
+
+impl<'a, 'ctx, T: Ast<'ctx>> IntoIterator for &'a AstVector<'ctx, T> {
+    type Item = T;
+    type IntoIter = AstVectorIter<'a, 'ctx, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'ctx, T: Ast<'ctx>> Clone for AstVector<'ctx, T> {
+    fn clone(&self) -> Self {
+        let mut v = Self::new(self.ctx);
+        v.extend(self.iter());
+        v
+    }
+}
+
+impl<'ctx, T: Ast<'ctx>> PartialEq<Vec<T>> for AstVector<'ctx, T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.len() as usize == other.len() && self.iter().zip(other).all(|(a, b)| &a == b)
+    }
+}
+
+impl<T> fmt::Display for AstVector<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let p = unsafe { Z3_ast_vector_to_string(self.ctx.z3_ctx, self.z3_vec) };
+        if p.is_null() {
+            return Result::Err(fmt::Error);
+        }
+        match unsafe { CStr::from_ptr(p) }.to_str() {
+            Ok(s) => write!(f, "{s}"),
+            Err(_) => Result::Err(fmt::Error),
+        }
+    }
+}
+
+impl<T> fmt::Debug for AstVector<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl<T> Drop for AstVector<'_, T> {
+    fn drop(&mut self) {
+        unsafe { Z3_ast_vector_dec_ref(self.ctx.z3_ctx, self.z3_vec) };
+    }
+}
@@ -3,9 +3,14 @@ use std::ffi::CString;
 
 use z3_sys::*;
 
-use crate::{Config, Context, ContextHandle};
+use crate::{Config, Context, ContextHandle, Error};
 
 impl Context {
+    /// Create a new logical context.
+    ///
+    /// Z3's default error handler aborts the process, so it is disabled.
+    /// Errors are instead reported through the `try_*` variants of the
+    /// methods that can fail, as an [`Error`].
     pub fn new(cfg: &Config) -> Context {
         Context {
             z3_ctx: unsafe {
@@ -21,6 +26,17 @@ impl Context {
         self.z3_ctx
     }
 
+    /// Return the error raised by the most recent Z3 API call on this context.
+    ///
+    /// Every Z3 API call resets the error code, so this must be called
+    /// directly after the call that is being checked.
+    pub(crate) fn check_error(&self) -> Result<(), Error> {
+        match unsafe { Z3_get_error_code(self.z3_ctx) } {
+            ErrorCode::OK => Ok(()),
+            code => Err(Error::from_code(self, code)),
+        }
+    }
+
     /// Interrupt a solver performing a satisfiability test, a tactic processing a goal, or simplify functions.
     pub fn interrupt(&self) {
         self.handle().interrupt();
@@ -32,7 +48,7 @@ impl Context {
     ///
     /// - [`ContextHandle`]
     /// - [`ContextHandle::interrupt()`]
-    pub fn handle(&self) -> ContextHandle {
+    pub fn handle(&self) -> ContextHandle<'_> {
         ContextHandle { ctx: self }
     }
 
@@ -1,10 +1,15 @@
 //! Helpers for building custom [datatype sorts](DatatypeSort).
 
-use std::{convert::TryInto, ptr::null_mut};
+use std::{
+    any::TypeId,
+    convert::{TryFrom, TryInto},
+    ptr::null_mut,
+};
 
 use z3_sys::*;
 
 use crate::{
+    ast::{self, Ast, Dynamic},
     Context, DatatypeAccessor, DatatypeBuilder, DatatypeSort, DatatypeVariant, FuncDecl, Sort,
     Symbol,
 };
@@ -15,9 +20,29 @@ impl<'ctx> DatatypeBuilder<'ctx> {
             ctx,
             name: name.into(),
             constructors: Vec::new(),
+            derived_from: None,
         }
     }
 
+    /// Get the name of the datatype being built.
+    pub fn name(&self) -> &Symbol {
+        &self.name
+    }
+
+    /// Record that the datatype is derived from the Rust type `T`, so that
+    /// different types with the same name can be told apart.
+    #[doc(hidden)]
+    pub fn derived_from<T: 'static>(mut self) -> Self {
+        self.derived_from = Some(TypeId::of::<T>());
+        self
+    }
+
+    /// Check whether the datatype is derived from the Rust type `T`.
+    #[doc(hidden)]
+    pub fn is_derived_from<T: 'static>(&self) -> bool {
+        self.derived_from == Some(TypeId::of::<T>())
+    }
+
     pub fn variant(mut self, name: &str, fields: Vec<(&str, DatatypeAccessor<'ctx>)>) -> Self {
         let mut accessor_vec: Vec<(String, DatatypeAccessor<'ctx>)> = Vec::new();
         for (accessor_name, accessor) in fields {
@@ -193,3 +218,109 @@ pub fn create_datatypes<'ctx>(
 
     datatype_sorts
 }
+
+/// A Rust type that can be used for the fields of types deriving
+/// `Z3Datatype` with the `z3-derive` crate.
+///
+/// The derive implements this trait for the annotated type itself, so that
+/// derived datatypes can refer to each other.
+pub trait DatatypeField<'ctx>: Sized {
+    /// The AST type of the field's sort.
+    type Ast: Ast<'ctx> + TryFrom<Dynamic<'ctx>, Error = String>;
+
+    /// Declare a field of this type in a [`DatatypeBuilder`].
+    fn accessor(ctx: &'ctx Context) -> DatatypeAccessor<'ctx>;
+
+    /// Push the builders of the datatypes this type refers to, directly or
+    /// not, unless `builders` already contains a builder with that name.
+    ///
+    /// The derived implementation panics if that builder was pushed for
+    /// another Rust type with the same name.
+    fn collect_builders(_ctx: &'ctx Context, _builders: &mut Vec<DatatypeBuilder<'ctx>>) {}
+
+    /// Decode a value of the field's sort, as found in a model.
+    ///
+    /// Returns `None` if `value` is not a value of the expected shape, or
+    /// does not fit in `Self`.
+    fn from_value(value: &Dynamic<'ctx>) -> Option<Self>;
+}
+
+/// Create the sort of a datatype deriving `Z3Datatype`, along with the sorts
+/// of all the datatypes it refers to, directly or not.
+///
+/// The sort of `T` comes first. The other sorts can be picked from the
+/// result by their generated `from_sorts`, which is how mutually recursive
+/// types get consistent sorts.
+///
+/// # Panics
+///
+/// Panics if two of the types have the same name, e.g. `a::Point` and
+/// `b::Point`, since the sorts are named and picked by type name.
+pub fn create_derived_datatypes<'ctx, T: DatatypeField<'ctx>>(
+    ctx: &'ctx Context,
+) -> Vec<DatatypeSort<'ctx>> {
+    let mut builders = Vec::new();
+    T::collect_builders(ctx, &mut builders);
+    create_datatypes(builders)
+}
+
+impl<'ctx> DatatypeField<'ctx> for bool {
+    type Ast = ast::Bool<'ctx>;
+
+    fn accessor(ctx: &'ctx Context) -> DatatypeAccessor<'ctx> {
+        DatatypeAccessor::Sort(Sort::bool(ctx))
+    }
+
+    fn from_value(value: &Dynamic<'ctx>) -> Option<Self> {
+        value.as_bool()?.as_bool()
+    }
+}
+
+macro_rules! impl_int_datatype_field {
+    ($as_prim:ident: $($ty:ty),*) => {
+        $(
+            impl<'ctx> DatatypeField<'ctx> for $ty {
+                type Ast = ast::Int<'ctx>;
+
+                fn accessor(ctx: &'ctx Context) -> DatatypeAccessor<'ctx> {
+                    DatatypeAccessor::Sort(Sort::int(ctx))
+                }
+
+                fn from_value(value: &Dynamic<'ctx>) -> Option<Self> {
+                    value.as_int()?.$as_prim()?.try_into().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_int_datatype_field!(as_i64: i8, i16, i32, i64, isize);
+impl_int_datatype_field!(as_u64: u8, u16, u32, u64, usize);
+
+impl<'ctx> DatatypeField<'ctx> for String {
+    type Ast = ast::String<'ctx>;
+
+    fn accessor(ctx: &'ctx Context) -> DatatypeAccessor<'ctx> {
+        DatatypeAccessor::Sort(Sort::string(ctx))
+    }
+
+    fn from_value(value: &Dynamic<'ctx>) -> Option<Self> {
+        value.as_string()?.as_string()
+    }
+}
+
+impl<'ctx, T: DatatypeField<'ctx>> DatatypeField<'ctx> for Box<T> {
+    type Ast = T::Ast;
+
+    fn accessor(ctx: &'ctx Context) -> DatatypeAccessor<'ctx> {
+        T::accessor(ctx)
+    }
+
+    fn collect_builders(ctx: &'ctx Context, builders: &mut Vec<DatatypeBuilder<'ctx>>) {
+        T::collect_builders(ctx, builders)
+    }
+
+    fn from_value(value: &Dynamic<'ctx>) -> Option<Self> {
+        T::from_value(value).map(Box::new)
+    }
+}
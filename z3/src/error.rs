@@ -0,0 +1,53 @@
+use std::ffi::{CStr, NulError};
+use std::fmt;
+
+use z3_sys::*;
+
+use crate::{Context, Error};
+
+impl Error {
+    /// Build an `Error` from a Z3 error code, retrieving the message from `ctx`.
+    pub(crate) fn from_code(ctx: &Context, code: ErrorCode) -> Error {
+        let message = unsafe {
+            let p = Z3_get_error_msg(ctx.z3_ctx, code);
+            if p.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(p).to_string_lossy().into_owned()
+            }
+        };
+        Error { code, message }
+    }
+
+    /// Build an `Error` for a string argument that cannot be passed to Z3
+    /// because it contains a NUL byte.
+    pub(crate) fn from_nul(err: NulError) -> Error {
+        Error::invalid_arg(err.to_string())
+    }
+
+    /// Build an `Error` for an argument rejected before reaching Z3.
+    pub(crate) fn invalid_arg(message: String) -> Error {
+        Error {
+            code: ErrorCode::InvalidArg,
+            message,
+        }
+    }
+
+    /// Return the [`ErrorCode`] reported by Z3.
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    /// Return the message Z3 associated with this error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Z3 error ({:?}): {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
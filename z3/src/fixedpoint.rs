@@ -0,0 +1,350 @@
+use std::convert::TryInto;
+use std::ffi::{CStr, CString};
+use std::fmt;
+
+use z3_sys::*;
+
+use crate::{
+    ast::{Ast, Bool, Dynamic},
+    AstVector, Context, Error, Fixedpoint, FuncDecl, Params, SatResult, Statistics, Symbol,
+};
+
+impl<'ctx> Fixedpoint<'ctx> {
+    unsafe fn wrap(ctx: &'ctx Context, z3_fp: Z3_fixedpoint) -> Fixedpoint<'ctx> {
+        Z3_fixedpoint_inc_ref(ctx.z3_ctx, z3_fp);
+        Fixedpoint { ctx, z3_fp }
+    }
+
+    /// Create a new fixedpoint context.
+    pub fn new(ctx: &'ctx Context) -> Fixedpoint<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_fixedpoint(ctx.z3_ctx)) }
+    }
+
+    /// Get this fixedpoint context's context.
+    pub fn get_context(&self) -> &'ctx Context {
+        self.ctx
+    }
+
+    /// Register a relation (a function declaration with range `Bool`)
+    /// so that it can be used as the head of rules and in queries.
+    pub fn register_relation(&self, relation: &FuncDecl<'ctx>) {
+        unsafe {
+            Z3_fixedpoint_register_relation(self.ctx.z3_ctx, self.z3_fp, relation.z3_func_decl)
+        };
+    }
+
+    /// Add a universal Horn clause as a named rule.
+    ///
+    /// The rule should be of the form:
+    ///
+    /// ```text
+    /// rule ::= (forall (bound-vars) rule)
+    ///       |  (=> (and body-literals) head)
+    ///       |  head
+    /// ```
+    ///
+    /// # See also:
+    ///
+    /// - [`Fixedpoint::update_rule()`]
+    pub fn add_rule(&self, rule: &Bool<'ctx>, name: Option<Symbol>) {
+        let name = name
+            .map(|n| n.as_z3_symbol(self.ctx))
+            .unwrap_or_else(std::ptr::null_mut);
+        unsafe { Z3_fixedpoint_add_rule(self.ctx.z3_ctx, self.z3_fp, rule.z3_ast, name) };
+    }
+
+    /// Update a named rule. A rule with the same name must have been
+    /// previously added with [`Fixedpoint::add_rule()`].
+    pub fn update_rule(&self, rule: &Bool<'ctx>, name: Symbol) {
+        unsafe {
+            Z3_fixedpoint_update_rule(
+                self.ctx.z3_ctx,
+                self.z3_fp,
+                rule.z3_ast,
+                name.as_z3_symbol(self.ctx),
+            )
+        };
+    }
+
+    /// Add a table fact to the fixedpoint context.
+    ///
+    /// The `args` are indices into the (finite) domain sorts of `relation`.
+    /// This has the same effect as adding a rule where `relation` is applied
+    /// to the arguments.
+    pub fn add_fact(&self, relation: &FuncDecl<'ctx>, args: &[u32]) {
+        let mut args = args.to_vec();
+        unsafe {
+            Z3_fixedpoint_add_fact(
+                self.ctx.z3_ctx,
+                self.z3_fp,
+                relation.z3_func_decl,
+                args.len().try_into().unwrap(),
+                args.as_mut_ptr(),
+            )
+        };
+    }
+
+    /// Assert a background axiom to the fixedpoint context.
+    ///
+    /// Background axioms are used by the PDR/Spacer engines and ignored in
+    /// Datalog mode.
+    pub fn assert(&self, axiom: &Bool<'ctx>) {
+        unsafe { Z3_fixedpoint_assert(self.ctx.z3_ctx, self.z3_fp, axiom.z3_ast) };
+    }
+
+    /// Pose a query against the asserted rules.
+    ///
+    /// Returns [`SatResult::Sat`] if the query is reachable, in which case
+    /// [`Fixedpoint::get_answer()`] describes the derivation, and
+    /// [`SatResult::Unsat`] if it is not, in which case the answer holds
+    /// the inductive invariants that prove it.
+    ///
+    /// # See also:
+    ///
+    /// - [`Fixedpoint::query_relations()`]
+    pub fn query(&self, query: &Bool<'ctx>) -> SatResult {
+        SatResult::from_lbool(unsafe {
+            Z3_fixedpoint_query(self.ctx.z3_ctx, self.z3_fp, query.z3_ast)
+        })
+    }
+
+    /// Pose a query that is a disjunction of the given relations.
+    pub fn query_relations(&self, relations: &[&FuncDecl<'ctx>]) -> SatResult {
+        let relations: Vec<Z3_func_decl> = relations.iter().map(|r| r.z3_func_decl).collect();
+        SatResult::from_lbool(unsafe {
+            Z3_fixedpoint_query_relations(
+                self.ctx.z3_ctx,
+                self.z3_fp,
+                relations.len().try_into().unwrap(),
+                relations.as_ptr(),
+            )
+        })
+    }
+
+    /// Pose a query against the asserted rules, starting at the given level.
+    ///
+    /// Note: this functionality is Spacer specific.
+    pub fn query_from_lvl(&self, query: &Bool<'ctx>, level: u32) -> SatResult {
+        SatResult::from_lbool(unsafe {
+            Z3_fixedpoint_query_from_lvl(self.ctx.z3_ctx, self.z3_fp, query.z3_ast, level)
+        })
+    }
+
+    /// Retrieve a formula that encodes the answer to the last query.
+    ///
+    /// Returns `None` if no answer is available.
+    pub fn get_answer(&self) -> Option<Dynamic<'ctx>> {
+        let ast = unsafe { Z3_fixedpoint_get_answer(self.ctx.z3_ctx, self.z3_fp) };
+        if ast.is_null() {
+            None
+        } else {
+            Some(unsafe { Dynamic::wrap(self.ctx, ast) })
+        }
+    }
+
+    /// Retrieve a bottom-up sequence of ground facts leading to the query.
+    ///
+    /// The previous call to [`Fixedpoint::query()`] must have returned
+    /// [`SatResult::Sat`].
+    ///
+    /// Note: this functionality is Spacer specific.
+    pub fn get_ground_sat_answer(&self) -> Option<Dynamic<'ctx>> {
+        let ast = unsafe { Z3_fixedpoint_get_ground_sat_answer(self.ctx.z3_ctx, self.z3_fp) };
+        if ast.is_null() {
+            None
+        } else {
+            Some(unsafe { Dynamic::wrap(self.ctx, ast) })
+        }
+    }
+
+    /// Retrieve the rules used along the counterexample trace of the last
+    /// satisfiable query.
+    ///
+    /// Note: this functionality is Spacer specific.
+    pub fn get_rules_along_trace(&self) -> AstVector<'ctx, Bool<'ctx>> {
+        let rules = unsafe { Z3_fixedpoint_get_rules_along_trace(self.ctx.z3_ctx, self.z3_fp) };
+        if rules.is_null() {
+            return AstVector::new(self.ctx);
+        }
+        unsafe { AstVector::wrap(self.ctx, rules) }
+    }
+
+    /// Retrieve a string that describes the last status returned by [`Fixedpoint::query()`].
+    ///
+    /// Use this method when [`Fixedpoint::query()`] returns [`SatResult::Unknown`].
+    pub fn get_reason_unknown(&self) -> Option<String> {
+        let p = unsafe { Z3_fixedpoint_get_reason_unknown(self.ctx.z3_ctx, self.z3_fp) };
+        if p.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(p) }
+            .to_str()
+            .ok()
+            .map(|s| s.to_string())
+    }
+
+    /// Retrieve the maximal number of levels explored for `relation`.
+    ///
+    /// Note: this functionality is PDR/Spacer specific.
+    pub fn get_num_levels(&self, relation: &FuncDecl<'ctx>) -> u32 {
+        unsafe { Z3_fixedpoint_get_num_levels(self.ctx.z3_ctx, self.z3_fp, relation.z3_func_decl) }
+    }
+
+    /// Retrieve the properties of `relation` that are known at exactly `level`
+    /// unfoldings. A `level` of `-1` denotes the fixedpoint itself.
+    ///
+    /// Note: this functionality is PDR/Spacer specific.
+    pub fn get_cover_delta(&self, level: i32, relation: &FuncDecl<'ctx>) -> Option<Bool<'ctx>> {
+        let ast = unsafe {
+            Z3_fixedpoint_get_cover_delta(self.ctx.z3_ctx, self.z3_fp, level, relation.z3_func_decl)
+        };
+        if ast.is_null() {
+            None
+        } else {
+            Some(unsafe { Bool::wrap(self.ctx, ast) })
+        }
+    }
+
+    /// Add a property of `relation` at `level`. A `level` of `-1` means the
+    /// property holds for the fixedpoint.
+    ///
+    /// The free variables of `property` are interpreted as the arguments of
+    /// `relation`, in order.
+    ///
+    /// Note: this functionality is PDR/Spacer specific.
+    pub fn add_cover(&self, level: i32, relation: &FuncDecl<'ctx>, property: &Bool<'ctx>) {
+        unsafe {
+            Z3_fixedpoint_add_cover(
+                self.ctx.z3_ctx,
+                self.z3_fp,
+                level,
+                relation.z3_func_decl,
+                property.z3_ast,
+            )
+        };
+    }
+
+    /// Add an assumed invariant of `relation`.
+    ///
+    /// Note: this functionality is Spacer specific.
+    pub fn add_invariant(&self, relation: &FuncDecl<'ctx>, property: &Bool<'ctx>) {
+        unsafe {
+            Z3_fixedpoint_add_invariant(
+                self.ctx.z3_ctx,
+                self.z3_fp,
+                relation.z3_func_decl,
+                property.z3_ast,
+            )
+        };
+    }
+
+    /// Retrieve the reachable states of `relation`.
+    ///
+    /// Note: this functionality is Spacer specific.
+    pub fn get_reachable(&self, relation: &FuncDecl<'ctx>) -> Option<Bool<'ctx>> {
+        let ast = unsafe {
+            Z3_fixedpoint_get_reachable(self.ctx.z3_ctx, self.z3_fp, relation.z3_func_decl)
+        };
+        if ast.is_null() {
+            None
+        } else {
+            Some(unsafe { Bool::wrap(self.ctx, ast) })
+        }
+    }
+
+    /// Retrieve the rules currently in the fixedpoint context.
+    pub fn get_rules(&self) -> AstVector<'ctx, Bool<'ctx>> {
+        let rules = unsafe { Z3_fixedpoint_get_rules(self.ctx.z3_ctx, self.z3_fp) };
+        unsafe { AstVector::wrap(self.ctx, rules) }
+    }
+
+    /// Retrieve the background axioms currently in the fixedpoint context.
+    pub fn get_assertions(&self) -> AstVector<'ctx, Bool<'ctx>> {
+        let assertions = unsafe { Z3_fixedpoint_get_assertions(self.ctx.z3_ctx, self.z3_fp) };
+        unsafe { AstVector::wrap(self.ctx, assertions) }
+    }
+
+    /// Parse an SMT-LIB2 string with fixedpoint rules, adding the rules,
+    /// relations and background axioms to this context.
+    ///
+    /// Returns the queries contained in the input, or an [`Error`] if the
+    /// input contains a NUL byte or Z3 fails to parse it.
+    pub fn try_from_string<T: Into<Vec<u8>>>(
+        &self,
+        source_string: T,
+    ) -> Result<Vec<Bool<'ctx>>, Error> {
+        let source_cstring = CString::new(source_string).map_err(Error::from_nul)?;
+        let queries = unsafe {
+            Z3_fixedpoint_from_string(self.ctx.z3_ctx, self.z3_fp, source_cstring.as_ptr())
+        };
+        self.ctx.check_error()?;
+        Ok(unsafe { AstVector::wrap(self.ctx, queries) }.into())
+    }
+
+    /// Parse an SMT-LIB2 file with fixedpoint rules, adding the rules,
+    /// relations and background axioms to this context.
+    ///
+    /// Returns the queries contained in the file, or an [`Error`] if Z3
+    /// fails to read or parse it.
+    pub fn try_from_file<T: Into<Vec<u8>>>(&self, file_name: T) -> Result<Vec<Bool<'ctx>>, Error> {
+        let file_cstring = CString::new(file_name).map_err(Error::from_nul)?;
+        let queries =
+            unsafe { Z3_fixedpoint_from_file(self.ctx.z3_ctx, self.z3_fp, file_cstring.as_ptr()) };
+        self.ctx.check_error()?;
+        Ok(unsafe { AstVector::wrap(self.ctx, queries) }.into())
+    }
+
+    /// Configure the parameters for this fixedpoint context.
+    ///
+    /// The engine is selected with the `engine` parameter, e.g. `"spacer"`
+    /// or `"datalog"`.
+    pub fn set_params(&self, params: &Params<'ctx>) {
+        unsafe { Z3_fixedpoint_set_params(self.ctx.z3_ctx, self.z3_fp, params.z3_params) };
+    }
+
+    /// Return a string describing all available parameters.
+    pub fn get_help(&self) -> String {
+        let p = unsafe { Z3_fixedpoint_get_help(self.ctx.z3_ctx, self.z3_fp) };
+        if p.is_null() {
+            return String::new();
+        }
+        unsafe { CStr::from_ptr(p) }.to_string_lossy().into_owned()
+    }
+
+    /// Retrieve the statistics for the last [`Fixedpoint::query()`].
+    pub fn get_statistics(&self) -> Statistics<'ctx> {
+        unsafe {
+            Statistics::wrap(
+                self.ctx,
+                Z3_fixedpoint_get_statistics(self.ctx.z3_ctx, self.z3_fp),
+            )
+        }
+    }
+}
+
+impl fmt::Display for Fixedpoint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let p = unsafe {
+            Z3_fixedpoint_to_string(self.ctx.z3_ctx, self.z3_fp, 0, std::ptr::null_mut())
+        };
+        if p.is_null() {
+            return Result::Err(fmt::Error);
+        }
+        match unsafe { CStr::from_ptr(p) }.to_str() {
+            Ok(s) => write!(f, "{s}"),
+            Err(_) => Result::Err(fmt::Error),
+        }
+    }
+}
+
+impl fmt::Debug for Fixedpoint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl Drop for Fixedpoint<'_> {
+    fn drop(&mut self) {
+        unsafe { Z3_fixedpoint_dec_ref(self.ctx.z3_ctx, self.z3_fp) };
+    }
+}
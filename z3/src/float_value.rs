@@ -0,0 +1,181 @@
+use std::ffi::CStr;
+use std::str::FromStr;
+
+use num::bigint::BigUint;
+use num::{One, Zero};
+use z3_sys::*;
+
+use crate::ast::Float;
+
+/// The concrete value of a floating-point numeral, as returned by
+/// [`Float::as_float_value()`].
+///
+/// Finite non-zero values are kept exactly, independently of the
+/// `(ebits, sbits)` format they came from: the value is
+/// `(-1)^negative * significand * 2^exponent`.
+///
+/// # See also:
+///
+/// - [`FloatValue::to_f32()`]
+/// - [`FloatValue::to_f64()`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FloatValue {
+    NaN,
+    Infinity {
+        negative: bool,
+    },
+    Zero {
+        negative: bool,
+    },
+    Finite {
+        negative: bool,
+        exponent: i64,
+        significand: BigUint,
+    },
+}
+
+impl FloatValue {
+    /// Convert to an `f32`, if that can be done without losing precision.
+    ///
+    /// NaN, infinities and zeros always convert. Finite values convert
+    /// if they are exactly representable as a (possibly subnormal) `f32`.
+    pub fn to_f32(&self) -> Option<f32> {
+        self.to_ieee_bits(8, 24)
+            .map(|bits| f32::from_bits(bits as u32))
+    }
+
+    /// Convert to an `f64`, if that can be done without losing precision.
+    ///
+    /// NaN, infinities and zeros always convert. Finite values convert
+    /// if they are exactly representable as a (possibly subnormal) `f64`.
+    pub fn to_f64(&self) -> Option<f64> {
+        self.to_ieee_bits(11, 53).map(f64::from_bits)
+    }
+
+    /// Encode as the bits of an IEEE-754 binary format with `ebits` exponent
+    /// bits and `sbits` significand bits (including the hidden bit), with the
+    /// sign in bit `ebits + sbits - 1`.
+    fn to_ieee_bits(&self, ebits: u32, sbits: u32) -> Option<u64> {
+        let sign_shift = ebits + sbits - 1;
+        let exp_mask = (1u64 << ebits) - 1;
+        let frac_bits = sbits - 1;
+        let sign = |negative: bool| (negative as u64) << sign_shift;
+        match self {
+            FloatValue::NaN => Some((exp_mask << frac_bits) | (1 << (frac_bits - 1))),
+            FloatValue::Infinity { negative } => Some(sign(*negative) | (exp_mask << frac_bits)),
+            FloatValue::Zero { negative } => Some(sign(*negative)),
+            FloatValue::Finite {
+                negative,
+                exponent,
+                significand,
+            } => {
+                if significand.is_zero() {
+                    return Some(sign(*negative));
+                }
+                // Normalize to an odd significand.
+                let trailing = significand.trailing_zeros().unwrap_or(0);
+                let sig = significand >> trailing;
+                let exp = exponent + trailing as i64;
+                let len = sig.bits() as i64;
+                let top = exp + len - 1;
+
+                let bias = (1i64 << (ebits - 1)) - 1;
+                let emin = 1 - bias;
+                let p = sbits as i64;
+                if top > bias {
+                    return None;
+                }
+                let (biased_exp, frac) = if top >= emin {
+                    if len > p {
+                        return None;
+                    }
+                    let m = sig << (p - len) as usize;
+                    let frac = m - (BigUint::one() << frac_bits as usize);
+                    ((top + bias) as u64, frac)
+                } else {
+                    let lowest = emin - (p - 1);
+                    if exp < lowest {
+                        return None;
+                    }
+                    (0, sig << (exp - lowest) as usize)
+                };
+                let frac = frac.iter_u64_digits().next().unwrap_or(0);
+                Some(sign(*negative) | (biased_exp << frac_bits) | frac)
+            }
+        }
+    }
+}
+
+impl<'ctx> Float<'ctx> {
+    /// Decode the value of a floating-point numeral, for example one
+    /// obtained from [`Model::eval()`](crate::Model::eval).
+    ///
+    /// Unlike [`Float::as_f64()`], this handles NaN, infinities, subnormals
+    /// and arbitrary `(ebits, sbits)` formats exactly.
+    ///
+    /// Returns `None` if this is not a numeral.
+    ///
+    /// ```
+    /// # use z3::{ast::Float, Config, Context, FloatValue};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let tiny = Float::from_f64(&ctx, 5e-324).as_float_value().unwrap();
+    /// assert_eq!(tiny.to_f64(), Some(5e-324));
+    /// assert_eq!(tiny.to_f32(), None);
+    ///
+    /// let nan = Float::nan(&ctx, 15, 113).as_float_value().unwrap();
+    /// assert_eq!(nan, FloatValue::NaN);
+    /// ```
+    pub fn as_float_value(&self) -> Option<FloatValue> {
+        let z3_ctx = self.ctx.z3_ctx;
+        let ast = self.z3_ast;
+        unsafe {
+            if !Z3_is_numeral_ast(z3_ctx, ast) {
+                return None;
+            }
+            if Z3_fpa_is_numeral_nan(z3_ctx, ast) {
+                return Some(FloatValue::NaN);
+            }
+            let mut sgn = 0;
+            if !Z3_fpa_get_numeral_sign(z3_ctx, ast, &mut sgn) {
+                return None;
+            }
+            let negative = sgn != 0;
+            if Z3_fpa_is_numeral_inf(z3_ctx, ast) {
+                return Some(FloatValue::Infinity { negative });
+            }
+            if Z3_fpa_is_numeral_zero(z3_ctx, ast) {
+                return Some(FloatValue::Zero { negative });
+            }
+
+            let sort = Z3_get_sort(z3_ctx, ast);
+            let frac_bits = Z3_fpa_get_sbits(z3_ctx, sort) as i64 - 1;
+
+            let mut exponent = 0;
+            if !Z3_fpa_get_numeral_exponent_int64(z3_ctx, ast, &mut exponent, false) {
+                return None;
+            }
+
+            // The significand is returned without the hidden bit.
+            let sig_bv = Z3_fpa_get_numeral_significand_bv(z3_ctx, ast);
+            Z3_inc_ref(z3_ctx, sig_bv);
+            let sig_str = Z3_get_numeral_string(z3_ctx, sig_bv);
+            let fraction = if sig_str.is_null() {
+                None
+            } else {
+                BigUint::from_str(&CStr::from_ptr(sig_str).to_string_lossy()).ok()
+            };
+            Z3_dec_ref(z3_ctx, sig_bv);
+            let mut significand = fraction?;
+
+            if !Z3_fpa_is_numeral_subnormal(z3_ctx, ast) {
+                significand += BigUint::one() << frac_bits as usize;
+            }
+            Some(FloatValue::Finite {
+                negative,
+                exponent: exponent - frac_bits,
+                significand,
+            })
+        }
+    }
+}
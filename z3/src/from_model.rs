@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+use num::bigint::BigInt;
+use num::rational::BigRational;
+use num::One;
+use z3_sys::*;
+
+use crate::array_value::decode_array;
+use crate::ast::{Ast, Dynamic};
+use crate::{DeclKind, Model, SortKind};
+
+/// A Rust type that values of Z3 terms can be converted to.
+///
+/// Implemented for `bool`, the primitive integers (from integer or
+/// bit-vector values), [`BigInt`], [`BigRational`], `String`, `f32` and
+/// `f64` (from floating-point values), tuples (from values of single
+/// constructor datatypes), `Vec` (from sequences) and `HashMap` (from
+/// arrays).
+///
+/// # See also:
+///
+/// - [`Model::get()`]
+pub trait FromModel<'ctx>: Sized {
+    /// Convert `value`, the value of a term in `model`.
+    ///
+    /// `value` is expected to be the result of a
+    /// [`Model::eval()`](crate::Model::eval) with model completion; `model`
+    /// is used to look into values that refer to function
+    /// interpretations, such as `as-array` terms.
+    fn from_model(model: &Model<'ctx>, value: &Dynamic<'ctx>) -> Result<Self, FromModelError>;
+}
+
+/// The reason why [`Model::get()`] could not convert a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromModelError {
+    /// The term could not be evaluated in the model.
+    NoValue,
+    /// The value is not of a sort the Rust type can be converted from.
+    WrongSort {
+        expected: &'static str,
+        found: String,
+    },
+    /// The value is not a literal of its sort, e.g. an irrational number or
+    /// a `lambda`.
+    NotALiteral(String),
+    /// The value does not fit in the Rust type.
+    OutOfRange { value: String, target: &'static str },
+}
+
+impl fmt::Display for FromModelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            FromModelError::NoValue => write!(f, "term has no value in the model"),
+            FromModelError::WrongSort { expected, found } => {
+                write!(f, "expected a value of sort {expected}, found sort {found}")
+            }
+            FromModelError::NotALiteral(value) => write!(f, "value is not a literal: {value}"),
+            FromModelError::OutOfRange { value, target } => {
+                write!(f, "value {value} does not fit in {target}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromModelError {}
+
+impl<'ctx> Model<'ctx> {
+    /// Evaluate `ast` in the model, with model completion, and convert the
+    /// result to the Rust type `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int, BV}, Config, Context, FromModelError, SatResult, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let x = Int::new_const(&ctx, "x");
+    /// let b = BV::new_const(&ctx, "b", 8);
+    /// solver.assert(&x._eq(&Int::from_i64(&ctx, 300)));
+    /// solver.assert(&b._eq(&BV::from_i64(&ctx, -2, 8)));
+    /// assert_eq!(solver.check(), SatResult::Sat);
+    ///
+    /// let model = solver.get_model().unwrap();
+    /// assert_eq!(model.get::<i64>(&x), Ok(300));
+    /// assert_eq!(model.get::<i8>(&b), Ok(-2));
+    /// assert_eq!(model.get::<u8>(&b), Ok(254));
+    /// assert!(matches!(model.get::<u8>(&x), Err(FromModelError::OutOfRange { .. })));
+    /// assert!(matches!(model.get::<bool>(&x), Err(FromModelError::WrongSort { .. })));
+    /// ```
+    pub fn get<T: FromModel<'ctx>>(&self, ast: &dyn Ast<'ctx>) -> Result<T, FromModelError> {
+        let value = self
+            .eval(&Dynamic::from_ast(ast), true)
+            .ok_or(FromModelError::NoValue)?;
+        T::from_model(self, &value)
+    }
+}
+
+fn wrong_sort(expected: &'static str, value: &Dynamic) -> FromModelError {
+    FromModelError::WrongSort {
+        expected,
+        found: value.get_sort().to_string(),
+    }
+}
+
+fn not_a_literal(value: &Dynamic) -> FromModelError {
+    FromModelError::NotALiteral(value.to_string())
+}
+
+/// Return the decimal (or `p/q` for rationals) representation of a numeral.
+fn numeral_string(value: &Dynamic) -> Option<String> {
+    let ctx = value.get_ctx();
+    unsafe {
+        if !Z3_is_numeral_ast(ctx.z3_ctx, value.get_z3_ast()) {
+            return None;
+        }
+        let s = Z3_get_numeral_string(ctx.z3_ctx, value.get_z3_ast());
+        if s.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(s).to_string_lossy().into_owned())
+    }
+}
+
+/// Convert an integer or bit-vector value, reading bit-vectors as two's
+/// complement if `signed`.
+fn integer_value(value: &Dynamic, signed: bool) -> Result<BigInt, FromModelError> {
+    let expected = "Int or BitVec";
+    let bits = match value.sort_kind() {
+        SortKind::Int => None,
+        SortKind::BV => Some(value.as_bv().unwrap().get_size()),
+        _ => return Err(wrong_sort(expected, value)),
+    };
+    let n = numeral_string(value)
+        .and_then(|s| BigInt::from_str(&s).ok())
+        .ok_or_else(|| not_a_literal(value))?;
+    match bits {
+        Some(bits) if signed && n.bits() == u64::from(bits) => Ok(n - (BigInt::one() << bits)),
+        _ => Ok(n),
+    }
+}
+
+impl<'ctx> FromModel<'ctx> for bool {
+    fn from_model(_model: &Model<'ctx>, value: &Dynamic<'ctx>) -> Result<Self, FromModelError> {
+        value
+            .as_bool()
+            .ok_or_else(|| wrong_sort("Bool", value))?
+            .as_bool()
+            .ok_or_else(|| not_a_literal(value))
+    }
+}
+
+macro_rules! impl_from_model_for_int {
+    ($signed:expr => $($ty:ty),*) => {
+        $(
+            impl<'ctx> FromModel<'ctx> for $ty {
+                fn from_model(
+                    _model: &Model<'ctx>,
+                    value: &Dynamic<'ctx>,
+                ) -> Result<Self, FromModelError> {
+                    let n = integer_value(value, $signed)?;
+                    <$ty>::try_from(&n).map_err(|_| FromModelError::OutOfRange {
+                        value: n.to_string(),
+                        target: stringify!($ty),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_from_model_for_int!(true => i8, i16, i32, i64, i128, isize);
+impl_from_model_for_int!(false => u8, u16, u32, u64, u128, usize);
+
+/// Bit-vectors are read as unsigned.
+impl<'ctx> FromModel<'ctx> for BigInt {
+    fn from_model(_model: &Model<'ctx>, value: &Dynamic<'ctx>) -> Result<Self, FromModelError> {
+        integer_value(value, false)
+    }
+}
+
+impl<'ctx> FromModel<'ctx> for BigRational {
+    fn from_model(_model: &Model<'ctx>, value: &Dynamic<'ctx>) -> Result<Self, FromModelError> {
+        match value.sort_kind() {
+            SortKind::Int | SortKind::Real => numeral_string(value)
+                .and_then(|s| BigRational::from_str(&s).ok())
+                .ok_or_else(|| not_a_literal(value)),
+            _ => Err(wrong_sort("Int or Real", value)),
+        }
+    }
+}
+
+impl<'ctx> FromModel<'ctx> for String {
+    fn from_model(_model: &Model<'ctx>, value: &Dynamic<'ctx>) -> Result<Self, FromModelError> {
+        value
+            .as_string()
+            .filter(|_| value.get_sort() == crate::Sort::string(value.get_ctx()))
+            .ok_or_else(|| wrong_sort("String", value))?
+            .as_string()
+            .ok_or_else(|| not_a_literal(value))
+    }
+}
+
+macro_rules! impl_from_model_for_float {
+    ($ty:ty, $to:ident) => {
+        impl<'ctx> FromModel<'ctx> for $ty {
+            fn from_model(
+                _model: &Model<'ctx>,
+                value: &Dynamic<'ctx>,
+            ) -> Result<Self, FromModelError> {
+                let float = value
+                    .as_float()
+                    .ok_or_else(|| wrong_sort("FloatingPoint", value))?
+                    .as_float_value()
+                    .ok_or_else(|| not_a_literal(value))?;
+                float.$to().ok_or_else(|| FromModelError::OutOfRange {
+                    value: value.to_string(),
+                    target: stringify!($ty),
+                })
+            }
+        }
+    };
+}
+
+impl_from_model_for_float!(f32, to_f32);
+impl_from_model_for_float!(f64, to_f64);
+
+macro_rules! impl_from_model_for_tuple {
+    ($len:expr => $($t:ident $i:tt),+) => {
+        /// Converted from values of datatypes with a single constructor of
+        /// the same arity.
+        impl<'ctx, $($t: FromModel<'ctx>),+> FromModel<'ctx> for ($($t,)+) {
+            fn from_model(
+                model: &Model<'ctx>,
+                value: &Dynamic<'ctx>,
+            ) -> Result<Self, FromModelError> {
+                if value.sort_kind() != SortKind::Datatype {
+                    return Err(wrong_sort("Datatype", value));
+                }
+                let args = value.children();
+                if !value.is_app() || args.len() != $len {
+                    return Err(not_a_literal(value));
+                }
+                Ok(($($t::from_model(model, &args[$i])?,)+))
+            }
+        }
+    };
+}
+
+impl_from_model_for_tuple!(1 => A 0);
+impl_from_model_for_tuple!(2 => A 0, B 1);
+impl_from_model_for_tuple!(3 => A 0, B 1, C 2);
+impl_from_model_for_tuple!(4 => A 0, B 1, C 2, D 3);
+impl_from_model_for_tuple!(5 => A 0, B 1, C 2, D 3, E 4);
+impl_from_model_for_tuple!(6 => A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// Converted from sequence values built with `seq.empty`, `seq.unit` and
+/// `seq.++`.
+impl<'ctx, T: FromModel<'ctx>> FromModel<'ctx> for Vec<T> {
+    fn from_model(model: &Model<'ctx>, value: &Dynamic<'ctx>) -> Result<Self, FromModelError> {
+        if value.sort_kind() != SortKind::Seq {
+            return Err(wrong_sort("Seq", value));
+        }
+        let mut elements = Vec::new();
+        let mut pending = vec![value.clone()];
+        while let Some(seq) = pending.pop() {
+            match seq.safe_decl().map(|d| d.kind()) {
+                Ok(DeclKind::SEQ_EMPTY) => {}
+                Ok(DeclKind::SEQ_UNIT) => elements.push(T::from_model(model, &seq.children()[0])?),
+                Ok(DeclKind::SEQ_CONCAT) => pending.extend(seq.children().into_iter().rev()),
+                _ => return Err(not_a_literal(&seq)),
+            }
+        }
+        Ok(elements)
+    }
+}
+
+/// Converted from array values, keeping only the entries that differ from
+/// the array's default value; see [`Array::decode()`](crate::ast::Array::decode)
+/// to also get the default.
+impl<'ctx, K, V> FromModel<'ctx> for HashMap<K, V>
+where
+    K: FromModel<'ctx> + Eq + Hash,
+    V: FromModel<'ctx>,
+{
+    fn from_model(model: &Model<'ctx>, value: &Dynamic<'ctx>) -> Result<Self, FromModelError> {
+        if value.sort_kind() != SortKind::Array {
+            return Err(wrong_sort("Array", value));
+        }
+        let (default, entries) = decode_array(model, value).ok_or_else(|| not_a_literal(value))?;
+        entries
+            .iter()
+            .filter(|(_, v)| *v != default)
+            .map(|(k, v)| Ok((K::from_model(model, k)?, V::from_model(model, v)?)))
+            .collect()
+    }
+}
@@ -4,7 +4,7 @@ use std::fmt;
 
 use z3_sys::*;
 
-use crate::{ast, ast::Ast, Context, FuncDecl, Sort, Symbol};
+use crate::{ast, ast::Ast, Context, Error, FuncDecl, Sort, Symbol};
 
 impl<'ctx> FuncDecl<'ctx> {
     pub(crate) unsafe fn wrap(ctx: &'ctx Context, z3_func_decl: Z3_func_decl) -> Self {
@@ -56,23 +56,63 @@ impl<'ctx> FuncDecl<'ctx> {
         unsafe { Z3_get_arity(self.ctx.z3_ctx, self.z3_func_decl) as usize }
     }
 
+    /// Return the sorts of the arguments of this `FuncDecl`.
+    pub fn domain(&self) -> Vec<Sort<'ctx>> {
+        (0..self.arity())
+            .map(|i| unsafe {
+                Sort::wrap(
+                    self.ctx,
+                    Z3_get_domain(self.ctx.z3_ctx, self.z3_func_decl, i.try_into().unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the sort of the result of this `FuncDecl`.
+    pub fn range(&self) -> Sort<'ctx> {
+        unsafe { Sort::wrap(self.ctx, Z3_get_range(self.ctx.z3_ctx, self.z3_func_decl)) }
+    }
+
     /// Create a constant (if `args` has length 0) or function application (otherwise).
     ///
     /// Note that `args` should have the types corresponding to the `domain` of the `FuncDecl`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if Z3 rejects the arguments; see [`FuncDecl::try_apply()`].
     pub fn apply(&self, args: &[&dyn ast::Ast<'ctx>]) -> ast::Dynamic<'ctx> {
+        self.try_apply(args).unwrap()
+    }
+
+    /// Create a constant (if `args` has length 0) or function application (otherwise).
+    ///
+    /// Returns an [`Error`] if the number or sorts of `args` do not match the
+    /// `domain` of the `FuncDecl`.
+    ///
+    /// ```
+    /// # use z3::{ast, Config, Context, ErrorCode, FuncDecl, Sort};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let f = FuncDecl::new(&ctx, "f", &[&Sort::int(&ctx)], &Sort::int(&ctx));
+    /// assert!(f.try_apply(&[&ast::Int::from_i64(&ctx, 1)]).is_ok());
+    ///
+    /// let err = f.try_apply(&[&ast::Bool::from_bool(&ctx, true)]).unwrap_err();
+    /// assert_ne!(err.code(), ErrorCode::OK);
+    /// ```
+    pub fn try_apply(&self, args: &[&dyn ast::Ast<'ctx>]) -> Result<ast::Dynamic<'ctx>, Error> {
         assert!(args.iter().all(|s| s.get_ctx().z3_ctx == self.ctx.z3_ctx));
 
         let args: Vec<_> = args.iter().map(|a| a.get_z3_ast()).collect();
 
         unsafe {
-            ast::Dynamic::wrap(self.ctx, {
-                Z3_mk_app(
-                    self.ctx.z3_ctx,
-                    self.z3_func_decl,
-                    args.len().try_into().unwrap(),
-                    args.as_ptr(),
-                )
-            })
+            let app = Z3_mk_app(
+                self.ctx.z3_ctx,
+                self.z3_func_decl,
+                args.len().try_into().unwrap(),
+                args.as_ptr(),
+            );
+            self.ctx.check_error()?;
+            Ok(ast::Dynamic::wrap(self.ctx, app))
         }
     }
 
@@ -81,6 +121,45 @@ impl<'ctx> FuncDecl<'ctx> {
         unsafe { Z3_get_decl_kind(self.ctx.z3_ctx, self.z3_func_decl) }
     }
 
+    /// Return the number of parameters of this `FuncDecl`.
+    ///
+    /// Parameters are the indices of indexed operators, such as the `hi`
+    /// and `lo` bounds of a bit-vector `extract`.
+    pub fn num_parameters(&self) -> usize {
+        unsafe { Z3_get_decl_num_parameters(self.ctx.z3_ctx, self.z3_func_decl) as usize }
+    }
+
+    /// Return the `idx`th parameter of this `FuncDecl`, if it exists and
+    /// is an integer.
+    ///
+    /// ```
+    /// # use z3::{ast::{Ast, BV}, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let x = BV::new_const(&ctx, "x", 32);
+    /// let decl = x.extract(15, 8).decl();
+    /// assert_eq!(decl.num_parameters(), 2);
+    /// assert_eq!(decl.int_parameter(0), Some(15));
+    /// assert_eq!(decl.int_parameter(1), Some(8));
+    /// assert_eq!(decl.int_parameter(2), None);
+    /// ```
+    pub fn int_parameter(&self, idx: usize) -> Option<i32> {
+        if idx >= self.num_parameters() {
+            return None;
+        }
+        let idx = idx as u32;
+        unsafe {
+            match Z3_get_decl_parameter_kind(self.ctx.z3_ctx, self.z3_func_decl, idx) {
+                ParameterKind::Int => Some(Z3_get_decl_int_parameter(
+                    self.ctx.z3_ctx,
+                    self.z3_func_decl,
+                    idx,
+                )),
+                _ => None,
+            }
+        }
+    }
+
     /// Return the name of this `FuncDecl`.
     ///
     /// Strings will return the `Symbol`.  Ints will have a `"k!"` prepended to
@@ -118,6 +197,20 @@ impl fmt::Debug for FuncDecl<'_> {
     }
 }
 
+impl Clone for FuncDecl<'_> {
+    fn clone(&self) -> Self {
+        unsafe { Self::wrap(self.ctx, self.z3_func_decl) }
+    }
+}
+
+impl PartialEq for FuncDecl<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { Z3_is_eq_func_decl(self.ctx.z3_ctx, self.z3_func_decl, other.z3_func_decl) }
+    }
+}
+
+impl Eq for FuncDecl<'_> {}
+
 impl Drop for FuncDecl<'_> {
     fn drop(&mut self) {
         unsafe {
@@ -36,11 +36,12 @@ impl<'ctx> FuncInterp<'ctx> {
                 .for_each(|a| Z3_ast_vector_push(self.ctx.z3_ctx, v, a.z3_ast));
 
             Z3_func_interp_add_entry(self.ctx.z3_ctx, self.z3_func_interp, v, value.z3_ast);
+            Z3_ast_vector_dec_ref(self.ctx.z3_ctx, v);
         }
     }
 
     /// Returns the entries of the function interpretation.
-    pub fn get_entries(&self) -> Vec<FuncEntry> {
+    pub fn get_entries(&self) -> Vec<FuncEntry<'ctx>> {
         (0..self.get_num_entries())
             .map(|i| unsafe {
                 FuncEntry::wrap(
@@ -3,7 +3,7 @@ use std::fmt;
 
 use z3_sys::*;
 
-use crate::{ast, ast::Ast, Context, Goal};
+use crate::{ast, ast::Ast, AstVector, Context, Goal};
 
 impl Clone for Goal<'_> {
     fn clone(&self) -> Self {
@@ -27,6 +27,7 @@ impl<'ctx> Goal<'ctx> {
 
     /// Add a new formula `a` to the given goal.
     pub fn assert(&self, ast: &impl ast::Ast<'ctx>) {
+        assert_eq!(self.ctx, ast.get_ctx());
         unsafe { Z3_goal_assert(self.ctx.z3_ctx, self.z3_goal, ast.get_z3_ast()) }
     }
 
@@ -94,16 +95,14 @@ impl<'ctx> Goal<'ctx> {
     }
 
     /// Return a vector of the formulas from the given goal.
-    pub fn get_formulas<T>(&self) -> Vec<T>
+    pub fn get_formulas<T>(&self) -> AstVector<'ctx, T>
     where
         T: Ast<'ctx>,
     {
-        let goal_size = self.get_size() as usize;
-        let mut formulas: Vec<T> = Vec::with_capacity(goal_size);
-
-        for i in 0..goal_size {
-            let formula = unsafe { Z3_goal_formula(self.ctx.z3_ctx, self.z3_goal, i as u32) };
-            formulas.push(unsafe { T::wrap(self.ctx, formula) });
+        let mut formulas = AstVector::new(self.ctx);
+        for i in 0..self.get_size() {
+            let formula = unsafe { Z3_goal_formula(self.ctx.z3_ctx, self.z3_goal, i) };
+            formulas.push(&unsafe { T::wrap(self.ctx, formula) });
         }
         formulas
     }
@@ -6,34 +6,71 @@
 #![warn(clippy::doc_markdown)]
 #![deny(missing_debug_implementations)]
 
+use std::cell::{Cell, RefCell};
 use std::ffi::CString;
+use std::marker::PhantomData;
 use z3_sys::*;
-pub use z3_sys::{AstKind, GoalPrec, SortKind};
+pub use z3_sys::{AstKind, ErrorCode, GoalPrec, ParamKind, SortKind};
 
+mod array_value;
 pub mod ast;
+mod ast_map;
+mod ast_vector;
 mod config;
 mod context;
 pub mod datatype_builder;
+mod error;
+mod fixedpoint;
+mod float_value;
+mod from_model;
 mod func_decl;
 mod func_entry;
 mod func_interp;
 mod goal;
 mod model;
+mod mus;
 mod ops;
 mod optimize;
+mod owned_context;
 mod params;
+mod parser;
 mod pattern;
+mod portfolio;
 mod probe;
+#[cfg(feature = "user-propagator")]
+mod propagator;
 mod rec_func_decl;
+#[cfg(feature = "serde")]
+mod serialization;
 mod solver;
 mod sort;
 mod statistics;
 mod symbol;
 mod tactic;
+mod term;
+mod translate;
 mod version;
 
+pub use crate::array_value::ArrayValue;
+pub use crate::ast_vector::AstVectorIter;
+pub use crate::float_value::FloatValue;
+pub use crate::from_model::{FromModel, FromModelError};
+pub use crate::mus::{MarcoIter, MarcoSubset};
+pub use crate::optimize::{
+    ObjectiveBound, ObjectiveHandle, ObjectivePriority, ParetoFront, ParetoPoint,
+};
+pub use crate::owned_context::{OwnedContext, OwnedContextHandle};
 pub use crate::params::{get_global_param, reset_all_global_params, set_global_param};
+pub use crate::parser::{parse_smtlib2_file, parse_smtlib2_string};
+pub use crate::portfolio::{Portfolio, PortfolioOutcome};
+#[cfg(feature = "user-propagator")]
+pub use crate::propagator::UserPropagator;
+#[cfg(feature = "serde")]
+pub use crate::serialization::ModelSeed;
+pub use crate::solver::Solutions;
 pub use crate::statistics::{StatisticsEntry, StatisticsValue};
+pub use crate::term::Term;
+pub use crate::translate::Translate;
 pub use crate::version::{full_version, version, Version};
 
 /// Configuration used to initialize [logical contexts](Context).
@@ -107,6 +144,17 @@ pub struct SortDiffers<'ctx> {
     right: Sort<'ctx>,
 }
 
+/// An error reported by Z3 through [`Z3_get_error_code`] and [`Z3_get_error_msg`].
+///
+/// Returned by the fallible `try_*` variants of methods which would otherwise
+/// panic (or hand back an invalid object) when Z3 rejects their input, such as
+/// ill-sorted terms, unknown tactics or malformed SMT-LIB2 input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    code: ErrorCode,
+    message: String,
+}
+
 /// A struct to represent when an ast is not a function application.
 #[derive(Debug)]
 pub struct IsNotApp {
@@ -120,6 +168,21 @@ pub struct IsNotApp {
 pub struct Solver<'ctx> {
     ctx: &'ctx Context,
     z3_slv: Z3_solver,
+    // Keeps the user propagator, if any, alive as long as the solver.
+    #[cfg(feature = "user-propagator")]
+    propagator: Option<propagator::PropagatorBox>,
+}
+
+/// Handle passed to the callbacks of a [`UserPropagator`], used to register
+/// terms and to propagate consequences or conflicts.
+//
+// Note for in-crate users: Never construct a `PropagatorCallback` directly;
+// it is only valid for the duration of the callback it is passed to.
+#[cfg(feature = "user-propagator")]
+#[derive(Debug)]
+pub struct PropagatorCallback<'ctx> {
+    ctx: &'ctx Context,
+    z3_cb: Z3_solver_callback,
 }
 
 /// Model for the constraints inserted into the logical context.
@@ -138,6 +201,60 @@ pub struct Model<'ctx> {
 pub struct Optimize<'ctx> {
     ctx: &'ctx Context,
     z3_opt: Z3_optimize,
+    num_scopes: Cell<u32>,
+    priority: Cell<ObjectivePriority>,
+    // The maximized or minimized term of each objective, by handle index.
+    objectives: RefCell<Vec<Option<ast::Dynamic<'ctx>>>>,
+    // The number of objectives at each `push`.
+    objective_scopes: RefCell<Vec<usize>>,
+}
+
+/// Fixedpoint context for solving constrained Horn clauses (CHC),
+/// using e.g. the Spacer or Datalog engines.
+///
+/// Relations are declared as [`FuncDecl`]s returning `Bool`, registered
+/// with [`Fixedpoint::register_relation()`], and constrained with rules
+/// and facts. Queries are then posed against the rules.
+//
+// Note for in-crate users: Never construct a `Fixedpoint` directly; only use
+// `Fixedpoint::new()` which handles Z3 refcounting properly.
+pub struct Fixedpoint<'ctx> {
+    ctx: &'ctx Context,
+    z3_fp: Z3_fixedpoint,
+}
+
+/// A vector of [`Ast`](ast::Ast)s of type `T`, backed by a Z3 `ast_vector`.
+///
+/// AST vectors are returned by Z3 for assertions, unsat cores, parsed
+/// formulas and the like. Elements are wrapped as `T` when they are read.
+///
+/// # See also:
+///
+/// - [`AstMap`]
+//
+// Note for in-crate users: Never construct an `AstVector` directly; only use
+// `AstVector::new()` which handles Z3 refcounting properly.
+pub struct AstVector<'ctx, T> {
+    ctx: &'ctx Context,
+    z3_vec: Z3_ast_vector,
+    _elem: PhantomData<T>,
+}
+
+/// A map from [`Ast`](ast::Ast)s of type `K` to [`Ast`](ast::Ast)s of type
+/// `V`, backed by a Z3 `ast_map`.
+///
+/// Keys are compared structurally, using Z3's hash-consing of terms.
+///
+/// # See also:
+///
+/// - [`AstVector`]
+//
+// Note for in-crate users: Never construct an `AstMap` directly; only use
+// `AstMap::new()` which handles Z3 refcounting properly.
+pub struct AstMap<'ctx, K, V> {
+    ctx: &'ctx Context,
+    z3_map: Z3_ast_map,
+    _entry: PhantomData<(K, V)>,
 }
 
 /// Function declaration. Every constant and function have an associated declaration.
@@ -229,6 +346,7 @@ pub struct DatatypeBuilder<'ctx> {
     ctx: &'ctx Context,
     name: Symbol,
     constructors: Vec<(String, Vec<(String, DatatypeAccessor<'ctx>)>)>,
+    derived_from: Option<std::any::TypeId>,
 }
 
 /// Wrapper which can point to a sort (by value) or to a custom datatype (by name).
@@ -239,7 +357,7 @@ pub enum DatatypeAccessor<'ctx> {
 }
 
 /// Inner variant for a custom [datatype sort](DatatypeSort).
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct DatatypeVariant<'ctx> {
     pub constructor: FuncDecl<'ctx>,
     pub tester: FuncDecl<'ctx>,
@@ -247,7 +365,7 @@ pub struct DatatypeVariant<'ctx> {
 }
 
 /// A custom datatype sort.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct DatatypeSort<'ctx> {
     pub sort: Sort<'ctx>,
     pub variants: Vec<DatatypeVariant<'ctx>>,
@@ -259,6 +377,17 @@ pub struct Params<'ctx> {
     z3_params: Z3_params,
 }
 
+/// Descriptions of the parameters accepted by a component, such as an
+/// [`Optimize`].
+///
+/// # See also:
+///
+/// - [`Optimize::get_param_descrs()`]
+pub struct ParamDescrs<'ctx> {
+    ctx: &'ctx Context,
+    z3_param_descrs: Z3_param_descrs,
+}
+
 /// Result of a satisfiability query.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum SatResult {
@@ -3,14 +3,43 @@ use std::fmt;
 
 use z3_sys::*;
 
-use crate::{ast::Ast, Context, FuncDecl, FuncInterp, Model, Optimize, Solver};
+use crate::{
+    ast::{Ast, Dynamic},
+    AstVector, Context, FuncDecl, FuncInterp, Model, Optimize, Solver, Sort, SortDiffers,
+};
 
 impl<'ctx> Model<'ctx> {
-    unsafe fn wrap(ctx: &'ctx Context, z3_mdl: Z3_model) -> Model<'ctx> {
+    pub(crate) unsafe fn wrap(ctx: &'ctx Context, z3_mdl: Z3_model) -> Model<'ctx> {
         Z3_model_inc_ref(ctx.z3_ctx, z3_mdl);
         Model { ctx, z3_mdl }
     }
 
+    /// Create an empty model, to be filled with
+    /// [`Model::add_const_interp()`] and [`Model::add_func_interp()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int}, Config, Context, FuncDecl, Model, Sort};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let x = FuncDecl::new(&ctx, "x", &[], &Sort::int(&ctx));
+    /// let f = FuncDecl::new(&ctx, "f", &[&Sort::int(&ctx)], &Sort::int(&ctx));
+    /// let int = |i| Int::from_i64(&ctx, i);
+    ///
+    /// let mut model = Model::new(&ctx);
+    /// model.add_const_interp(&x, &int(2)).unwrap();
+    /// let f_interp = model.add_func_interp(&f, &int(0)).unwrap();
+    /// f_interp.add_entry(&[int(2).into()], &int(10).into());
+    /// assert!(model.has_interp(&x) && model.has_interp(&f));
+    ///
+    /// let x = x.apply(&[]).as_int().unwrap();
+    /// let fx = f.apply(&[&x]).as_int().unwrap();
+    /// assert_eq!(model.eval(&(fx + &x), true).unwrap().as_i64(), Some(12));
+    /// ```
+    pub fn new(ctx: &'ctx Context) -> Model<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_model(ctx.z3_ctx)) }
+    }
+
     pub fn of_solver(slv: &Solver<'ctx>) -> Option<Model<'ctx>> {
         unsafe {
             let m = Z3_solver_get_model(slv.ctx.z3_ctx, slv.z3_slv);
@@ -43,6 +72,120 @@ impl<'ctx> Model<'ctx> {
         }
     }
 
+    /// Return `true` if the model has an interpretation for the constant or
+    /// function `f`.
+    pub fn has_interp(&self, f: &FuncDecl<'ctx>) -> bool {
+        unsafe { Z3_model_has_interp(self.ctx.z3_ctx, self.z3_mdl, f.z3_func_decl) }
+    }
+
+    /// Interpret the constant `f` as `value` in the model, replacing any
+    /// previous interpretation.
+    ///
+    /// Returns [`SortDiffers`] if `value` is not of the sort of `f`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` is not a constant, i.e. if its arity is not `0`.
+    pub fn add_const_interp<T: Ast<'ctx>>(
+        &mut self,
+        f: &FuncDecl<'ctx>,
+        value: &T,
+    ) -> Result<(), SortDiffers<'ctx>> {
+        assert_eq!(f.arity(), 0, "{} is not a constant", f.name());
+        check_range(f, value)?;
+        unsafe {
+            Z3_add_const_interp(
+                self.ctx.z3_ctx,
+                self.z3_mdl,
+                f.z3_func_decl,
+                value.get_z3_ast(),
+            )
+        };
+        Ok(())
+    }
+
+    /// Add an interpretation of the function `f` to the model, mapping
+    /// every argument to `default_value`, and return it so that entries can
+    /// be added with [`FuncInterp::add_entry()`].
+    ///
+    /// Returns [`SortDiffers`] if `default_value` is not of the range sort
+    /// of `f`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` is a constant; use [`Model::add_const_interp()`]
+    /// instead.
+    pub fn add_func_interp<T: Ast<'ctx>>(
+        &mut self,
+        f: &FuncDecl<'ctx>,
+        default_value: &T,
+    ) -> Result<FuncInterp<'ctx>, SortDiffers<'ctx>> {
+        assert_ne!(f.arity(), 0, "{} is a constant", f.name());
+        check_range(f, default_value)?;
+        unsafe {
+            Ok(FuncInterp::wrap(
+                self.ctx,
+                Z3_add_func_interp(
+                    self.ctx.z3_ctx,
+                    self.z3_mdl,
+                    f.z3_func_decl,
+                    default_value.get_z3_ast(),
+                ),
+            ))
+        }
+    }
+
+    /// Return the uninterpreted sorts that the model assigns a finite
+    /// universe to.
+    ///
+    /// # See also:
+    ///
+    /// - [`Model::universe()`]
+    pub fn sorts(&self) -> Vec<Sort<'ctx>> {
+        let n = unsafe { Z3_model_get_num_sorts(self.ctx.z3_ctx, self.z3_mdl) };
+        (0..n)
+            .map(|i| unsafe {
+                Sort::wrap(self.ctx, Z3_model_get_sort(self.ctx.z3_ctx, self.z3_mdl, i))
+            })
+            .collect()
+    }
+
+    /// Return the finite set of elements the model uses to interpret the
+    /// uninterpreted sort `sort`, or `None` if `sort` is not one of
+    /// [`Model::sorts()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Dynamic}, Config, Context, SatResult, Solver, Sort, Symbol};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let node = Sort::uninterpreted(&ctx, Symbol::from("Node"));
+    /// let a = Dynamic::new_const(&ctx, "a", &node);
+    /// let b = Dynamic::new_const(&ctx, "b", &node);
+    /// let solver = Solver::new(&ctx);
+    /// solver.assert(&a._eq(&b).not());
+    /// assert_eq!(solver.check(), SatResult::Sat);
+    ///
+    /// let model = solver.get_model().unwrap();
+    /// assert_eq!(model.sorts(), vec![node.clone()]);
+    /// let universe = model.universe(&node).unwrap();
+    /// assert_eq!(universe.len(), 2);
+    /// assert!(universe.contains(&model.eval(&a, true).unwrap()));
+    /// assert_eq!(model.universe(&Sort::int(&ctx)), None);
+    /// ```
+    pub fn universe(&self, sort: &Sort<'ctx>) -> Option<Vec<Dynamic<'ctx>>> {
+        if !self.sorts().contains(sort) {
+            return None;
+        }
+        let universe: AstVector<Dynamic> = unsafe {
+            AstVector::wrap(
+                self.ctx,
+                Z3_model_get_sort_universe(self.ctx.z3_ctx, self.z3_mdl, sort.z3_sort),
+            )
+        };
+        Some(universe.to_vec())
+    }
+
     /// Returns the interpretation of the given `ast` in the `Model`
     /// Returns `None` if there is no interpretation in the `Model`
     pub fn get_const_interp<T: Ast<'ctx>>(&self, ast: &T) -> Option<T> {
@@ -135,8 +278,28 @@ impl<'ctx> Model<'ctx> {
     }
 }
 
+fn check_range<'ctx, T: Ast<'ctx>>(f: &FuncDecl<'ctx>, value: &T) -> Result<(), SortDiffers<'ctx>> {
+    let range = f.range();
+    let sort = value.get_sort();
+    if range == sort {
+        Ok(())
+    } else {
+        Err(SortDiffers::new(range, sort))
+    }
+}
+
+/// Lists the universes of [`Model::sorts()`] in SMT-LIB2 comments, as Z3
+/// does for `(get-model)`, followed by the interpretations.
 impl fmt::Display for Model<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for sort in self.sorts() {
+            writeln!(f, ";; universe for {sort}:")?;
+            write!(f, ";;  ")?;
+            for element in self.universe(&sort).unwrap_or_default() {
+                write!(f, " {element}")?;
+            }
+            writeln!(f)?;
+        }
         let p = unsafe { Z3_model_to_string(self.ctx.z3_ctx, self.z3_mdl) };
         if p.is_null() {
             return Result::Err(fmt::Error);
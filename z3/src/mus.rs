@@ -0,0 +1,263 @@
+use crate::{ast, SatResult, Solver};
+
+impl<'ctx> Solver<'ctx> {
+    /// Shrink an unsatisfiable set of assumptions to a minimal
+    /// unsatisfiable subset (MUS).
+    ///
+    /// Starting from the unsat core of `assumptions`, each assumption is
+    /// dropped in turn and kept out if the rest is still unsatisfiable
+    /// together with the solver's assertions. Removing any single element
+    /// of the returned subset makes it satisfiable.
+    ///
+    /// Returns `None` if `assumptions` are not unsatisfiable, i.e. if
+    /// [`Solver::check_assumptions()`] returns [`SatResult::Sat`] or
+    /// [`SatResult::Unknown`]. If a check on a smaller candidate returns
+    /// [`SatResult::Unknown`], the assumption under test is conservatively
+    /// kept, so the result is still unsatisfiable but may not be minimal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Bool, Int}, Config, Context, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let x = Int::new_const(&ctx, "x");
+    /// let a = x.gt(&Int::from_i64(&ctx, 10));
+    /// let b = x.lt(&Int::from_i64(&ctx, 5));
+    /// let c = x.lt(&Int::from_i64(&ctx, 20));
+    ///
+    /// let mus = solver.minimize_core(&[a.clone(), c, b.clone()]).unwrap();
+    /// assert_eq!(mus, vec![a, b]);
+    /// ```
+    ///
+    /// # See also:
+    ///
+    /// - [`Solver::get_unsat_core()`]
+    /// - [`Solver::iter_mus_mcs()`]
+    pub fn minimize_core(&self, assumptions: &[ast::Bool<'ctx>]) -> Option<Vec<ast::Bool<'ctx>>> {
+        if self.check_assumptions(assumptions) != SatResult::Unsat {
+            return None;
+        }
+        let mut core = self.core_of(assumptions);
+        let mut i = 0;
+        while i < core.len() {
+            let candidate: Vec<ast::Bool<'ctx>> = core
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, a)| a.clone())
+                .collect();
+            if self.check_assumptions(&candidate) == SatResult::Unsat {
+                // The new core may drop more than the element under test.
+                core = self.core_of(&candidate);
+            } else {
+                i += 1;
+            }
+        }
+        Some(core)
+    }
+
+    /// Enumerate all minimal unsatisfiable subsets (MUSes) and minimal
+    /// correction subsets (MCSes) of `literals`, relative to the solver's
+    /// assertions, using the MARCO algorithm.
+    ///
+    /// An MCS is a minimal subset of `literals` whose removal makes the
+    /// rest satisfiable; every MCS is a minimal hitting set of the MUSes
+    /// and vice versa. The solver itself is only queried through
+    /// [`Solver::check_assumptions()`] and is left unchanged.
+    ///
+    /// The iteration stops once every subset has been classified, or as
+    /// soon as a check returns [`SatResult::Unknown`]; see
+    /// [`MarcoIter::result()`] to tell both cases apart.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Bool}, Config, Context, MarcoSubset, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let p = Bool::new_const(&ctx, "p");
+    /// let q = Bool::new_const(&ctx, "q");
+    /// let literals = [p.clone(), p.not(), q.clone(), q.not()];
+    ///
+    /// let muses = solver
+    ///     .iter_mus_mcs(&literals)
+    ///     .filter(|s| matches!(s, MarcoSubset::Mus(_)))
+    ///     .count();
+    /// assert_eq!(muses, 2);
+    /// ```
+    pub fn iter_mus_mcs<'a>(&'a self, literals: &[ast::Bool<'ctx>]) -> MarcoIter<'a, 'ctx> {
+        let map = Solver::new(self.ctx);
+        let selectors = literals
+            .iter()
+            .map(|_| ast::Bool::fresh_const(self.ctx, "marco"))
+            .collect();
+        MarcoIter {
+            solver: self,
+            literals: literals.to_vec(),
+            map,
+            selectors,
+            result: None,
+            reason_unknown: None,
+        }
+    }
+
+    /// Return the unsat core of the last check, which must have been made
+    /// with `assumptions`, in the order of `assumptions`.
+    fn core_of(&self, assumptions: &[ast::Bool<'ctx>]) -> Vec<ast::Bool<'ctx>> {
+        let core = self.get_unsat_core().to_vec();
+        assumptions
+            .iter()
+            .filter(|a| core.contains(a))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A subset of literals classified by [`Solver::iter_mus_mcs()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarcoSubset<'ctx> {
+    /// A minimal unsatisfiable subset.
+    Mus(Vec<ast::Bool<'ctx>>),
+    /// A minimal correction subset.
+    Mcs(Vec<ast::Bool<'ctx>>),
+}
+
+/// Iterator over the MUSes and MCSes of a set of literals.
+///
+/// # See also:
+///
+/// - [`Solver::iter_mus_mcs()`]
+#[derive(Debug)]
+pub struct MarcoIter<'a, 'ctx> {
+    solver: &'a Solver<'ctx>,
+    literals: Vec<ast::Bool<'ctx>>,
+    /// Tracks the subsets that are still unexplored: selector `i` is true
+    /// if literal `i` is in the subset.
+    map: Solver<'ctx>,
+    selectors: Vec<ast::Bool<'ctx>>,
+    result: Option<SatResult>,
+    reason_unknown: Option<String>,
+}
+
+impl<'a, 'ctx> MarcoIter<'a, 'ctx> {
+    /// Return [`SatResult::Unsat`] if all subsets were classified, or
+    /// [`SatResult::Unknown`] if the solver gave up, or `None` if the
+    /// iteration has not ended yet.
+    pub fn result(&self) -> Option<SatResult> {
+        self.result
+    }
+
+    /// Return the solver's justification if the iteration ended with
+    /// [`SatResult::Unknown`].
+    pub fn reason_unknown(&self) -> Option<&str> {
+        self.reason_unknown.as_deref()
+    }
+
+    fn finish(&mut self, result: SatResult, reason_unknown: Option<String>) {
+        self.result = Some(result);
+        self.reason_unknown = reason_unknown;
+    }
+
+    fn literals_of(&self, seed: &[usize]) -> Vec<ast::Bool<'ctx>> {
+        seed.iter().map(|&i| self.literals[i].clone()).collect()
+    }
+
+    /// Grow a satisfiable seed into a maximal satisfiable subset.
+    fn grow(&self, seed: &mut Vec<usize>) -> Option<()> {
+        for i in 0..self.literals.len() {
+            if seed.contains(&i) {
+                continue;
+            }
+            seed.push(i);
+            match self.solver.check_assumptions(&self.literals_of(seed)) {
+                SatResult::Sat => {}
+                SatResult::Unsat => {
+                    seed.pop();
+                }
+                SatResult::Unknown => return None,
+            }
+        }
+        Some(())
+    }
+}
+
+impl<'a, 'ctx> Iterator for MarcoIter<'a, 'ctx> {
+    type Item = MarcoSubset<'ctx>;
+
+    fn next(&mut self) -> Option<MarcoSubset<'ctx>> {
+        if self.result.is_some() {
+            return None;
+        }
+        match self.map.check() {
+            SatResult::Sat => {}
+            SatResult::Unsat => {
+                self.finish(SatResult::Unsat, None);
+                return None;
+            }
+            SatResult::Unknown => {
+                let reason = self.map.get_reason_unknown();
+                self.finish(SatResult::Unknown, reason);
+                return None;
+            }
+        }
+        let model = match self.map.get_model() {
+            Some(model) => model,
+            None => {
+                self.finish(SatResult::Unknown, None);
+                return None;
+            }
+        };
+        // Selectors left unassigned by the map count as included.
+        let mut seed: Vec<usize> = (0..self.literals.len())
+            .filter(|&i| {
+                model
+                    .eval(&self.selectors[i], false)
+                    .and_then(|b| b.as_bool())
+                    != Some(false)
+            })
+            .collect();
+
+        let ctx = self.solver.ctx;
+        match self.solver.check_assumptions(&self.literals_of(&seed)) {
+            SatResult::Sat => {
+                if self.grow(&mut seed).is_none() {
+                    let reason = self.solver.get_reason_unknown();
+                    self.finish(SatResult::Unknown, reason);
+                    return None;
+                }
+                let mcs: Vec<usize> = (0..self.literals.len())
+                    .filter(|i| !seed.contains(i))
+                    .collect();
+                // Block every subset of the maximal satisfiable subset.
+                let block: Vec<&ast::Bool<'ctx>> =
+                    mcs.iter().map(|&i| &self.selectors[i]).collect();
+                self.map.assert(&ast::Bool::or(ctx, &block));
+                Some(MarcoSubset::Mcs(self.literals_of(&mcs)))
+            }
+            SatResult::Unsat => {
+                let mus = match self.solver.minimize_core(&self.literals_of(&seed)) {
+                    Some(mus) => mus,
+                    None => {
+                        let reason = self.solver.get_reason_unknown();
+                        self.finish(SatResult::Unknown, reason);
+                        return None;
+                    }
+                };
+                // Block every superset of the MUS.
+                let block: Vec<ast::Bool<'ctx>> = (0..self.literals.len())
+                    .filter(|&i| mus.contains(&self.literals[i]))
+                    .map(|i| self.selectors[i].not())
+                    .collect();
+                let block: Vec<&ast::Bool<'ctx>> = block.iter().collect();
+                self.map.assert(&ast::Bool::or(ctx, &block));
+                Some(MarcoSubset::Mus(mus))
+            }
+            SatResult::Unknown => {
+                let reason = self.solver.get_reason_unknown();
+                self.finish(SatResult::Unknown, reason);
+                None
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use log::debug;
+use std::cell::{Cell, RefCell};
 use std::convert::TryInto;
 use std::ffi::{CStr, CString};
 use std::fmt;
@@ -7,7 +8,7 @@ use z3_sys::*;
 
 use crate::{
     ast::{Ast, Bool, Dynamic},
-    Context, Model, Optimize, Params, SatResult, Statistics, Symbol,
+    AstVector, Context, Error, Model, Optimize, ParamDescrs, Params, SatResult, Statistics, Symbol,
 };
 
 use num::{
@@ -18,7 +19,14 @@ use num::{
 impl<'ctx> Optimize<'ctx> {
     unsafe fn wrap(ctx: &'ctx Context, z3_opt: Z3_optimize) -> Optimize<'ctx> {
         Z3_optimize_inc_ref(ctx.z3_ctx, z3_opt);
-        Optimize { ctx, z3_opt }
+        Optimize {
+            ctx,
+            z3_opt,
+            num_scopes: Cell::new(0),
+            priority: Cell::new(ObjectivePriority::default()),
+            objectives: RefCell::new(Vec::new()),
+            objective_scopes: RefCell::new(Vec::new()),
+        }
     }
 
     /// Create a new optimize context.
@@ -28,6 +36,9 @@ impl<'ctx> Optimize<'ctx> {
 
     /// Parse an SMT-LIB2 string with assertions, soft constraints and optimization objectives.
     /// Add the parsed constraints and objectives to the optimizer.
+    ///
+    /// Parse errors are silently ignored; use [`Optimize::try_from_string()`]
+    /// to have them reported.
     pub fn from_string<T: Into<Vec<u8>>>(&self, source_string: T) {
         let source_cstring = CString::new(source_string).unwrap();
         unsafe {
@@ -35,6 +46,45 @@ impl<'ctx> Optimize<'ctx> {
         }
     }
 
+    /// Parse an SMT-LIB2 string with assertions, soft constraints and optimization objectives.
+    /// Add the parsed constraints and objectives to the optimizer.
+    ///
+    /// Returns an [`Error`] if the input contains a NUL byte or Z3 fails to
+    /// parse it. Constraints and objectives parsed before the error was
+    /// encountered may still have been added.
+    ///
+    /// ```
+    /// # use z3::{Config, Context, Optimize};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let optimize = Optimize::new(&ctx);
+    /// assert!(optimize.try_from_string("(declare-const x Int) (maximize x)").is_ok());
+    /// assert!(optimize.try_from_string("(minimize y)").is_err());
+    /// ```
+    pub fn try_from_string<T: Into<Vec<u8>>>(&self, source_string: T) -> Result<(), Error> {
+        let source_cstring = CString::new(source_string).map_err(Error::from_nul)?;
+        unsafe {
+            Z3_optimize_from_string(self.ctx.z3_ctx, self.z3_opt, source_cstring.as_ptr());
+        }
+        self.ctx.check_error()
+    }
+
+    /// Parse an SMT-LIB2 file with assertions, soft constraints and
+    /// optimization objectives, and add them to the optimizer.
+    ///
+    /// Returns an [`Error`] if the file name contains a NUL byte or Z3 fails
+    /// to read or parse the file. The optimizer's
+    /// [`Display`](fmt::Display) output can be read back this way.
+    ///
+    /// # See also:
+    ///
+    /// - [`Optimize::try_from_string()`]
+    pub fn from_file<T: Into<Vec<u8>>>(&self, file_name: T) -> Result<(), Error> {
+        let file_cstring = CString::new(file_name).map_err(Error::from_nul)?;
+        unsafe { Z3_optimize_from_file(self.ctx.z3_ctx, self.z3_opt, file_cstring.as_ptr()) };
+        self.ctx.check_error()
+    }
+
     /// Get this optimizers 's context.
     pub fn get_context(&self) -> &'ctx Context {
         self.ctx
@@ -75,18 +125,26 @@ impl<'ctx> Optimize<'ctx> {
     /// Weight is a positive, rational penalty for violating the constraint.
     /// Group is an optional identifier to group soft constraints.
     ///
+    /// Returns the handle of the objective of `group`, which is shared by
+    /// all the soft constraints in the group.
+    ///
     /// # See also:
     ///
     /// - [`Optimize::assert()`]
     /// - [`Optimize::maximize()`]
     /// - [`Optimize::minimize()`]
-    pub fn assert_soft(&self, ast: &impl Ast<'ctx>, weight: impl Weight, group: Option<Symbol>) {
+    pub fn assert_soft(
+        &self,
+        ast: &impl Ast<'ctx>,
+        weight: impl Weight,
+        group: Option<Symbol>,
+    ) -> ObjectiveHandle {
         let weight_string = weight.to_string();
         let weight_cstring = CString::new(weight_string).unwrap();
         let group = group
             .map(|g| g.as_z3_symbol(self.ctx))
             .unwrap_or_else(std::ptr::null_mut);
-        unsafe {
+        let index = unsafe {
             Z3_optimize_assert_soft(
                 self.ctx.z3_ctx,
                 self.z3_opt,
@@ -95,35 +153,44 @@ impl<'ctx> Optimize<'ctx> {
                 group,
             )
         };
+        self.add_objective(index, None)
     }
 
-    /// Add a maximization constraint.
+    /// Add a maximization constraint, and return the handle of its
+    /// objective.
     ///
     /// # See also:
     ///
     /// - [`Optimize::assert()`]
     /// - [`Optimize::minimize()`]
-    pub fn maximize(&self, ast: &impl Ast<'ctx>) {
+    /// - [`Optimize::lower()`]
+    /// - [`Optimize::upper()`]
+    pub fn maximize(&self, ast: &impl Ast<'ctx>) -> ObjectiveHandle {
         // https://github.com/Z3Prover/z3/blob/09f911d8a84cd91988e5b96b69485b2a9a2edba3/src/opt/opt_context.cpp#L118-L120
         assert!(matches!(
             ast.get_sort().kind(),
             SortKind::Int | SortKind::Real | SortKind::BV
         ));
-        unsafe { Z3_optimize_maximize(self.ctx.z3_ctx, self.z3_opt, ast.get_z3_ast()) };
+        let index = unsafe { Z3_optimize_maximize(self.ctx.z3_ctx, self.z3_opt, ast.get_z3_ast()) };
+        self.add_objective(index, Some(Dynamic::from_ast(ast)))
     }
 
-    /// Add a minimization constraint.
+    /// Add a minimization constraint, and return the handle of its
+    /// objective.
     ///
     /// # See also:
     ///
     /// - [`Optimize::assert()`]
     /// - [`Optimize::maximize()`]
-    pub fn minimize(&self, ast: &impl Ast<'ctx>) {
+    /// - [`Optimize::lower()`]
+    /// - [`Optimize::upper()`]
+    pub fn minimize(&self, ast: &impl Ast<'ctx>) -> ObjectiveHandle {
         assert!(matches!(
             ast.get_sort().kind(),
             SortKind::Int | SortKind::Real | SortKind::BV
         ));
-        unsafe { Z3_optimize_minimize(self.ctx.z3_ctx, self.z3_opt, ast.get_z3_ast()) };
+        let index = unsafe { Z3_optimize_minimize(self.ctx.z3_ctx, self.z3_opt, ast.get_z3_ast()) };
+        self.add_objective(index, Some(Dynamic::from_ast(ast)))
     }
 
     /// Return a subset of the assumptions provided to either the last
@@ -146,23 +213,13 @@ impl<'ctx> Optimize<'ctx> {
     /// # See also:
     ///
     /// - [`Optimize::check`]
-    pub fn get_unsat_core(&self) -> Vec<Bool<'ctx>> {
+    pub fn get_unsat_core(&self) -> AstVector<'ctx, Bool<'ctx>> {
         let z3_unsat_core = unsafe { Z3_optimize_get_unsat_core(self.ctx.z3_ctx, self.z3_opt) };
         if z3_unsat_core.is_null() {
-            return vec![];
+            return AstVector::new(self.ctx);
         }
 
-        let len = unsafe { Z3_ast_vector_size(self.ctx.z3_ctx, z3_unsat_core) };
-
-        let mut unsat_core = Vec::with_capacity(len as usize);
-
-        for i in 0..len {
-            let elem = unsafe { Z3_ast_vector_get(self.ctx.z3_ctx, z3_unsat_core, i) };
-            let elem = unsafe { Bool::wrap(self.ctx, elem) };
-            unsat_core.push(elem);
-        }
-
-        unsat_core
+        unsafe { AstVector::wrap(self.ctx, z3_unsat_core) }
     }
 
     /// Create a backtracking point.
@@ -176,20 +233,44 @@ impl<'ctx> Optimize<'ctx> {
     /// - [`Optimize::pop()`]
     pub fn push(&self) {
         unsafe { Z3_optimize_push(self.ctx.z3_ctx, self.z3_opt) };
+        self.num_scopes.set(self.num_scopes.get() + 1);
+        let num_objectives = self.objectives.borrow().len();
+        self.objective_scopes.borrow_mut().push(num_objectives);
     }
 
-    /// Backtrack one level.
+    /// Backtrack `n` levels.
     ///
-    /// # Preconditions:
+    /// # Panics
     ///
-    /// - The number of calls to [`Optimize::pop`] cannot exceed the number of calls to
-    ///   [`Optimize::push()`].
+    /// Panics if `n` is greater than [`Optimize::get_num_scopes()`].
     ///
     /// # See also:
     ///
     /// - [`Optimize::push()`]
-    pub fn pop(&self) {
-        unsafe { Z3_optimize_pop(self.ctx.z3_ctx, self.z3_opt) };
+    pub fn pop(&self, n: u32) {
+        let num_scopes = self.get_num_scopes();
+        assert!(
+            n <= num_scopes,
+            "cannot pop {} scopes, only {} were pushed",
+            n,
+            num_scopes
+        );
+        for _ in 0..n {
+            unsafe { Z3_optimize_pop(self.ctx.z3_ctx, self.z3_opt) };
+        }
+        self.num_scopes.set(num_scopes - n);
+        if n > 0 {
+            let mut scopes = self.objective_scopes.borrow_mut();
+            let depth = scopes.len() - n as usize;
+            self.objectives.borrow_mut().truncate(scopes[depth]);
+            scopes.truncate(depth);
+        }
+    }
+
+    /// Return the number of backtracking points, i.e. the number of calls
+    /// to [`Optimize::push()`] not undone by [`Optimize::pop()`].
+    pub fn get_num_scopes(&self) -> u32 {
+        self.num_scopes.get()
     }
 
     /// Check consistency and produce optimal values.
@@ -199,19 +280,14 @@ impl<'ctx> Optimize<'ctx> {
     /// - [`Optimize::get_model()`]
     pub fn check(&self, assumptions: &[Bool<'ctx>]) -> SatResult {
         let assumptions: Vec<Z3_ast> = assumptions.iter().map(|a| a.z3_ast).collect();
-        match unsafe {
+        SatResult::from_lbool(unsafe {
             Z3_optimize_check(
                 self.ctx.z3_ctx,
                 self.z3_opt,
                 assumptions.len().try_into().unwrap(),
                 assumptions.as_ptr(),
             )
-        } {
-            Z3_L_FALSE => SatResult::Unsat,
-            Z3_L_UNDEF => SatResult::Unknown,
-            Z3_L_TRUE => SatResult::Sat,
-            _ => unreachable!(),
-        }
+        })
     }
 
     /// Retrieve the model for the last [`Optimize::check()`].
@@ -223,25 +299,178 @@ impl<'ctx> Optimize<'ctx> {
         Model::of_optimize(self)
     }
 
+    /// Return the hard constraints asserted in the optimizer, including
+    /// those added by [`Optimize::from_string()`] and still in scope.
+    ///
+    /// Soft constraints and objectives are not included; see
+    /// [`Optimize::get_objectives()`].
+    pub fn get_assertions(&self) -> AstVector<'ctx, Bool<'ctx>> {
+        unsafe {
+            AstVector::wrap(
+                self.ctx,
+                Z3_optimize_get_assertions(self.ctx.z3_ctx, self.z3_opt),
+            )
+        }
+    }
+
     /// Retrieve the objectives for the last [`Optimize::check()`].
     ///
     /// This contains maximize/minimize objectives and grouped soft constraints.
     pub fn get_objectives(&self) -> Vec<Dynamic<'ctx>> {
-        let (z3_objectives, len) = unsafe {
-            let objectives = Z3_optimize_get_objectives(self.ctx.z3_ctx, self.z3_opt);
-            let len = Z3_ast_vector_size(self.ctx.z3_ctx, objectives);
-            (objectives, len)
-        };
+        unsafe {
+            AstVector::wrap(
+                self.ctx,
+                Z3_optimize_get_objectives(self.ctx.z3_ctx, self.z3_opt),
+            )
+        }
+        .into()
+    }
+
+    /// Retrieve the lower bound found for `objective` by the last
+    /// [`Optimize::check()`].
+    ///
+    /// For a minimization this is the optimum once the check returns
+    /// [`SatResult::Sat`]; if the search was interrupted, the gap between
+    /// the bounds is how far it was from proving optimality. Unbounded and
+    /// strict bounds are expressed with `oo` and `epsilon` terms; see
+    /// [`Optimize::lower_as_vector()`] to get them as numerals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `objective` belongs to another optimizer, or is no longer
+    /// an objective of this one because it was removed by
+    /// [`Optimize::pop()`]. A handle of a removed objective may instead
+    /// refer to an objective added after the `pop`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int}, Config, Context, Optimize, SatResult};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let opt = Optimize::new(&ctx);
+    /// let x = Int::new_const(&ctx, "x");
+    /// opt.assert(&x.le(&Int::from_i64(&ctx, 10)));
+    /// let objective = opt.maximize(&x);
+    /// assert_eq!(opt.check(&[]), SatResult::Sat);
+    ///
+    /// assert_eq!(opt.lower(objective).as_int().unwrap().as_i64(), Some(10));
+    /// assert_eq!(opt.upper(objective).as_int().unwrap().as_i64(), Some(10));
+    /// ```
+    pub fn lower(&self, objective: ObjectiveHandle) -> Dynamic<'ctx> {
+        self.bound(objective, Z3_optimize_get_lower)
+    }
+
+    /// Retrieve the upper bound found for `objective` by the last
+    /// [`Optimize::check()`].
+    ///
+    /// For a maximization this is the optimum once the check returns
+    /// [`SatResult::Sat`]. See [`Optimize::lower()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `objective` is not an objective of this optimizer.
+    pub fn upper(&self, objective: ObjectiveHandle) -> Dynamic<'ctx> {
+        self.bound(objective, Z3_optimize_get_upper)
+    }
+
+    /// Retrieve the lower bound of `objective` as the coefficients of its
+    /// infinite, finite and infinitesimal parts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `objective` is not an objective of this optimizer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int}, Config, Context, Optimize, SatResult};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let opt = Optimize::new(&ctx);
+    /// let x = Int::new_const(&ctx, "x");
+    /// opt.assert(&x.le(&Int::from_i64(&ctx, 0)));
+    /// let objective = opt.minimize(&x);
+    /// assert_eq!(opt.check(&[]), SatResult::Sat);
+    ///
+    /// // x is unbounded below, so its lower bound is -1 * oo + 0.
+    /// let lower = opt.lower_as_vector(objective);
+    /// assert_eq!(lower.infinite.as_int().unwrap().as_i64(), Some(-1));
+    /// assert_eq!(lower.finite.as_int().unwrap().as_i64(), Some(0));
+    /// assert_eq!(lower.infinitesimal.as_int().unwrap().as_i64(), Some(0));
+    /// ```
+    pub fn lower_as_vector(&self, objective: ObjectiveHandle) -> ObjectiveBound<'ctx> {
+        self.bound_as_vector(objective, Z3_optimize_get_lower_as_vector)
+    }
 
-        let mut objectives = Vec::with_capacity(len as usize);
+    /// Retrieve the upper bound of `objective` as the coefficients of its
+    /// infinite, finite and infinitesimal parts. See
+    /// [`Optimize::lower_as_vector()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `objective` is not an objective of this optimizer.
+    pub fn upper_as_vector(&self, objective: ObjectiveHandle) -> ObjectiveBound<'ctx> {
+        self.bound_as_vector(objective, Z3_optimize_get_upper_as_vector)
+    }
 
-        for i in 0..len {
-            let elem = unsafe { Z3_ast_vector_get(self.ctx.z3_ctx, z3_objectives, i) };
-            let elem = unsafe { Dynamic::wrap(self.ctx, elem) };
-            objectives.push(elem);
+    /// Record the term of a maximization or minimization, to evaluate it
+    /// at the points of a [`ParetoFront`].
+    fn add_objective(&self, index: u32, term: Option<Dynamic<'ctx>>) -> ObjectiveHandle {
+        let mut objectives = self.objectives.borrow_mut();
+        if objectives.len() <= index as usize {
+            objectives.resize(index as usize + 1, None);
+        }
+        if term.is_some() {
+            objectives[index as usize] = term;
         }
+        self.handle(index)
+    }
 
-        objectives
+    fn handle(&self, index: u32) -> ObjectiveHandle {
+        ObjectiveHandle {
+            opt: self.z3_opt as usize,
+            index,
+        }
+    }
+
+    fn objective_index(&self, objective: ObjectiveHandle) -> u32 {
+        assert_eq!(
+            objective.opt, self.z3_opt as usize,
+            "not an objective of this optimizer"
+        );
+        objective.index
+    }
+
+    fn bound(
+        &self,
+        objective: ObjectiveHandle,
+        get: unsafe extern "C" fn(Z3_context, Z3_optimize, u32) -> Z3_ast,
+    ) -> Dynamic<'ctx> {
+        let index = self.objective_index(objective);
+        let bound = unsafe { get(self.ctx.z3_ctx, self.z3_opt, index) };
+        self.ctx
+            .check_error()
+            .expect("not an objective of this optimizer");
+        unsafe { Dynamic::wrap(self.ctx, bound) }
+    }
+
+    fn bound_as_vector(
+        &self,
+        objective: ObjectiveHandle,
+        get: unsafe extern "C" fn(Z3_context, Z3_optimize, u32) -> Z3_ast_vector,
+    ) -> ObjectiveBound<'ctx> {
+        let index = self.objective_index(objective);
+        let bound = unsafe { get(self.ctx.z3_ctx, self.z3_opt, index) };
+        self.ctx
+            .check_error()
+            .expect("not an objective of this optimizer");
+        let bound: AstVector<Dynamic> = unsafe { AstVector::wrap(self.ctx, bound) };
+        let mut coefficients = bound.iter();
+        let mut next = || coefficients.next().unwrap();
+        ObjectiveBound {
+            infinite: next(),
+            finite: next(),
+            infinitesimal: next(),
+        }
     }
 
     /// Retrieve a string that describes the last status returned by [`Optimize::check()`].
@@ -263,6 +492,85 @@ impl<'ctx> Optimize<'ctx> {
         unsafe { Z3_optimize_set_params(self.ctx.z3_ctx, self.z3_opt, params.z3_params) };
     }
 
+    /// Return a string describing all available parameters.
+    pub fn get_help(&self) -> String {
+        let p = unsafe { Z3_optimize_get_help(self.ctx.z3_ctx, self.z3_opt) };
+        if p.is_null() {
+            return String::new();
+        }
+        unsafe { CStr::from_ptr(p) }.to_string_lossy().into_owned()
+    }
+
+    /// Return the descriptions of the parameters accepted by
+    /// [`Optimize::set_params()`].
+    pub fn get_param_descrs(&self) -> ParamDescrs<'ctx> {
+        unsafe {
+            ParamDescrs::wrap(
+                self.ctx,
+                Z3_optimize_get_param_descrs(self.ctx.z3_ctx, self.z3_opt),
+            )
+        }
+    }
+
+    /// Set how multiple objectives are combined.
+    ///
+    /// This sets the `priority` parameter of the optimizer; see
+    /// [`ObjectivePriority`].
+    pub fn set_priority(&self, priority: ObjectivePriority) {
+        let mut params = Params::new(self.ctx);
+        params.set_symbol("priority", priority.as_str());
+        self.set_params(&params);
+        self.priority.set(priority);
+    }
+
+    /// Iterate over the Pareto front of the objectives, calling
+    /// [`Optimize::check()`] for each Pareto-optimal point.
+    ///
+    /// This sets the priority to [`ObjectivePriority::Pareto`] until the
+    /// returned [`ParetoFront`] is dropped, which restores the priority
+    /// last set with [`Optimize::set_priority()`]. The iteration ends when
+    /// Z3 returns anything other than [`SatResult::Sat`]; see
+    /// [`ParetoFront::result()`] to tell whether the whole front was
+    /// enumerated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int}, Config, Context, Optimize, SatResult};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let opt = Optimize::new(&ctx);
+    /// let x = Int::new_const(&ctx, "x");
+    /// let y = Int::new_const(&ctx, "y");
+    /// let int = |i| Int::from_i64(&ctx, i);
+    /// opt.assert(&x.ge(&int(0)));
+    /// opt.assert(&y.ge(&int(0)));
+    /// opt.assert(&(&x + &y).le(&int(2)));
+    /// let max_x = opt.maximize(&x);
+    /// let max_y = opt.maximize(&y);
+    ///
+    /// let mut front = opt.pareto_iter();
+    /// let mut points: Vec<_> = front
+    ///     .by_ref()
+    ///     .map(|point| {
+    ///         let value = |h| point.value(h).as_int().unwrap().as_i64().unwrap();
+    ///         (value(max_x), value(max_y))
+    ///     })
+    ///     .collect();
+    /// points.sort();
+    /// assert_eq!(points, vec![(0, 2), (1, 1), (2, 0)]);
+    /// assert_eq!(front.result(), Some(SatResult::Unsat));
+    /// ```
+    pub fn pareto_iter<'a>(&'a self) -> ParetoFront<'a, 'ctx> {
+        let previous_priority = self.priority.get();
+        self.set_priority(ObjectivePriority::Pareto);
+        ParetoFront {
+            opt: self,
+            previous_priority,
+            result: None,
+            reason_unknown: None,
+        }
+    }
+
     /// Retrieve the statistics for the last [`Optimize::check()`].
     pub fn get_statistics(&self) -> Statistics<'ctx> {
         unsafe {
@@ -299,6 +607,199 @@ impl Drop for Optimize<'_> {
     }
 }
 
+/// Identifies an objective of an [`Optimize`]: a maximization, a
+/// minimization, or a group of soft constraints.
+///
+/// The handle holds the position of the objective in
+/// [`Optimize::get_objectives()`], and can only be used with the optimizer
+/// that created it.
+///
+/// # See also:
+///
+/// - [`Optimize::lower()`]
+/// - [`Optimize::upper()`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectiveHandle {
+    // The address of the optimizer, only compared, so the handle stays
+    // `Send`.
+    opt: usize,
+    index: u32,
+}
+
+impl ObjectiveHandle {
+    /// Return the position of the objective in
+    /// [`Optimize::get_objectives()`].
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+}
+
+/// A bound on an objective, as returned by
+/// [`Optimize::lower_as_vector()`] and [`Optimize::upper_as_vector()`]:
+/// the numerals `a`, `b` and `c` of `a * oo + b + c * epsilon`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectiveBound<'ctx> {
+    /// The coefficient of infinity; non-zero if the objective is unbounded.
+    pub infinite: Dynamic<'ctx>,
+    /// The finite part of the bound.
+    pub finite: Dynamic<'ctx>,
+    /// The coefficient of an infinitesimal; non-zero for strict bounds.
+    pub infinitesimal: Dynamic<'ctx>,
+}
+
+/// How an [`Optimize`] combines multiple objectives.
+///
+/// # See also:
+///
+/// - [`Optimize::set_priority()`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ObjectivePriority {
+    /// Optimize the objectives in the order they were added, each one
+    /// subject to the optimality of the previous ones. This is Z3's
+    /// default.
+    #[default]
+    Lexicographic,
+    /// Optimize each objective independently.
+    Box,
+    /// Find Pareto-optimal points, one per [`Optimize::check()`]; see
+    /// [`Optimize::pareto_iter()`].
+    Pareto,
+}
+
+impl ObjectivePriority {
+    fn as_str(self) -> &'static str {
+        match self {
+            ObjectivePriority::Lexicographic => "lex",
+            ObjectivePriority::Box => "box",
+            ObjectivePriority::Pareto => "pareto",
+        }
+    }
+}
+
+/// A Pareto-optimal point, as returned by [`ParetoFront`].
+#[derive(Debug)]
+pub struct ParetoPoint<'ctx> {
+    /// The model of the point.
+    pub model: Model<'ctx>,
+    /// The values of the objectives at the point, in the order of
+    /// [`Optimize::get_objectives()`].
+    ///
+    /// Those are the values in the model of the maximized and minimized
+    /// terms, and of the penalties of the soft constraint groups.
+    /// Objectives added by [`Optimize::from_string()`] or
+    /// [`Optimize::from_file()`] are evaluated as reported by
+    /// [`Optimize::get_objectives()`], which older Z3 versions negate for
+    /// maximizations.
+    pub values: Vec<Dynamic<'ctx>>,
+    opt: usize,
+}
+
+impl<'ctx> ParetoPoint<'ctx> {
+    /// Return the value of `objective` at this point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `objective` is not an objective of the optimizer the
+    /// point was found by.
+    pub fn value(&self, objective: ObjectiveHandle) -> &Dynamic<'ctx> {
+        assert_eq!(
+            objective.opt, self.opt,
+            "not an objective of this optimizer"
+        );
+        &self.values[objective.index()]
+    }
+}
+
+/// Iterator over the Pareto front of an [`Optimize`].
+///
+/// # See also:
+///
+/// - [`Optimize::pareto_iter()`]
+#[derive(Debug)]
+pub struct ParetoFront<'a, 'ctx> {
+    opt: &'a Optimize<'ctx>,
+    previous_priority: ObjectivePriority,
+    result: Option<SatResult>,
+    reason_unknown: Option<String>,
+}
+
+impl<'a, 'ctx> ParetoFront<'a, 'ctx> {
+    /// Return the result of the check that ended the iteration, or `None`
+    /// if the iteration has not ended yet.
+    ///
+    /// [`SatResult::Unsat`] means that the whole front was enumerated,
+    /// while [`SatResult::Unknown`] means that the optimizer gave up; see
+    /// [`ParetoFront::reason_unknown()`].
+    pub fn result(&self) -> Option<SatResult> {
+        self.result
+    }
+
+    /// Return the optimizer's justification if the iteration ended with
+    /// [`SatResult::Unknown`].
+    pub fn reason_unknown(&self) -> Option<&str> {
+        self.reason_unknown.as_deref()
+    }
+
+    fn finish(&mut self, result: SatResult) {
+        if result == SatResult::Unknown {
+            self.reason_unknown = self.opt.get_reason_unknown();
+        }
+        self.result = Some(result);
+    }
+}
+
+impl Drop for ParetoFront<'_, '_> {
+    fn drop(&mut self) {
+        self.opt.set_priority(self.previous_priority);
+    }
+}
+
+impl<'a, 'ctx> Iterator for ParetoFront<'a, 'ctx> {
+    type Item = ParetoPoint<'ctx>;
+
+    fn next(&mut self) -> Option<ParetoPoint<'ctx>> {
+        if self.result.is_some() {
+            return None;
+        }
+        let model = match self.opt.check(&[]) {
+            SatResult::Sat => self.opt.get_model(),
+            result => {
+                self.finish(result);
+                return None;
+            }
+        };
+        let model = match model {
+            Some(model) => model,
+            None => {
+                self.finish(SatResult::Unknown);
+                return None;
+            }
+        };
+        // Evaluate the maximized and minimized terms themselves, as some
+        // Z3 versions report maximizations negated.
+        let objectives = self.opt.objectives.borrow();
+        let values = self
+            .opt
+            .get_objectives()
+            .iter()
+            .enumerate()
+            .map(|(i, reported)| {
+                let term = objectives
+                    .get(i)
+                    .and_then(Option::as_ref)
+                    .unwrap_or(reported);
+                model.eval(term, true).unwrap()
+            })
+            .collect();
+        drop(objectives);
+        Some(ParetoPoint {
+            model,
+            values,
+            opt: self.opt.z3_opt as usize,
+        })
+    }
+}
+
 /// A rational non-negative weight for soft assertions.
 /// This trait is sealed and cannot be implemented for types outside of
 /// `z3`.
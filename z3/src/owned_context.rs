@@ -0,0 +1,185 @@
+use std::fmt;
+use std::sync::Arc;
+
+use z3_sys::*;
+
+use crate::{Config, Context, Translate};
+
+/// A [`Context`] bundled with values living in it, which can be sent to
+/// other threads.
+///
+/// Values that borrow a `Context`, such as ASTs, solvers and models, are
+/// tied to the thread the context is used on. An `OwnedContext` owns both
+/// its context and a value of type `T`, e.g. a [`Solver`](crate::Solver)
+/// or a tuple of a solver and some terms, so the whole session can be
+/// moved to another thread. The value is copied in from another context
+/// with [`Translate`], used through closures that get a reference to the
+/// context, and copied out to other contexts with
+/// [`OwnedContext::translate()`].
+///
+/// `T` is the type of the value, e.g. `OwnedContext<(Solver, Int)>`; its
+/// own lifetime does not matter, the closures get the value with the
+/// lifetime of the context.
+///
+/// # Examples
+/// ```
+/// # use z3::{ast::{Ast, Int}, Config, Context, OwnedContext, SatResult, Solver};
+/// let cfg = Config::new();
+/// let ctx = Context::new(&cfg);
+/// let x = Int::new_const(&ctx, "x");
+/// let solver = Solver::new(&ctx);
+/// solver.assert(&x.gt(&Int::from_i64(&ctx, 2)));
+///
+/// let session = OwnedContext::translated(&cfg, &(solver, x));
+/// let (session, value) = std::thread::spawn(move || {
+///     let value = session.with(|ctx, (solver, x)| {
+///         solver.assert(&x.lt(&Int::from_i64(ctx, 4)));
+///         assert_eq!(solver.check(), SatResult::Sat);
+///         solver.get_model().unwrap().eval(x, true).unwrap().as_i64()
+///     });
+///     (session, value)
+/// })
+/// .join()
+/// .unwrap();
+/// assert_eq!(value, Some(3));
+///
+/// // The solver keeps the assertion added on the other thread.
+/// let (solver, x) = session.translate(&ctx);
+/// solver.assert(&x._eq(&Int::from_i64(&ctx, 2)));
+/// assert_eq!(solver.check(), SatResult::Unsat);
+/// ```
+///
+/// The value only ever comes from a translation, so the closures cannot
+/// put values of another context into it, or return values of its own:
+///
+/// ```compile_fail
+/// # use z3::{ast::Int, Config, Context, OwnedContext};
+/// let cfg = Config::new();
+/// let leaked: &'static Context = Box::leak(Box::new(Context::new(&cfg)));
+/// let x = Int::new_const(leaked, "x");
+/// let session = OwnedContext::<Int>::build(&cfg, |_| x.clone());
+/// ```
+///
+/// ```compile_fail
+/// # use z3::{ast::Int, Config, Context, OwnedContext};
+/// let cfg = Config::new();
+/// let ctx = Context::new(&cfg);
+/// let session = OwnedContext::translated(&cfg, &Int::new_const(&ctx, "x"));
+/// let x = session.with(|_, x| x.clone());
+/// ```
+///
+/// Translated solvers never have a
+/// [`UserPropagator`](crate::UserPropagator), and the closures only get a
+/// shared reference to the value, so none can be attached to them.
+pub struct OwnedContext<T: Translate = ()> {
+    // Declared first to be dropped before the context it lives in.
+    value: T::Target<'static>,
+    ctx: Arc<Context>,
+}
+
+/// Handle that can be used to interrupt the computation of an
+/// [`OwnedContext`] from another thread.
+///
+/// Unlike a [`ContextHandle`](crate::ContextHandle), it does not borrow the
+/// context, so the `OwnedContext` can be moved to another thread while
+/// the handle is kept.
+///
+/// # See also:
+///
+/// - [`OwnedContext::handle()`]
+#[derive(Clone, Debug)]
+pub struct OwnedContextHandle {
+    ctx: Arc<Context>,
+}
+
+impl OwnedContext {
+    /// Create a new context, holding no value.
+    pub fn new(cfg: &Config) -> OwnedContext {
+        OwnedContext::translated(cfg, &())
+    }
+
+    /// Create a new context, and translate `value` to it.
+    // The context is only shared with `OwnedContextHandle`, to interrupt it.
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub fn translated<S: Translate>(cfg: &Config, value: &S) -> OwnedContext<S::Target<'static>> {
+        let ctx = Arc::new(Context::new(cfg));
+        let value = value.translate_to(unsafe { context(&ctx) });
+        OwnedContext { value, ctx }
+    }
+}
+
+impl<T: Translate> OwnedContext<T> {
+    /// Call `f` with the context and the value.
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: for<'a> FnOnce(&'a Context, &T::Target<'a>) -> R,
+    {
+        f(unsafe { context(&self.ctx) }, &self.value)
+    }
+
+    /// Replace the value with the result of calling `f` with the context
+    /// and the value, keeping the context.
+    ///
+    /// # Safety
+    ///
+    /// `f` must only return values of the context it is given, as values
+    /// of other contexts, e.g. leaked ones, also coerce to the returned
+    /// type.
+    pub(crate) unsafe fn map<U, F>(self, f: F) -> OwnedContext<U>
+    where
+        U: Translate,
+        F: for<'a> FnOnce(&'a Context, T::Target<'a>) -> U::Target<'a>,
+    {
+        let OwnedContext { value, ctx } = self;
+        let value = f(context(&ctx), value);
+        OwnedContext { value, ctx }
+    }
+
+    /// Translate the value to the context `dest`.
+    pub fn translate<'dest>(&self, dest: &'dest Context) -> T::Target<'dest> {
+        self.value.translate_to(dest)
+    }
+
+    /// Obtain a handle that can be used to interrupt computation from
+    /// another thread.
+    pub fn handle(&self) -> OwnedContextHandle {
+        OwnedContextHandle {
+            ctx: self.ctx.clone(),
+        }
+    }
+}
+
+/// The values and closures given the context only get a lifetime bounded by
+/// the `OwnedContext`, or a higher-ranked one, so they cannot outlive it.
+unsafe fn context(ctx: &Arc<Context>) -> &'static Context {
+    &*Arc::as_ptr(ctx)
+}
+
+impl<T: Translate> fmt::Debug for OwnedContext<T>
+where
+    T::Target<'static>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("OwnedContext")
+            .field("value", &self.value)
+            .field("ctx", &self.ctx)
+            .finish()
+    }
+}
+
+// The value is a translation to the context, so the context and the Z3
+// objects in the value are only reachable through the `OwnedContext`, and
+// are all moved together. Translated solvers have no user propagator.
+unsafe impl<T: Translate> Send for OwnedContext<T> {}
+
+impl OwnedContextHandle {
+    /// Interrupt a solver performing a satisfiability test, a tactic processing a goal, or simplify functions.
+    pub fn interrupt(&self) {
+        unsafe {
+            Z3_interrupt(self.ctx.z3_ctx);
+        }
+    }
+}
+
+unsafe impl Sync for OwnedContextHandle {}
+unsafe impl Send for OwnedContextHandle {}
@@ -3,7 +3,7 @@ use std::fmt;
 
 use z3_sys::*;
 
-use crate::{Context, Params, Symbol};
+use crate::{Context, Error, ParamDescrs, Params, Symbol};
 
 impl<'ctx> Params<'ctx> {
     unsafe fn wrap(ctx: &'ctx Context, z3_params: Z3_params) -> Params<'ctx> {
@@ -58,6 +58,50 @@ impl<'ctx> Params<'ctx> {
             );
         };
     }
+
+    /// Fallible version of [`Params::set_symbol()`], also returning an
+    /// [`Error`] if `k` or `v` contain a NUL byte.
+    ///
+    /// Note that Z3 only checks parameter names and values against a
+    /// component's parameter descriptions when the parameters are applied,
+    /// e.g. through [`Solver::try_set_params()`](crate::Solver::try_set_params).
+    pub fn try_set_symbol<K: Into<Symbol>, V: Into<Symbol>>(
+        &mut self,
+        k: K,
+        v: V,
+    ) -> Result<(), Error> {
+        let k = k.into().try_as_z3_symbol(self.ctx)?;
+        let v = v.into().try_as_z3_symbol(self.ctx)?;
+        unsafe { Z3_params_set_symbol(self.ctx.z3_ctx, self.z3_params, k, v) };
+        self.ctx.check_error()
+    }
+
+    /// Fallible version of [`Params::set_bool()`].
+    ///
+    /// See [`Params::try_set_symbol()`] for when parameters are validated.
+    pub fn try_set_bool<K: Into<Symbol>>(&mut self, k: K, v: bool) -> Result<(), Error> {
+        let k = k.into().try_as_z3_symbol(self.ctx)?;
+        unsafe { Z3_params_set_bool(self.ctx.z3_ctx, self.z3_params, k, v) };
+        self.ctx.check_error()
+    }
+
+    /// Fallible version of [`Params::set_f64()`].
+    ///
+    /// See [`Params::try_set_symbol()`] for when parameters are validated.
+    pub fn try_set_f64<K: Into<Symbol>>(&mut self, k: K, v: f64) -> Result<(), Error> {
+        let k = k.into().try_as_z3_symbol(self.ctx)?;
+        unsafe { Z3_params_set_double(self.ctx.z3_ctx, self.z3_params, k, v) };
+        self.ctx.check_error()
+    }
+
+    /// Fallible version of [`Params::set_u32()`].
+    ///
+    /// See [`Params::try_set_symbol()`] for when parameters are validated.
+    pub fn try_set_u32<K: Into<Symbol>>(&mut self, k: K, v: u32) -> Result<(), Error> {
+        let k = k.into().try_as_z3_symbol(self.ctx)?;
+        unsafe { Z3_params_set_uint(self.ctx.z3_ctx, self.z3_params, k, v) };
+        self.ctx.check_error()
+    }
 }
 
 /// Get a global (or module) parameter.
@@ -123,3 +167,95 @@ impl Drop for Params<'_> {
         unsafe { Z3_params_dec_ref(self.ctx.z3_ctx, self.z3_params) };
     }
 }
+
+impl<'ctx> ParamDescrs<'ctx> {
+    pub(crate) unsafe fn wrap(
+        ctx: &'ctx Context,
+        z3_param_descrs: Z3_param_descrs,
+    ) -> ParamDescrs<'ctx> {
+        Z3_param_descrs_inc_ref(ctx.z3_ctx, z3_param_descrs);
+        ParamDescrs {
+            ctx,
+            z3_param_descrs,
+        }
+    }
+
+    /// Return the number of described parameters.
+    pub fn len(&self) -> usize {
+        unsafe { Z3_param_descrs_size(self.ctx.z3_ctx, self.z3_param_descrs) as usize }
+    }
+
+    /// Return `true` if no parameters are described.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the names of the described parameters.
+    pub fn names(&self) -> Vec<Symbol> {
+        (0..self.len() as u32)
+            .map(|i| unsafe {
+                Symbol::from_z3_symbol(
+                    self.ctx,
+                    Z3_param_descrs_get_name(self.ctx.z3_ctx, self.z3_param_descrs, i),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the kind of the parameter `name`, or [`ParamKind::Invalid`]
+    /// if there is no such parameter.
+    pub fn kind<S: Into<Symbol>>(&self, name: S) -> ParamKind {
+        unsafe {
+            Z3_param_descrs_get_kind(
+                self.ctx.z3_ctx,
+                self.z3_param_descrs,
+                name.into().as_z3_symbol(self.ctx),
+            )
+        }
+    }
+
+    /// Return the documentation of the parameter `name`, or `None` if there
+    /// is no such parameter.
+    pub fn documentation<S: Into<Symbol>>(&self, name: S) -> Option<String> {
+        let name = name.into();
+        if self.kind(name.clone()) == ParamKind::Invalid {
+            return None;
+        }
+        let p = unsafe {
+            Z3_param_descrs_get_documentation(
+                self.ctx.z3_ctx,
+                self.z3_param_descrs,
+                name.as_z3_symbol(self.ctx),
+            )
+        };
+        if p.is_null() {
+            return None;
+        }
+        Some(unsafe { CStr::from_ptr(p) }.to_string_lossy().into_owned())
+    }
+}
+
+impl fmt::Display for ParamDescrs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let p = unsafe { Z3_param_descrs_to_string(self.ctx.z3_ctx, self.z3_param_descrs) };
+        if p.is_null() {
+            return Result::Err(fmt::Error);
+        }
+        match unsafe { CStr::from_ptr(p) }.to_str() {
+            Ok(s) => write!(f, "{s}"),
+            Err(_) => Result::Err(fmt::Error),
+        }
+    }
+}
+
+impl fmt::Debug for ParamDescrs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl Drop for ParamDescrs<'_> {
+    fn drop(&mut self) {
+        unsafe { Z3_param_descrs_dec_ref(self.ctx.z3_ctx, self.z3_param_descrs) };
+    }
+}
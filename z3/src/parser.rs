@@ -0,0 +1,124 @@
+use std::convert::TryInto;
+use std::ffi::CString;
+
+use z3_sys::*;
+
+use crate::{ast::Bool, AstVector, Context, Error, FuncDecl, Sort};
+
+/// Parse an SMT-LIB2 benchmark and return its assertions, without adding
+/// them to any solver.
+///
+/// The given `sorts` and `decls` are made available to the benchmark under
+/// their own names, so that it can refer to sorts and symbols created
+/// through the API. Anything the benchmark declares itself is only
+/// reachable through the returned assertions.
+///
+/// Returns an [`Error`] if the input contains a NUL byte or Z3 fails to
+/// parse it.
+///
+/// ```
+/// # use z3::{Config, Context, FuncDecl, Sort, parse_smtlib2_string};
+/// # let cfg = Config::new();
+/// # let ctx = Context::new(&cfg);
+/// let f = FuncDecl::new(&ctx, "f", &[&Sort::int(&ctx)], &Sort::int(&ctx));
+/// let assertions = parse_smtlib2_string(
+///     &ctx,
+///     "(declare-const x Int) (assert (> (f x) x)) (assert (= x 2))",
+///     &[],
+///     &[&f],
+/// )
+/// .unwrap();
+/// assert_eq!(assertions.len(), 2);
+///
+/// assert!(parse_smtlib2_string(&ctx, "(assert (> y 0))", &[], &[]).is_err());
+/// ```
+///
+/// # See also:
+///
+/// - [`parse_smtlib2_file()`]
+/// - [`Solver::from_string()`](crate::Solver::from_string)
+pub fn parse_smtlib2_string<'ctx, T: Into<Vec<u8>>>(
+    ctx: &'ctx Context,
+    source_string: T,
+    sorts: &[&Sort<'ctx>],
+    decls: &[&FuncDecl<'ctx>],
+) -> Result<Vec<Bool<'ctx>>, Error> {
+    let source_cstring = CString::new(source_string).map_err(Error::from_nul)?;
+    parse_with(ctx, sorts, decls, |sorts, decls| unsafe {
+        Z3_parse_smtlib2_string(
+            ctx.z3_ctx,
+            source_cstring.as_ptr(),
+            sorts.len().try_into().unwrap(),
+            sorts.names.as_ptr(),
+            sorts.values.as_ptr(),
+            decls.len().try_into().unwrap(),
+            decls.names.as_ptr(),
+            decls.values.as_ptr(),
+        )
+    })
+}
+
+/// Parse an SMT-LIB2 benchmark file and return its assertions, without
+/// adding them to any solver.
+///
+/// This behaves like [`parse_smtlib2_string()`], but reads the benchmark
+/// from `file_name`. Returns an [`Error`] if the file cannot be read or
+/// parsed.
+pub fn parse_smtlib2_file<'ctx, T: Into<Vec<u8>>>(
+    ctx: &'ctx Context,
+    file_name: T,
+    sorts: &[&Sort<'ctx>],
+    decls: &[&FuncDecl<'ctx>],
+) -> Result<Vec<Bool<'ctx>>, Error> {
+    let file_cstring = CString::new(file_name).map_err(Error::from_nul)?;
+    parse_with(ctx, sorts, decls, |sorts, decls| unsafe {
+        Z3_parse_smtlib2_file(
+            ctx.z3_ctx,
+            file_cstring.as_ptr(),
+            sorts.len().try_into().unwrap(),
+            sorts.names.as_ptr(),
+            sorts.values.as_ptr(),
+            decls.len().try_into().unwrap(),
+            decls.names.as_ptr(),
+            decls.values.as_ptr(),
+        )
+    })
+}
+
+struct Named<T> {
+    names: Vec<Z3_symbol>,
+    values: Vec<T>,
+}
+
+impl<T> Named<T> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+fn parse_with<'ctx>(
+    ctx: &'ctx Context,
+    sorts: &[&Sort<'ctx>],
+    decls: &[&FuncDecl<'ctx>],
+    parse: impl FnOnce(&Named<Z3_sort>, &Named<Z3_func_decl>) -> Z3_ast_vector,
+) -> Result<Vec<Bool<'ctx>>, Error> {
+    let sorts = Named {
+        names: sorts
+            .iter()
+            .map(|s| unsafe { Z3_get_sort_name(ctx.z3_ctx, s.z3_sort) })
+            .collect(),
+        values: sorts.iter().map(|s| s.z3_sort).collect(),
+    };
+    let decls = Named {
+        names: decls
+            .iter()
+            .map(|d| unsafe { Z3_get_decl_name(ctx.z3_ctx, d.z3_func_decl) })
+            .collect(),
+        values: decls.iter().map(|d| d.z3_func_decl).collect(),
+    };
+
+    let z3_vec = parse(&sorts, &decls);
+    ctx.check_error()?;
+
+    Ok(unsafe { AstVector::wrap(ctx, z3_vec) }.into())
+}
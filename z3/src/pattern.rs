@@ -4,7 +4,10 @@ use std::fmt;
 
 use z3_sys::*;
 
-use crate::{ast::Ast, Context, Pattern};
+use crate::{
+    ast::{Ast, Dynamic},
+    Context, Pattern,
+};
 
 impl<'ctx> Pattern<'ctx> {
     /// Create a pattern for quantifier instantiation.
@@ -46,6 +49,26 @@ impl<'ctx> Pattern<'ctx> {
     }
 }
 
+impl<'ctx> Pattern<'ctx> {
+    pub(crate) unsafe fn wrap(ctx: &'ctx Context, z3_pattern: Z3_pattern) -> Pattern<'ctx> {
+        Z3_inc_ref(ctx.z3_ctx, z3_pattern as Z3_ast);
+        Pattern { ctx, z3_pattern }
+    }
+
+    /// Return the terms of this (multi-)pattern.
+    pub fn terms(&self) -> Vec<Dynamic<'ctx>> {
+        let n = unsafe { Z3_get_pattern_num_terms(self.ctx.z3_ctx, self.z3_pattern) };
+        (0..n)
+            .map(|i| unsafe {
+                Dynamic::wrap(
+                    self.ctx,
+                    Z3_get_pattern(self.ctx.z3_ctx, self.z3_pattern, i),
+                )
+            })
+            .collect()
+    }
+}
+
 impl fmt::Debug for Pattern<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         let p = unsafe { Z3_pattern_to_string(self.ctx.z3_ctx, self.z3_pattern) };
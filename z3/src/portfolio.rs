@@ -0,0 +1,262 @@
+use std::fmt;
+use std::panic;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::ast::Bool;
+use crate::{Config, Context, Model, OwnedContext, Params, SatResult, Solver, Tactic};
+
+type Configuration<'a> = Box<dyn for<'c> FnOnce(&'c Context) -> Solver<'c> + Send + 'a>;
+
+/// Races several solver configurations on the assertions of a [`Solver`],
+/// and keeps the first definitive answer.
+///
+/// Each configuration runs on its own thread, in a fresh context holding a
+/// translation of the solver's assertions. As soon as one of them finds the
+/// assertions satisfiable or unsatisfiable, the others are interrupted, and
+/// the answer is returned in the solver's context.
+///
+/// # Examples
+/// ```
+/// # use z3::{ast::{Ast, Int}, Config, Context, Portfolio, SatResult, Solver};
+/// let cfg = Config::new();
+/// let ctx = Context::new(&cfg);
+/// let solver = Solver::new(&ctx);
+/// let x = Int::new_const(&ctx, "x");
+/// solver.assert(&(&x * &x)._eq(&Int::from_i64(&ctx, 49)));
+/// solver.assert(&x.lt(&Int::from_i64(&ctx, 0)));
+///
+/// let outcome = Portfolio::new(&solver)
+///     .add_logic("QF_NIA")
+///     .add_tactic("qfnia")
+///     .add_params(|params| params.set_u32("random_seed", 7))
+///     .check();
+/// assert_eq!(outcome.result, SatResult::Sat);
+/// let model = outcome.model.unwrap();
+/// assert_eq!(model.eval(&x, true).unwrap().as_i64(), Some(-7));
+/// ```
+pub struct Portfolio<'a, 'ctx> {
+    solver: &'a Solver<'ctx>,
+    cfg: Option<&'a Config>,
+    configurations: Vec<Configuration<'a>>,
+}
+
+/// The answer of a [`Portfolio`].
+#[derive(Debug)]
+pub struct PortfolioOutcome<'ctx> {
+    /// The first definitive answer, or [`SatResult::Unknown`] if no
+    /// configuration found one.
+    pub result: SatResult,
+    /// The model found by the winning configuration, in the context of the
+    /// portfolio's solver, if the result is [`SatResult::Sat`].
+    pub model: Option<Model<'ctx>>,
+    /// The index of the winning configuration, in the order they were
+    /// added.
+    pub winner: Option<usize>,
+    /// The reasons given by the configurations, if the result is
+    /// [`SatResult::Unknown`].
+    pub reasons_unknown: Vec<String>,
+}
+
+struct Report {
+    index: usize,
+    result: SatResult,
+    model: OwnedContext<Option<Model<'static>>>,
+    reason_unknown: Option<String>,
+}
+
+impl<'a, 'ctx> Portfolio<'a, 'ctx> {
+    /// Create a portfolio for the assertions of `solver`, with no
+    /// configurations.
+    pub fn new(solver: &'a Solver<'ctx>) -> Portfolio<'a, 'ctx> {
+        Portfolio {
+            solver,
+            cfg: None,
+            configurations: Vec::new(),
+        }
+    }
+
+    /// Use `cfg` to create the contexts the configurations run in, instead
+    /// of a default [`Config`].
+    pub fn with_config(self, cfg: &'a Config) -> Portfolio<'a, 'ctx> {
+        Portfolio {
+            cfg: Some(cfg),
+            ..self
+        }
+    }
+
+    /// Add a configuration, given as a function creating the solver to run
+    /// in a fresh context.
+    ///
+    /// Checking the portfolio panics if the function returns a solver of
+    /// another context.
+    pub fn add_solver<F>(mut self, f: F) -> Portfolio<'a, 'ctx>
+    where
+        F: for<'c> FnOnce(&'c Context) -> Solver<'c> + Send + 'a,
+    {
+        self.configurations.push(Box::new(f));
+        self
+    }
+
+    /// Add a default solver, configured with the parameters set by `f`.
+    pub fn add_params<F>(self, f: F) -> Portfolio<'a, 'ctx>
+    where
+        F: for<'c> FnOnce(&mut Params<'c>) + Send + 'a,
+    {
+        self.add_solver(move |ctx| {
+            let solver = Solver::new(ctx);
+            let mut params = Params::new(ctx);
+            f(&mut params);
+            solver.set_params(&params);
+            solver
+        })
+    }
+
+    /// Add a solver for `logic`; see [`Solver::new_for_logic()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if Z3 cannot create a solver for `logic`.
+    pub fn add_logic(self, logic: &str) -> Portfolio<'a, 'ctx> {
+        assert!(
+            Solver::new_for_logic(self.solver.get_context(), logic).is_some(),
+            "no solver for logic {}",
+            logic
+        );
+        let logic = logic.to_owned();
+        self.add_solver(move |ctx| Solver::new_for_logic(ctx, logic).unwrap())
+    }
+
+    /// Add a solver using the tactic `name`; see [`Tactic::solver()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a known tactic.
+    pub fn add_tactic(self, name: &str) -> Portfolio<'a, 'ctx> {
+        Tactic::new(self.solver.get_context(), name);
+        let name = name.to_owned();
+        self.add_solver(move |ctx| Tactic::new(ctx, &name).solver())
+    }
+
+    /// Race the configurations on the assertions of the solver.
+    ///
+    /// If no configuration was added, a single default solver is used.
+    pub fn check(self) -> PortfolioOutcome<'ctx> {
+        self.check_assumptions(&[])
+    }
+
+    /// Race the configurations on the assertions of the solver and the
+    /// given assumptions; see [`Solver::check_assumptions()`].
+    pub fn check_assumptions(mut self, assumptions: &[Bool<'ctx>]) -> PortfolioOutcome<'ctx> {
+        if self.configurations.is_empty() {
+            self = self.add_solver(|ctx| Solver::new(ctx));
+        }
+        let default_cfg = Config::new();
+        let cfg = self.cfg.unwrap_or(&default_cfg);
+        let problem = (self.solver.get_assertions(), assumptions.to_vec());
+        let sessions: Vec<_> = self
+            .configurations
+            .iter()
+            .map(|_| OwnedContext::translated(cfg, &problem))
+            .collect();
+        let handles: Vec<_> = sessions.iter().map(OwnedContext::handle).collect();
+
+        let (sender, receiver) = mpsc::channel();
+        let reports = thread::scope(|scope| {
+            let mut threads = Vec::with_capacity(handles.len());
+            for (index, (configuration, session)) in
+                self.configurations.drain(..).zip(sessions).enumerate()
+            {
+                let sender = sender.clone();
+                threads.push(scope.spawn(move || {
+                    let mut outcome = None;
+                    // The model comes from a solver checked to be in `ctx`.
+                    let model = unsafe {
+                        session.map::<Option<Model>, _>(|ctx, (assertions, assumptions)| {
+                            let solver = configuration(ctx);
+                            assert!(
+                                solver.get_context() == ctx,
+                                "a portfolio configuration returned a solver of another context"
+                            );
+                            for assertion in assertions {
+                                solver.assert(&assertion);
+                            }
+                            let result = solver.check_assumptions(&assumptions);
+                            outcome = Some((result, solver.get_reason_unknown()));
+                            solver.get_model().filter(|_| result == SatResult::Sat)
+                        })
+                    };
+                    let (result, reason_unknown) = outcome.unwrap();
+                    // The portfolio stops waiting once all reports are in.
+                    let _ = sender.send(Report {
+                        index,
+                        result,
+                        model,
+                        reason_unknown,
+                    });
+                }));
+            }
+            drop(sender);
+
+            let mut reports = Vec::with_capacity(handles.len());
+            let mut decided = false;
+            while reports.len() < handles.len() {
+                if decided {
+                    // An interrupt is lost if it comes before the check
+                    // starts, so repeat it until all the losers stop.
+                    for handle in &handles {
+                        handle.interrupt();
+                    }
+                }
+                match receiver.recv_timeout(Duration::from_millis(10)) {
+                    Ok(report) => {
+                        decided |= report.result != SatResult::Unknown;
+                        reports.push(report);
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            for thread in threads {
+                if let Err(payload) = thread.join() {
+                    panic::resume_unwind(payload);
+                }
+            }
+            reports
+        });
+
+        let ctx = self.solver.get_context();
+        match reports.iter().find(|r| r.result != SatResult::Unknown) {
+            Some(winner) => PortfolioOutcome {
+                result: winner.result,
+                model: winner.model.translate(ctx),
+                winner: Some(winner.index),
+                reasons_unknown: Vec::new(),
+            },
+            None => {
+                let mut reports = reports;
+                reports.sort_by_key(|r| r.index);
+                PortfolioOutcome {
+                    result: SatResult::Unknown,
+                    model: None,
+                    winner: None,
+                    reasons_unknown: reports
+                        .into_iter()
+                        .filter_map(|r| r.reason_unknown)
+                        .collect(),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Portfolio<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Portfolio")
+            .field("solver", self.solver)
+            .field("cfg", &self.cfg)
+            .field("configurations", &self.configurations.len())
+            .finish()
+    }
+}
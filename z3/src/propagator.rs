@@ -0,0 +1,396 @@
+use std::any::Any;
+use std::convert::TryInto;
+use std::ffi::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use z3_sys::*;
+
+use crate::{
+    ast::{Ast, Bool, Dynamic},
+    Context, FuncDecl, PropagatorCallback, Solver, Sort, Symbol,
+};
+
+/// A custom theory that takes part in a [`Solver`]'s search.
+///
+/// Terms registered with [`Solver::propagate_register()`] (or
+/// [`PropagatorCallback::register()`]) are tracked by the solver, which
+/// reports their assignments and equalities through the callbacks below.
+/// From within a callback, the propagator can use the
+/// [`PropagatorCallback`] to propagate consequences or raise conflicts.
+///
+/// The propagator must keep its state consistent with the solver's
+/// backtracking: every [`UserPropagator::pop()`] undoes the state changes
+/// made since the matching number of [`UserPropagator::push()`] calls.
+///
+/// Only Boolean and bit-vector terms can be registered.
+///
+/// This needs the `user-propagator` feature, and Z3 4.13 or later.
+///
+/// # Examples
+///
+/// A propagator that forbids two registered Booleans from both being true:
+///
+/// ```
+/// # use z3::{ast::{Ast, Bool, Dynamic}, Config, Context, PropagatorCallback, SatResult, Solver, UserPropagator};
+/// struct AtMostOne<'ctx> {
+///     trail: Vec<Vec<Dynamic<'ctx>>>,
+///     true_terms: Vec<Dynamic<'ctx>>,
+/// }
+///
+/// impl<'ctx> UserPropagator<'ctx> for AtMostOne<'ctx> {
+///     fn push(&mut self) {
+///         self.trail.push(self.true_terms.clone());
+///     }
+///
+///     fn pop(&mut self, num_scopes: u32) {
+///         for _ in 0..num_scopes {
+///             self.true_terms = self.trail.pop().unwrap();
+///         }
+///     }
+///
+///     fn fixed(&mut self, cb: &PropagatorCallback<'ctx>, term: &Dynamic<'ctx>, value: &Dynamic<'ctx>) {
+///         if value.as_bool().and_then(|b| b.as_bool()) == Some(true) {
+///             self.true_terms.push(term.clone());
+///             if self.true_terms.len() > 1 {
+///                 let fixed: Vec<&dyn Ast<'ctx>> =
+///                     self.true_terms.iter().map(|t| t as &dyn Ast<'ctx>).collect();
+///                 cb.conflict(&fixed, &[]);
+///             }
+///         }
+///     }
+/// }
+///
+/// let cfg = Config::new();
+/// let ctx = Context::new(&cfg);
+/// let mut solver = Solver::new(&ctx);
+/// let a = Bool::new_const(&ctx, "a");
+/// let b = Bool::new_const(&ctx, "b");
+/// solver.set_user_propagator(AtMostOne { trail: vec![], true_terms: vec![] });
+/// solver.propagate_register(&a);
+/// solver.propagate_register(&b);
+///
+/// solver.assert(&Bool::or(&ctx, &[&a, &b]));
+/// assert_eq!(solver.check(), SatResult::Sat);
+/// solver.assert(&a);
+/// solver.assert(&b);
+/// assert_eq!(solver.check(), SatResult::Unsat);
+/// ```
+pub trait UserPropagator<'ctx> {
+    /// Called when the solver creates a backtracking point.
+    fn push(&mut self);
+
+    /// Called when the solver backtracks `num_scopes` backtracking points.
+    fn pop(&mut self, num_scopes: u32);
+
+    /// Called when the registered `term` is assigned `value`.
+    fn fixed(
+        &mut self,
+        _cb: &PropagatorCallback<'ctx>,
+        _term: &Dynamic<'ctx>,
+        _value: &Dynamic<'ctx>,
+    ) {
+    }
+
+    /// Called when the registered terms `lhs` and `rhs` become equal.
+    fn eq(&mut self, _cb: &PropagatorCallback<'ctx>, _lhs: &Dynamic<'ctx>, _rhs: &Dynamic<'ctx>) {}
+
+    /// Called once all registered terms have been assigned, before the
+    /// solver reports [`SatResult::Sat`](crate::SatResult::Sat).
+    ///
+    /// Propagating a consequence or a conflict from here makes the solver
+    /// continue its search.
+    fn final_check(&mut self, _cb: &PropagatorCallback<'ctx>) {}
+
+    /// Called when the solver creates a term whose head was declared with
+    /// [`FuncDecl::new_user_propagated()`].
+    fn created(&mut self, _cb: &PropagatorCallback<'ctx>, _term: &Dynamic<'ctx>) {}
+}
+
+impl<'ctx> PropagatorCallback<'ctx> {
+    /// Start tracking `term`, typically a subterm of a term passed to
+    /// [`UserPropagator::created()`].
+    pub fn register(&self, term: &dyn Ast<'ctx>) {
+        assert_eq!(self.ctx, term.get_ctx());
+        unsafe { Z3_solver_propagate_register_cb(self.ctx.z3_ctx, self.z3_cb, term.get_z3_ast()) };
+    }
+
+    /// Propagate `consequence`, justified by the registered terms in `fixed`
+    /// having their current values and by the equalities in `eqs`.
+    ///
+    /// Returns `false` if the solver is already in a conflict, in which case
+    /// the callback should return without propagating anything else.
+    pub fn propagate(
+        &self,
+        fixed: &[&dyn Ast<'ctx>],
+        eqs: &[(&dyn Ast<'ctx>, &dyn Ast<'ctx>)],
+        consequence: &Bool<'ctx>,
+    ) -> bool {
+        assert!(fixed.iter().all(|t| t.get_ctx() == self.ctx));
+        assert!(eqs
+            .iter()
+            .all(|(l, r)| l.get_ctx() == self.ctx && r.get_ctx() == self.ctx));
+        assert_eq!(self.ctx, consequence.get_ctx());
+        let fixed: Vec<Z3_ast> = fixed.iter().map(|t| t.get_z3_ast()).collect();
+        let lhs: Vec<Z3_ast> = eqs.iter().map(|(l, _)| l.get_z3_ast()).collect();
+        let rhs: Vec<Z3_ast> = eqs.iter().map(|(_, r)| r.get_z3_ast()).collect();
+        unsafe {
+            Z3_solver_propagate_consequence(
+                self.ctx.z3_ctx,
+                self.z3_cb,
+                fixed.len().try_into().unwrap(),
+                fixed.as_ptr(),
+                lhs.len().try_into().unwrap(),
+                lhs.as_ptr(),
+                rhs.as_ptr(),
+                consequence.get_z3_ast(),
+            )
+        }
+    }
+
+    /// Raise a conflict: the current values of the registered terms in
+    /// `fixed`, together with the equalities in `eqs`, are inconsistent.
+    ///
+    /// Returns `false` if the solver was already in a conflict.
+    pub fn conflict(
+        &self,
+        fixed: &[&dyn Ast<'ctx>],
+        eqs: &[(&dyn Ast<'ctx>, &dyn Ast<'ctx>)],
+    ) -> bool {
+        self.propagate(fixed, eqs, &Bool::from_bool(self.ctx, false))
+    }
+}
+
+impl<'ctx> Solver<'ctx> {
+    /// Attach a [`UserPropagator`] to this solver.
+    ///
+    /// The propagator lives as long as the solver. Solvers created from this
+    /// one, with [`Clone`] or [`Solver::translate()`], do not inherit it.
+    /// Neither do the copies Z3 makes internally, for example in parallel
+    /// mode, so the propagator should not be used with it.
+    ///
+    /// If a callback panics, the search is interrupted and the panic is
+    /// resumed when [`Solver::check()`] or [`Solver::check_assumptions()`]
+    /// returns. The state of the propagator is then unspecified, as it
+    /// misses the callbacks made in the meantime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a propagator was already attached to this solver.
+    pub fn set_user_propagator<P: UserPropagator<'ctx> + 'ctx>(&mut self, propagator: P) {
+        assert!(
+            self.propagator.is_none(),
+            "a user propagator is already attached to this solver"
+        );
+        let state = Box::into_raw(Box::new(State {
+            ctx: self.ctx,
+            panic: None,
+            propagator,
+        }));
+        self.propagator = Some(PropagatorBox {
+            ptr: state as *mut c_void,
+            drop: drop_state::<P>,
+            take_panic: take_panic::<P>,
+        });
+        unsafe {
+            let z3_ctx = self.ctx.z3_ctx;
+            Z3_solver_propagate_init(
+                z3_ctx,
+                self.z3_slv,
+                state as *mut c_void,
+                Some(push_eh::<P>),
+                Some(pop_eh::<P>),
+                Some(fresh_eh),
+            );
+            Z3_solver_propagate_fixed(z3_ctx, self.z3_slv, Some(fixed_eh::<P>));
+            Z3_solver_propagate_eq(z3_ctx, self.z3_slv, Some(eq_eh::<P>));
+            Z3_solver_propagate_final(z3_ctx, self.z3_slv, Some(final_eh::<P>));
+            Z3_solver_propagate_created(z3_ctx, self.z3_slv, Some(created_eh::<P>));
+        }
+    }
+
+    /// Start tracking `term` with this solver's [`UserPropagator`].
+    ///
+    /// # See also:
+    ///
+    /// - [`Solver::set_user_propagator()`]
+    /// - [`PropagatorCallback::register()`]
+    pub fn propagate_register(&self, term: &dyn Ast<'ctx>) {
+        assert_eq!(self.ctx, term.get_ctx());
+        unsafe { Z3_solver_propagate_register(self.ctx.z3_ctx, self.z3_slv, term.get_z3_ast()) };
+    }
+}
+
+impl<'ctx> FuncDecl<'ctx> {
+    /// Declare a function whose applications are interpreted by a
+    /// [`UserPropagator`].
+    ///
+    /// The solver reports each application it creates through
+    /// [`UserPropagator::created()`].
+    pub fn new_user_propagated<S: Into<Symbol>>(
+        ctx: &'ctx Context,
+        name: S,
+        domain: &[&Sort<'ctx>],
+        range: &Sort<'ctx>,
+    ) -> Self {
+        assert!(domain.iter().all(|s| s.ctx.z3_ctx == ctx.z3_ctx));
+        assert_eq!(ctx.z3_ctx, range.ctx.z3_ctx);
+
+        let mut domain: Vec<_> = domain.iter().map(|s| s.z3_sort).collect();
+
+        unsafe {
+            Self::wrap(
+                ctx,
+                Z3_solver_propagate_declare(
+                    ctx.z3_ctx,
+                    name.into().as_z3_symbol(ctx),
+                    domain.len().try_into().unwrap(),
+                    domain.as_mut_ptr(),
+                    range.z3_sort,
+                ),
+            )
+        }
+    }
+}
+
+/// A type-erased, owned `State<'ctx, P>`.
+pub(crate) struct PropagatorBox {
+    ptr: *mut c_void,
+    drop: unsafe fn(*mut c_void),
+    take_panic: unsafe fn(*mut c_void) -> Option<Box<dyn Any + Send>>,
+}
+
+impl Drop for PropagatorBox {
+    fn drop(&mut self) {
+        unsafe { (self.drop)(self.ptr) };
+    }
+}
+
+impl<'ctx> Solver<'ctx> {
+    /// Resume the panic of a propagator callback during the last check, if
+    /// any.
+    pub(crate) fn resume_propagator_panic(&self) {
+        if let Some(propagator) = &self.propagator {
+            if let Some(payload) = unsafe { (propagator.take_panic)(propagator.ptr) } {
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+}
+
+struct State<'ctx, P> {
+    ctx: &'ctx Context,
+    // A panic of a callback, kept until the check returns.
+    panic: Option<Box<dyn Any + Send>>,
+    propagator: P,
+}
+
+unsafe fn drop_state<'ctx, P: UserPropagator<'ctx>>(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr as *mut State<'ctx, P>));
+}
+
+unsafe fn take_panic<'ctx, P: UserPropagator<'ctx>>(
+    ptr: *mut c_void,
+) -> Option<Box<dyn Any + Send>> {
+    (*(ptr as *mut State<'ctx, P>)).panic.take()
+}
+
+/// The user context of the copies Z3 makes of a solver, e.g. when
+/// translating it, which the propagator does not follow.
+static DETACHED: u8 = 0;
+
+fn is_detached(ctx: *mut c_void) -> bool {
+    ptr::eq(ctx as *const u8, &DETACHED)
+}
+
+/// Call `f` with the propagator and a callback handle recovered from the
+/// raw callback arguments.
+///
+/// Nothing is called for a detached copy, or after a callback panicked. A
+/// panic is caught, as it cannot unwind into Z3, and the search is
+/// interrupted so that it can be resumed once the check returns.
+unsafe fn with_state<'ctx, P, F>(ctx: *mut c_void, cb: Z3_solver_callback, f: F)
+where
+    P: UserPropagator<'ctx>,
+    F: FnOnce(&mut P, &PropagatorCallback<'ctx>),
+{
+    if is_detached(ctx) {
+        return;
+    }
+    let state = &mut *(ctx as *mut State<'ctx, P>);
+    if state.panic.is_some() {
+        return;
+    }
+    let cb = PropagatorCallback {
+        ctx: state.ctx,
+        z3_cb: cb,
+    };
+    let propagator = &mut state.propagator;
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(propagator, &cb))) {
+        state.panic = Some(payload);
+        Z3_interrupt(state.ctx.z3_ctx);
+    }
+}
+
+unsafe extern "C" fn push_eh<'ctx, P: UserPropagator<'ctx>>(
+    ctx: *mut c_void,
+    cb: Z3_solver_callback,
+) {
+    with_state::<P, _>(ctx, cb, |propagator, _| propagator.push());
+}
+
+unsafe extern "C" fn pop_eh<'ctx, P: UserPropagator<'ctx>>(
+    ctx: *mut c_void,
+    cb: Z3_solver_callback,
+    num_scopes: u32,
+) {
+    with_state::<P, _>(ctx, cb, |propagator, _| propagator.pop(num_scopes));
+}
+
+unsafe extern "C" fn fresh_eh(_ctx: *mut c_void, _new_context: Z3_context) -> *mut c_void {
+    &DETACHED as *const u8 as *mut c_void
+}
+
+unsafe extern "C" fn fixed_eh<'ctx, P: UserPropagator<'ctx>>(
+    ctx: *mut c_void,
+    cb: Z3_solver_callback,
+    term: Z3_ast,
+    value: Z3_ast,
+) {
+    with_state::<P, _>(ctx, cb, |propagator, cb| {
+        let term = Dynamic::wrap(cb.ctx, term);
+        let value = Dynamic::wrap(cb.ctx, value);
+        propagator.fixed(cb, &term, &value);
+    });
+}
+
+unsafe extern "C" fn eq_eh<'ctx, P: UserPropagator<'ctx>>(
+    ctx: *mut c_void,
+    cb: Z3_solver_callback,
+    lhs: Z3_ast,
+    rhs: Z3_ast,
+) {
+    with_state::<P, _>(ctx, cb, |propagator, cb| {
+        let lhs = Dynamic::wrap(cb.ctx, lhs);
+        let rhs = Dynamic::wrap(cb.ctx, rhs);
+        propagator.eq(cb, &lhs, &rhs);
+    });
+}
+
+unsafe extern "C" fn final_eh<'ctx, P: UserPropagator<'ctx>>(
+    ctx: *mut c_void,
+    cb: Z3_solver_callback,
+) {
+    with_state::<P, _>(ctx, cb, |propagator, cb| propagator.final_check(cb));
+}
+
+unsafe extern "C" fn created_eh<'ctx, P: UserPropagator<'ctx>>(
+    ctx: *mut c_void,
+    cb: Z3_solver_callback,
+    term: Z3_ast,
+) {
+    with_state::<P, _>(ctx, cb, |propagator, cb| {
+        let term = Dynamic::wrap(cb.ctx, term);
+        propagator.created(cb, &term);
+    });
+}
@@ -0,0 +1,334 @@
+//! [`serde`] support for [`Model`] and [`Statistics`], enabled by the
+//! `serde` feature.
+
+use serde::de::{self, DeserializeSeed, Deserializer};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use z3_sys::*;
+
+use crate::ast::{Ast, Dynamic};
+use crate::{parse_smtlib2_string, Context, FuncDecl, FuncInterp, Model, Sort, Statistics, Symbol};
+
+/// The serialized form of a [`Model`]. Sorts and values are written in
+/// SMT-LIB2 syntax.
+#[derive(Serialize, Deserialize)]
+struct ModelRepr {
+    #[serde(default)]
+    sorts: Vec<SortRepr>,
+    consts: Vec<ConstRepr>,
+    funcs: Vec<FuncRepr>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SortRepr {
+    name: String,
+    universe: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConstRepr {
+    name: String,
+    sort: String,
+    value: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FuncRepr {
+    name: String,
+    domain: Vec<String>,
+    range: String,
+    entries: Vec<EntryRepr>,
+    #[serde(rename = "else")]
+    else_value: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EntryRepr {
+    args: Vec<String>,
+    value: String,
+}
+
+impl<'ctx> Model<'ctx> {
+    fn to_repr(&self) -> ModelRepr {
+        let sorts = self
+            .sorts()
+            .iter()
+            .map(|sort| SortRepr {
+                name: sort.to_string(),
+                universe: self
+                    .universe(sort)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(Dynamic::to_string)
+                    .collect(),
+            })
+            .collect();
+        let mut consts = Vec::new();
+        let mut funcs = Vec::new();
+        for f in self {
+            if f.arity() == 0 {
+                let value = unsafe {
+                    Z3_model_get_const_interp(self.ctx.z3_ctx, self.z3_mdl, f.z3_func_decl)
+                };
+                if value.is_null() {
+                    continue;
+                }
+                consts.push(ConstRepr {
+                    name: f.name(),
+                    sort: f.range().to_string(),
+                    value: unsafe { Dynamic::wrap(self.ctx, value) }.to_string(),
+                });
+            } else {
+                let interp = unsafe {
+                    Z3_model_get_func_interp(self.ctx.z3_ctx, self.z3_mdl, f.z3_func_decl)
+                };
+                if interp.is_null() {
+                    continue;
+                }
+                let interp = unsafe { FuncInterp::wrap(self.ctx, interp) };
+                funcs.push(FuncRepr {
+                    name: f.name(),
+                    domain: f.domain().iter().map(Sort::to_string).collect(),
+                    range: f.range().to_string(),
+                    entries: interp
+                        .get_entries()
+                        .iter()
+                        .map(|e| EntryRepr {
+                            args: e.get_args().iter().map(Dynamic::to_string).collect(),
+                            value: e.get_value().to_string(),
+                        })
+                        .collect(),
+                    else_value: interp.get_else().to_string(),
+                });
+            }
+        }
+        ModelRepr {
+            sorts,
+            consts,
+            funcs,
+        }
+    }
+}
+
+/// Serializes the universes of the model's uninterpreted sorts and its
+/// constant and function interpretations, with the sorts of their
+/// declarations. Sorts and values are written as SMT-LIB2 strings:
+///
+/// ```json
+/// {
+///   "sorts": [{ "name": "A", "universe": ["A!val!0", "A!val!1"] }],
+///   "consts": [{ "name": "x", "sort": "Int", "value": "3" }],
+///   "funcs": [{
+///     "name": "f", "domain": ["Int"], "range": "Bool",
+///     "entries": [{ "args": ["3"], "value": "true" }], "else": "false"
+///   }]
+/// }
+/// ```
+///
+/// Use [`ModelSeed`] to deserialize it back into a context.
+impl Serialize for Model<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_repr().serialize(serializer)
+    }
+}
+
+/// Deserializes a [`Model`] serialized with `serde` into a context, adding
+/// the interpretations with [`Model::add_const_interp()`] and
+/// [`Model::add_func_interp()`].
+///
+/// Sorts and values are parsed as SMT-LIB2, so datatypes they refer to
+/// must be passed with [`ModelSeed::with_sorts()`]. Uninterpreted sorts
+/// with a universe in the serialized model are looked up by name among
+/// those sorts, or created, and their elements are added to the model as
+/// constants of the sort interpreted as themselves. Z3 cannot add universes
+/// to a model, though, so equalities between elements do not evaluate to
+/// `true` or `false` in the deserialized model, and [`Model::universe()`]
+/// returns `None` for its sorts.
+///
+/// # Examples
+/// ```
+/// # use z3::{ast::{Ast, Int}, Config, Context, ModelSeed, SatResult, Solver};
+/// use serde::de::DeserializeSeed;
+///
+/// # let cfg = Config::new();
+/// # let ctx = Context::new(&cfg);
+/// let solver = Solver::new(&ctx);
+/// let x = Int::new_const(&ctx, "x");
+/// solver.assert(&x._eq(&Int::from_i64(&ctx, 3)));
+/// assert_eq!(solver.check(), SatResult::Sat);
+/// let json = serde_json::to_string(&solver.get_model().unwrap()).unwrap();
+///
+/// let other = Context::new(&cfg);
+/// let mut deserializer = serde_json::Deserializer::from_str(&json);
+/// let model = ModelSeed::new(&other).deserialize(&mut deserializer).unwrap();
+/// assert_eq!(model.eval(&Int::new_const(&other, "x"), true).unwrap().as_i64(), Some(3));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ModelSeed<'a, 'ctx> {
+    ctx: &'ctx Context,
+    sorts: &'a [&'a Sort<'ctx>],
+}
+
+impl<'a, 'ctx> ModelSeed<'a, 'ctx> {
+    pub fn new(ctx: &'ctx Context) -> Self {
+        ModelSeed { ctx, sorts: &[] }
+    }
+
+    /// Make `sorts` available, under their own names, to the sorts and
+    /// values of the deserialized model.
+    pub fn with_sorts(self, sorts: &'a [&'a Sort<'ctx>]) -> Self {
+        ModelSeed { sorts, ..self }
+    }
+
+    fn parse_term(
+        &self,
+        text: &str,
+        sorts: &[&Sort<'ctx>],
+        decls: &[&FuncDecl<'ctx>],
+    ) -> Result<Dynamic<'ctx>, String> {
+        // Wrap the term in an assertion, the only thing the parser returns.
+        let source = format!("(assert (= {text} {text}))");
+        let assertions = parse_smtlib2_string(self.ctx, source, sorts, decls)
+            .map_err(|e| format!("cannot parse {text:?}: {e}"))?;
+        // Fold `(- 3)` and the like back into numerals.
+        Ok(assertions[0].children().remove(0).simplify())
+    }
+
+    fn parse_sort(&self, text: &str, sorts: &[&Sort<'ctx>]) -> Result<Sort<'ctx>, String> {
+        let source =
+            format!("(declare-const |z3.rs!sort| {text}) (assert (= |z3.rs!sort| |z3.rs!sort|))");
+        let assertions = parse_smtlib2_string(self.ctx, source, sorts, &[])
+            .map_err(|e| format!("cannot parse sort {text:?}: {e}"))?;
+        Ok(assertions[0].children()[0].get_sort())
+    }
+
+    fn symbol(&self, name: &str) -> Result<Z3_symbol, String> {
+        Symbol::String(name.to_owned())
+            .try_as_z3_symbol(self.ctx)
+            .map_err(|e| format!("bad name {name:?}: {e}"))
+    }
+
+    fn declare_sort(&self, name: &str) -> Result<Sort<'ctx>, String> {
+        let symbol = self.symbol(name)?;
+        Ok(unsafe { Sort::wrap(self.ctx, Z3_mk_uninterpreted_sort(self.ctx.z3_ctx, symbol)) })
+    }
+
+    fn declare_func(
+        &self,
+        name: &str,
+        domain: &[&Sort<'ctx>],
+        range: &Sort<'ctx>,
+    ) -> Result<FuncDecl<'ctx>, String> {
+        let symbol = self.symbol(name)?;
+        let domain: Vec<_> = domain.iter().map(|s| s.z3_sort).collect();
+        Ok(unsafe {
+            FuncDecl::wrap(
+                self.ctx,
+                Z3_mk_func_decl(
+                    self.ctx.z3_ctx,
+                    symbol,
+                    domain.len() as u32,
+                    domain.as_ptr(),
+                    range.z3_sort,
+                ),
+            )
+        })
+    }
+
+    fn build(&self, repr: &ModelRepr) -> Result<Model<'ctx>, String> {
+        let ctx = self.ctx;
+        let mut model = Model::new(ctx);
+
+        let mut sorts: Vec<Sort<'ctx>> = self.sorts.iter().map(|&s| s.clone()).collect();
+        let mut element_decls = Vec::new();
+        for s in &repr.sorts {
+            let sort = match sorts.iter().find(|sort| sort.to_string() == s.name) {
+                Some(sort) => sort.clone(),
+                None => {
+                    let sort = self.declare_sort(&s.name)?;
+                    sorts.push(sort.clone());
+                    sort
+                }
+            };
+            for element in &s.universe {
+                let decl = self.declare_func(element, &[], &sort)?;
+                // Keep model completion from giving elements other values.
+                model
+                    .add_const_interp(&decl, &decl.apply(&[]))
+                    .map_err(|e| e.to_string())?;
+                element_decls.push(decl);
+            }
+        }
+        let sorts: Vec<_> = sorts.iter().collect();
+
+        // Declare everything first, as values may refer to other
+        // declarations, e.g. through `as-array`.
+        let mut const_decls = Vec::with_capacity(repr.consts.len());
+        for c in &repr.consts {
+            let sort = self.parse_sort(&c.sort, &sorts)?;
+            const_decls.push(self.declare_func(&c.name, &[], &sort)?);
+        }
+        let mut func_decls = Vec::with_capacity(repr.funcs.len());
+        for f in &repr.funcs {
+            if f.domain.is_empty() {
+                return Err(format!("function {} has no arguments", f.name));
+            }
+            let domain = f
+                .domain
+                .iter()
+                .map(|s| self.parse_sort(s, &sorts))
+                .collect::<Result<Vec<_>, _>>()?;
+            let domain: Vec<_> = domain.iter().collect();
+            let range = self.parse_sort(&f.range, &sorts)?;
+            func_decls.push(self.declare_func(&f.name, &domain, &range)?);
+        }
+        let decls: Vec<_> = element_decls
+            .iter()
+            .chain(&const_decls)
+            .chain(&func_decls)
+            .collect();
+
+        for (c, decl) in repr.consts.iter().zip(&const_decls) {
+            let value = self.parse_term(&c.value, &sorts, &decls)?;
+            model
+                .add_const_interp(decl, &value)
+                .map_err(|e| format!("bad value for {}: {e}", c.name))?;
+        }
+        for (f, decl) in repr.funcs.iter().zip(&func_decls) {
+            let else_value = self.parse_term(&f.else_value, &sorts, &decls)?;
+            let interp = model
+                .add_func_interp(decl, &else_value)
+                .map_err(|e| format!("bad else value for {}: {e}", f.name))?;
+            for e in &f.entries {
+                let args = e
+                    .args
+                    .iter()
+                    .map(|a| self.parse_term(a, &sorts, &decls))
+                    .collect::<Result<Vec<_>, _>>()?;
+                interp.add_entry(&args, &self.parse_term(&e.value, &sorts, &decls)?);
+            }
+        }
+        Ok(model)
+    }
+}
+
+impl<'de, 'ctx> DeserializeSeed<'de> for ModelSeed<'_, 'ctx> {
+    type Value = Model<'ctx>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Model<'ctx>, D::Error> {
+        let repr = ModelRepr::deserialize(deserializer)?;
+        self.build(&repr).map_err(de::Error::custom)
+    }
+}
+
+/// Serializes the statistics as a map from keys to values.
+impl Serialize for Statistics<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let entries: Vec<_> = self.entries().collect();
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for e in &entries {
+            map.serialize_entry(&e.key, &e.value)?;
+        }
+        map.end()
+    }
+}
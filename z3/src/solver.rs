@@ -6,12 +6,19 @@ use z3_sys::*;
 
 use std::ops::AddAssign;
 
-use crate::{ast, ast::Ast, Context, Model, Params, SatResult, Solver, Statistics, Symbol};
+use crate::{
+    ast, ast::Ast, AstVector, Context, Error, Model, Params, SatResult, Solver, Statistics, Symbol,
+};
 
 impl<'ctx> Solver<'ctx> {
     pub(crate) unsafe fn wrap(ctx: &'ctx Context, z3_slv: Z3_solver) -> Solver<'ctx> {
         Z3_solver_inc_ref(ctx.z3_ctx, z3_slv);
-        Solver { ctx, z3_slv }
+        Solver {
+            ctx,
+            z3_slv,
+            #[cfg(feature = "user-propagator")]
+            propagator: None,
+        }
     }
 
     /// Create a new solver. This solver is a "combined solver"
@@ -51,6 +58,9 @@ impl<'ctx> Solver<'ctx> {
 
     /// Parse an SMT-LIB2 string with assertions, soft constraints and optimization objectives.
     /// Add the parsed constraints and objectives to the solver.
+    ///
+    /// Parse errors are silently ignored; use [`Solver::try_from_string()`]
+    /// to have them reported.
     pub fn from_string<T: Into<Vec<u8>>>(&self, source_string: T) {
         let source_cstring = CString::new(source_string).unwrap();
         unsafe {
@@ -58,6 +68,29 @@ impl<'ctx> Solver<'ctx> {
         }
     }
 
+    /// Parse an SMT-LIB2 string with assertions, soft constraints and optimization objectives.
+    /// Add the parsed constraints and objectives to the solver.
+    ///
+    /// Returns an [`Error`] if the input contains a NUL byte or Z3 fails to
+    /// parse it. Constraints parsed before the error was encountered may
+    /// still have been added.
+    ///
+    /// ```
+    /// # use z3::{Config, Context, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// assert!(solver.try_from_string("(declare-const x Int) (assert (> x 0))").is_ok());
+    /// assert!(solver.try_from_string("(assert (> y 0))").is_err());
+    /// ```
+    pub fn try_from_string<T: Into<Vec<u8>>>(&self, source_string: T) -> Result<(), Error> {
+        let source_cstring = CString::new(source_string).map_err(Error::from_nul)?;
+        unsafe {
+            Z3_solver_from_string(self.ctx.z3_ctx, self.z3_slv, source_cstring.as_ptr());
+        }
+        self.ctx.check_error()
+    }
+
     /// Create a new solver customized for the given logic.
     /// It returns `None` if the logic is unknown or unsupported.
     pub fn new_for_logic<S: Into<Symbol>>(ctx: &'ctx Context, logic: S) -> Option<Solver<'ctx>> {
@@ -109,6 +142,7 @@ impl<'ctx> Solver<'ctx> {
     /// - [`Solver::assert_and_track()`]
     pub fn assert(&self, ast: &ast::Bool<'ctx>) {
         debug!("assert: {:?}", ast);
+        assert_eq!(self.ctx, ast.ctx);
         unsafe { Z3_solver_assert(self.ctx.z3_ctx, self.z3_slv, ast.z3_ast) };
     }
 
@@ -128,6 +162,7 @@ impl<'ctx> Solver<'ctx> {
     /// - [`Solver::assert()`]
     pub fn assert_and_track(&self, ast: &ast::Bool<'ctx>, p: &ast::Bool<'ctx>) {
         debug!("assert_and_track: {:?}", ast);
+        assert!(self.ctx == ast.ctx && self.ctx == p.ctx);
         unsafe { Z3_solver_assert_and_track(self.ctx.z3_ctx, self.z3_slv, ast.z3_ast, p.z3_ast) };
     }
 
@@ -160,12 +195,10 @@ impl<'ctx> Solver<'ctx> {
     /// [model construction is enabled]: crate::Config::set_model_generation
     /// [proof generation was enabled]: crate::Config::set_proof_generation
     pub fn check(&self) -> SatResult {
-        match unsafe { Z3_solver_check(self.ctx.z3_ctx, self.z3_slv) } {
-            Z3_L_FALSE => SatResult::Unsat,
-            Z3_L_UNDEF => SatResult::Unknown,
-            Z3_L_TRUE => SatResult::Sat,
-            _ => unreachable!(),
-        }
+        let result = unsafe { Z3_solver_check(self.ctx.z3_ctx, self.z3_slv) };
+        #[cfg(feature = "user-propagator")]
+        self.resume_propagator_panic();
+        SatResult::from_lbool(result)
     }
 
     /// Check whether the assertions in the given solver and
@@ -179,27 +212,24 @@ impl<'ctx> Solver<'ctx> {
     ///
     /// - [`Solver::check()`]
     pub fn check_assumptions(&self, assumptions: &[ast::Bool<'ctx>]) -> SatResult {
+        assert!(assumptions.iter().all(|a| a.ctx == self.ctx));
         let a: Vec<Z3_ast> = assumptions.iter().map(|a| a.z3_ast).collect();
-        match unsafe {
+        let result = unsafe {
             Z3_solver_check_assumptions(self.ctx.z3_ctx, self.z3_slv, a.len() as u32, a.as_ptr())
-        } {
-            Z3_L_FALSE => SatResult::Unsat,
-            Z3_L_UNDEF => SatResult::Unknown,
-            Z3_L_TRUE => SatResult::Sat,
-            _ => unreachable!(),
-        }
+        };
+        #[cfg(feature = "user-propagator")]
+        self.resume_propagator_panic();
+        SatResult::from_lbool(result)
     }
 
     // Return a vector of assumptions in the solver.
-    pub fn get_assertions(&self) -> Vec<ast::Bool<'ctx>> {
-        let z3_vec = unsafe { Z3_solver_get_assertions(self.ctx.z3_ctx, self.z3_slv) };
-
-        (0..unsafe { Z3_ast_vector_size(self.ctx.z3_ctx, z3_vec) })
-            .map(|i| unsafe {
-                let z3_ast = Z3_ast_vector_get(self.ctx.z3_ctx, z3_vec, i);
-                ast::Bool::wrap(self.ctx, z3_ast)
-            })
-            .collect()
+    pub fn get_assertions(&self) -> AstVector<'ctx, ast::Bool<'ctx>> {
+        unsafe {
+            AstVector::wrap(
+                self.ctx,
+                Z3_solver_get_assertions(self.ctx.z3_ctx, self.z3_slv),
+            )
+        }
     }
 
     /// Return a subset of the assumptions provided to either the last
@@ -223,23 +253,13 @@ impl<'ctx> Solver<'ctx> {
     ///
     /// - [`Solver::check_assumptions`]
     /// - [`Solver::assert_and_track`]
-    pub fn get_unsat_core(&self) -> Vec<ast::Bool<'ctx>> {
+    pub fn get_unsat_core(&self) -> AstVector<'ctx, ast::Bool<'ctx>> {
         let z3_unsat_core = unsafe { Z3_solver_get_unsat_core(self.ctx.z3_ctx, self.z3_slv) };
         if z3_unsat_core.is_null() {
-            return vec![];
+            return AstVector::new(self.ctx);
         }
 
-        let len = unsafe { Z3_ast_vector_size(self.ctx.z3_ctx, z3_unsat_core) };
-
-        let mut unsat_core = Vec::with_capacity(len as usize);
-
-        for i in 0..len {
-            let elem = unsafe { Z3_ast_vector_get(self.ctx.z3_ctx, z3_unsat_core, i) };
-            let elem = unsafe { ast::Bool::wrap(self.ctx, elem) };
-            unsat_core.push(elem);
-        }
-
-        unsat_core
+        unsafe { AstVector::wrap(self.ctx, z3_unsat_core) }
     }
 
     /// Retrieve consequences from the solver given a set of assumptions.
@@ -247,36 +267,20 @@ impl<'ctx> Solver<'ctx> {
         &self,
         assumptions: &[ast::Bool<'ctx>],
         variables: &[ast::Bool<'ctx>],
-    ) -> Vec<ast::Bool<'ctx>> {
+    ) -> AstVector<'ctx, ast::Bool<'ctx>> {
+        let assumptions = AstVector::from_slice(self.ctx, assumptions);
+        let variables = AstVector::from_slice(self.ctx, variables);
+        let consequences = AstVector::new(self.ctx);
         unsafe {
-            let _assumptions = Z3_mk_ast_vector(self.ctx.z3_ctx);
-            Z3_ast_vector_inc_ref(self.ctx.z3_ctx, _assumptions);
-            assumptions.iter().for_each(|x| {
-                Z3_ast_vector_push(self.ctx.z3_ctx, _assumptions, x.z3_ast);
-            });
-
-            let _variables = Z3_mk_ast_vector(self.ctx.z3_ctx);
-            Z3_ast_vector_inc_ref(self.ctx.z3_ctx, _variables);
-            variables.iter().for_each(|x| {
-                Z3_ast_vector_push(self.ctx.z3_ctx, _variables, x.z3_ast);
-            });
-            let consequences = Z3_mk_ast_vector(self.ctx.z3_ctx);
-            Z3_ast_vector_inc_ref(self.ctx.z3_ctx, consequences);
-
             Z3_solver_get_consequences(
                 self.ctx.z3_ctx,
                 self.z3_slv,
-                _assumptions,
-                _variables,
-                consequences,
+                assumptions.z3_vec,
+                variables.z3_vec,
+                consequences.z3_vec,
             );
-            let mut cons = vec![];
-            for i in 0..Z3_ast_vector_size(self.ctx.z3_ctx, consequences) {
-                let val = Z3_ast_vector_get(self.ctx.z3_ctx, consequences, i);
-                cons.push(ast::Bool::wrap(self.ctx, val));
-            }
-            cons
         }
+        consequences
     }
 
     /// Create a backtracking point.
@@ -347,11 +351,71 @@ impl<'ctx> Solver<'ctx> {
             .map(|s| s.to_string())
     }
 
+    /// Enumerate the models of the current assertions that differ on the
+    /// values of `terms`.
+    ///
+    /// Each time a model is found, a blocking clause stating that at least
+    /// one of `terms` must take a different value is asserted, so every
+    /// yielded model assigns a distinct tuple of values to `terms`. Use
+    /// [`Model::eval()`] with model completion to read those values.
+    ///
+    /// The blocking clauses are asserted inside a fresh backtracking point,
+    /// which is popped once the iteration stops or the iterator is dropped,
+    /// restoring the solver to its previous state.
+    ///
+    /// The iteration stops as soon as [`Solver::check()`] returns something
+    /// other than [`SatResult::Sat`]; see [`Solutions::result()`] and
+    /// [`Solutions::reason_unknown()`] to tell whether all solutions were
+    /// enumerated. It also stops, with [`SatResult::Unknown`], if one of
+    /// `terms` cannot be evaluated in a model.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{ast::{Ast, Int}, Config, Context, SatResult, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let x = Int::new_const(&ctx, "x");
+    /// solver.assert(&x.ge(&Int::from_i64(&ctx, 1)));
+    /// solver.assert(&x.le(&Int::from_i64(&ctx, 3)));
+    ///
+    /// let mut solutions = solver.iter_solutions(&[x.clone().into()]);
+    /// let mut values: Vec<i64> = solutions
+    ///     .by_ref()
+    ///     .map(|model| model.eval(&x, true).unwrap().as_i64().unwrap())
+    ///     .collect();
+    /// values.sort();
+    /// assert_eq!(values, vec![1, 2, 3]);
+    /// assert_eq!(solutions.result(), Some(SatResult::Unsat));
+    /// drop(solutions);
+    ///
+    /// // The blocking clauses are gone again.
+    /// assert_eq!(solver.get_assertions().len(), 2);
+    /// ```
+    pub fn iter_solutions<'a>(&'a self, terms: &[ast::Dynamic<'ctx>]) -> Solutions<'a, 'ctx> {
+        self.push();
+        Solutions {
+            solver: self,
+            terms: terms.to_vec(),
+            result: None,
+            reason_unknown: None,
+        }
+    }
+
     /// Set the current solver using the given parameters.
     pub fn set_params(&self, params: &Params<'ctx>) {
         unsafe { Z3_solver_set_params(self.ctx.z3_ctx, self.z3_slv, params.z3_params) };
     }
 
+    /// Set the current solver using the given parameters.
+    ///
+    /// Returns an [`Error`] if Z3 rejects the parameters, for example
+    /// because one of them is not known to this solver.
+    pub fn try_set_params(&self, params: &Params<'ctx>) -> Result<(), Error> {
+        unsafe { Z3_solver_set_params(self.ctx.z3_ctx, self.z3_slv, params.z3_params) };
+        self.ctx.check_error()
+    }
+
     /// Retrieve the statistics for the last [`Solver::check()`].
     pub fn get_statistics(&self) -> Statistics<'ctx> {
         unsafe {
@@ -367,7 +431,7 @@ impl<'ctx> Solver<'ctx> {
         let logic = CString::new("").unwrap();
         let status = CString::new("unknown").unwrap();
         let attributes = CString::new("").unwrap();
-        let assumptions = self.get_assertions();
+        let assumptions = self.get_assertions().to_vec();
         let mut num_assumptions = assumptions.len() as u32;
         let formula = if num_assumptions > 0 {
             num_assumptions -= 1;
@@ -400,6 +464,103 @@ impl<'ctx> Solver<'ctx> {
     }
 }
 
+/// Iterator over the solutions of a [`Solver`].
+///
+/// # See also:
+///
+/// - [`Solver::iter_solutions()`]
+#[derive(Debug)]
+pub struct Solutions<'a, 'ctx> {
+    solver: &'a Solver<'ctx>,
+    terms: Vec<ast::Dynamic<'ctx>>,
+    result: Option<SatResult>,
+    reason_unknown: Option<String>,
+}
+
+impl SatResult {
+    pub(crate) fn from_lbool(result: Z3_lbool) -> SatResult {
+        match result {
+            Z3_L_FALSE => SatResult::Unsat,
+            Z3_L_UNDEF => SatResult::Unknown,
+            Z3_L_TRUE => SatResult::Sat,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a, 'ctx> Solutions<'a, 'ctx> {
+    /// Return the result of the check that ended the iteration, or `None`
+    /// if the iteration has not ended yet.
+    ///
+    /// [`SatResult::Unsat`] means that all solutions were enumerated,
+    /// while [`SatResult::Unknown`] means that the solver gave up; see
+    /// [`Solutions::reason_unknown()`].
+    pub fn result(&self) -> Option<SatResult> {
+        self.result
+    }
+
+    /// Return the solver's justification if the iteration ended with
+    /// [`SatResult::Unknown`].
+    pub fn reason_unknown(&self) -> Option<&str> {
+        self.reason_unknown.as_deref()
+    }
+
+    fn finish(&mut self, result: SatResult) {
+        if result == SatResult::Unknown {
+            self.reason_unknown = self.solver.get_reason_unknown();
+        }
+        self.result = Some(result);
+        self.solver.pop(1);
+    }
+}
+
+impl<'a, 'ctx> Iterator for Solutions<'a, 'ctx> {
+    type Item = Model<'ctx>;
+
+    fn next(&mut self) -> Option<Model<'ctx>> {
+        if self.result.is_some() {
+            return None;
+        }
+        let model = match self.solver.check() {
+            SatResult::Sat => self.solver.get_model(),
+            result => {
+                self.finish(result);
+                return None;
+            }
+        };
+        let model = match model {
+            Some(model) => model,
+            None => {
+                self.finish(SatResult::Unknown);
+                return None;
+            }
+        };
+        let mut differs = Vec::with_capacity(self.terms.len());
+        for term in &self.terms {
+            // Leaving a term out would block other solutions as well.
+            match model.eval(term, true) {
+                Some(value) => differs.push(term._eq(&value).not()),
+                None => {
+                    self.finish(SatResult::Unknown);
+                    return None;
+                }
+            }
+        }
+        let differs: Vec<&ast::Bool<'ctx>> = differs.iter().collect();
+        self.solver
+            .assert(&ast::Bool::or(self.solver.ctx, &differs));
+        Some(model)
+    }
+}
+
+impl Drop for Solutions<'_, '_> {
+    fn drop(&mut self) {
+        if self.result.is_none() {
+            self.solver.pop(1);
+        }
+    }
+}
+
 impl fmt::Display for Solver<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         let p = unsafe { Z3_solver_to_string(self.ctx.z3_ctx, self.z3_slv) };
@@ -431,7 +592,7 @@ impl<'ctx> Clone for Solver<'ctx> {
         let new_solver = Solver::new(self.ctx);
 
         self.get_assertions().iter().for_each(|a| {
-            new_solver.assert(a);
+            new_solver.assert(&a);
         });
 
         new_solver
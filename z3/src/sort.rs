@@ -49,10 +49,23 @@ impl<'ctx> Sort<'ctx> {
         unsafe { Self::wrap(ctx, Z3_mk_fpa_sort(ctx.z3_ctx, 11, 53)) }
     }
 
+    /// Create the IEEE-754 rounding mode sort.
+    pub fn rounding_mode(ctx: &'ctx Context) -> Sort<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_fpa_rounding_mode_sort(ctx.z3_ctx)) }
+    }
+
     pub fn string(ctx: &'ctx Context) -> Sort<'ctx> {
         unsafe { Self::wrap(ctx, Z3_mk_string_sort(ctx.z3_ctx)) }
     }
 
+    /// Create the Unicode character sort.
+    ///
+    /// Requires the `unicode` feature.
+    #[cfg(feature = "unicode")]
+    pub fn char(ctx: &'ctx Context) -> Sort<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_char_sort(ctx.z3_ctx)) }
+    }
+
     pub fn bitvector(ctx: &'ctx Context, sz: u32) -> Sort<'ctx> {
         unsafe { Self::wrap(ctx, Z3_mk_bv_sort(ctx.z3_ctx, sz as ::std::os::raw::c_uint)) }
     }
@@ -74,6 +87,11 @@ impl<'ctx> Sort<'ctx> {
         unsafe { Self::wrap(ctx, Z3_mk_seq_sort(ctx.z3_ctx, elt.z3_sort)) }
     }
 
+    /// Create the sort of regular expressions over the sequence sort `seq`.
+    pub fn regexp(ctx: &'ctx Context, seq: &Sort<'ctx>) -> Sort<'ctx> {
+        unsafe { Self::wrap(ctx, Z3_mk_re_sort(ctx.z3_ctx, seq.z3_sort)) }
+    }
+
     /// Create an enumeration sort.
     ///
     /// Creates a Z3 enumeration sort with the given `name`.
@@ -323,11 +341,11 @@ impl<'ctx> SortDiffers<'ctx> {
         Self { left, right }
     }
 
-    pub fn left(&self) -> &Sort {
+    pub fn left(&self) -> &Sort<'ctx> {
         &self.left
     }
 
-    pub fn right(&self) -> &Sort {
+    pub fn right(&self) -> &Sort<'ctx> {
         &self.right
     }
 }
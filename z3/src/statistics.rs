@@ -12,6 +12,11 @@ use crate::{Context, Statistics};
 /// - [`StatisticsEntry`]
 /// - [`Statistics::value`]
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
 pub enum StatisticsValue {
     UInt(u32),
     Double(f64),
@@ -23,6 +28,7 @@ pub enum StatisticsValue {
 ///
 /// - [`Statistics::entries`]
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatisticsEntry {
     pub key: String,
     pub value: StatisticsValue,
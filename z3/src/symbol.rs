@@ -1,8 +1,8 @@
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 
 use z3_sys::*;
 
-use crate::{Context, Symbol};
+use crate::{Context, Error, Symbol};
 
 impl Symbol {
     pub fn as_z3_symbol(&self, ctx: &Context) -> Z3_symbol {
@@ -17,6 +17,32 @@ impl Symbol {
     }
 }
 
+impl Symbol {
+    /// Fallible version of [`Symbol::as_z3_symbol()`], returning an
+    /// [`Error`] if a string symbol contains a NUL byte.
+    pub(crate) fn try_as_z3_symbol(&self, ctx: &Context) -> Result<Z3_symbol, Error> {
+        match self {
+            Symbol::Int(_) => Ok(self.as_z3_symbol(ctx)),
+            Symbol::String(s) => {
+                let ss = CString::new(s.clone()).map_err(Error::from_nul)?;
+                Ok(unsafe { Z3_mk_string_symbol(ctx.z3_ctx, ss.as_ptr()) })
+            }
+        }
+    }
+
+    /// Convert a raw [`Z3_symbol`] into a `Symbol`.
+    pub(crate) unsafe fn from_z3_symbol(ctx: &Context, z3_symbol: Z3_symbol) -> Symbol {
+        match Z3_get_symbol_kind(ctx.z3_ctx, z3_symbol) {
+            SymbolKind::Int => Symbol::Int(Z3_get_symbol_int(ctx.z3_ctx, z3_symbol) as u32),
+            SymbolKind::String => Symbol::String(
+                CStr::from_ptr(Z3_get_symbol_string(ctx.z3_ctx, z3_symbol))
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+        }
+    }
+}
+
 impl From<u32> for Symbol {
     fn from(val: u32) -> Self {
         Symbol::Int(val)
@@ -8,7 +8,7 @@ use std::time::Duration;
 
 use z3_sys::*;
 
-use crate::{ApplyResult, Context, Goal, Params, Probe, Solver, Tactic};
+use crate::{ApplyResult, Context, Error, Goal, Params, Probe, Solver, Tactic};
 
 impl<'ctx> ApplyResult<'ctx> {
     unsafe fn wrap(ctx: &'ctx Context, z3_apply_result: Z3_apply_result) -> ApplyResult<'ctx> {
@@ -82,9 +82,35 @@ impl<'ctx> Tactic<'ctx> {
     /// # See also
     ///
     /// - [`Tactic::list_all()`]
+    /// - [`Tactic::try_new()`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a known tactic.
     pub fn new(ctx: &'ctx Context, name: &str) -> Tactic<'ctx> {
-        let tactic_name = CString::new(name).unwrap();
-        unsafe { Self::wrap(ctx, Z3_mk_tactic(ctx.z3_ctx, tactic_name.as_ptr())) }
+        Self::try_new(ctx, name).unwrap()
+    }
+
+    /// Create a tactic by name, returning an [`Error`] if no tactic
+    /// with this name exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use z3::{Config, Context, Tactic};
+    ///
+    /// let cfg = Config::new();
+    /// let ctx = Context::new(&cfg);
+    /// assert!(Tactic::try_new(&ctx, "simplify").is_ok());
+    /// assert!(Tactic::try_new(&ctx, "no-such-tactic").is_err());
+    /// ```
+    pub fn try_new(ctx: &'ctx Context, name: &str) -> Result<Tactic<'ctx>, Error> {
+        let tactic_name = CString::new(name).map_err(Error::from_nul)?;
+        unsafe {
+            let tactic = Z3_mk_tactic(ctx.z3_ctx, tactic_name.as_ptr());
+            ctx.check_error()?;
+            Ok(Self::wrap(ctx, tactic))
+        }
     }
 
     /// Return a tactic that just return the given goal.
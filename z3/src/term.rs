@@ -0,0 +1,316 @@
+use z3_sys::*;
+
+use crate::ast::{Array, Ast, Bool, Dynamic, Int, Quantifier, Real, BV};
+use crate::FuncDecl;
+
+/// A typed view of the top-level operator of an AST and its arguments,
+/// as returned by [`Ast::view()`].
+///
+/// Arguments are converted to the AST type the operator requires. Where an
+/// operator accepts several sorts, such as the arithmetic operators which
+/// work on both `Int` and `Real`, arguments are left as [`Dynamic`].
+///
+/// Operators without a dedicated variant are reported as [`Term::Other`];
+/// use [`Ast::decl()`] and [`Ast::children()`] to inspect them.
+///
+/// # Examples
+/// ```
+/// # use z3::{ast::{Ast, BV, Int}, Config, Context, Term};
+/// # let cfg = Config::new();
+/// # let ctx = Context::new(&cfg);
+/// let x = Int::new_const(&ctx, "x");
+/// let sum = &x + Int::from_i64(&ctx, 1);
+/// match sum.view() {
+///     Term::Add(args) => assert_eq!(args.len(), 2),
+///     other => panic!("unexpected term {:?}", other),
+/// }
+///
+/// let y = BV::new_const(&ctx, "y", 32);
+/// match y.extract(15, 8).view() {
+///     Term::BvExtract { hi, lo, arg } => {
+///         assert_eq!((hi, lo), (15, 8));
+///         assert_eq!(arg, y);
+///     }
+///     other => panic!("unexpected term {:?}", other),
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Term<'ctx> {
+    /// A numeral of any sort, such as an integer, real or bit-vector literal.
+    Numeral(Dynamic<'ctx>),
+    /// A bound variable, given by its de Bruijn index.
+    Var(u32),
+    /// A quantifier or lambda.
+    Quantifier(Quantifier<'ctx>),
+    /// An uninterpreted constant (if `args` is empty) or function application.
+    Uninterpreted {
+        decl: FuncDecl<'ctx>,
+        args: Vec<Dynamic<'ctx>>,
+    },
+
+    // Propositional logic and equality
+    True,
+    False,
+    Eq(Dynamic<'ctx>, Dynamic<'ctx>),
+    Distinct(Vec<Dynamic<'ctx>>),
+    Ite(Bool<'ctx>, Dynamic<'ctx>, Dynamic<'ctx>),
+    And(Vec<Bool<'ctx>>),
+    Or(Vec<Bool<'ctx>>),
+    Not(Bool<'ctx>),
+    Implies(Bool<'ctx>, Bool<'ctx>),
+    Iff(Bool<'ctx>, Bool<'ctx>),
+    Xor(Bool<'ctx>, Bool<'ctx>),
+    /// Pseudo-Boolean `at-most`: at most `k` of `args` are true.
+    AtMost {
+        k: u32,
+        args: Vec<Bool<'ctx>>,
+    },
+    /// Pseudo-Boolean `at-least`: at least `k` of `args` are true.
+    AtLeast {
+        k: u32,
+        args: Vec<Bool<'ctx>>,
+    },
+
+    // Arithmetic, over `Int` or `Real`
+    Le(Dynamic<'ctx>, Dynamic<'ctx>),
+    Ge(Dynamic<'ctx>, Dynamic<'ctx>),
+    Lt(Dynamic<'ctx>, Dynamic<'ctx>),
+    Gt(Dynamic<'ctx>, Dynamic<'ctx>),
+    Add(Vec<Dynamic<'ctx>>),
+    Sub(Vec<Dynamic<'ctx>>),
+    Mul(Vec<Dynamic<'ctx>>),
+    Neg(Dynamic<'ctx>),
+    Power(Dynamic<'ctx>, Dynamic<'ctx>),
+    Div(Real<'ctx>, Real<'ctx>),
+    IDiv(Int<'ctx>, Int<'ctx>),
+    Rem(Int<'ctx>, Int<'ctx>),
+    Mod(Int<'ctx>, Int<'ctx>),
+    ToReal(Int<'ctx>),
+    ToInt(Real<'ctx>),
+    IsInt(Real<'ctx>),
+
+    // Arrays
+    Select(Array<'ctx>, Dynamic<'ctx>),
+    Store(Array<'ctx>, Dynamic<'ctx>, Dynamic<'ctx>),
+    /// A constant array, mapping every index to the given value.
+    ConstArray(Dynamic<'ctx>),
+    ArrayDefault(Array<'ctx>),
+
+    // Bit-vectors
+    BvNeg(BV<'ctx>),
+    BvAdd(Vec<BV<'ctx>>),
+    BvSub(BV<'ctx>, BV<'ctx>),
+    BvMul(Vec<BV<'ctx>>),
+    BvSDiv(BV<'ctx>, BV<'ctx>),
+    BvUDiv(BV<'ctx>, BV<'ctx>),
+    BvSRem(BV<'ctx>, BV<'ctx>),
+    BvURem(BV<'ctx>, BV<'ctx>),
+    BvSMod(BV<'ctx>, BV<'ctx>),
+    BvULe(BV<'ctx>, BV<'ctx>),
+    BvSLe(BV<'ctx>, BV<'ctx>),
+    BvUGe(BV<'ctx>, BV<'ctx>),
+    BvSGe(BV<'ctx>, BV<'ctx>),
+    BvULt(BV<'ctx>, BV<'ctx>),
+    BvSLt(BV<'ctx>, BV<'ctx>),
+    BvUGt(BV<'ctx>, BV<'ctx>),
+    BvSGt(BV<'ctx>, BV<'ctx>),
+    BvAnd(Vec<BV<'ctx>>),
+    BvOr(Vec<BV<'ctx>>),
+    BvXor(Vec<BV<'ctx>>),
+    BvNot(BV<'ctx>),
+    BvNand(BV<'ctx>, BV<'ctx>),
+    BvNor(BV<'ctx>, BV<'ctx>),
+    BvXnor(BV<'ctx>, BV<'ctx>),
+    BvShl(BV<'ctx>, BV<'ctx>),
+    BvLShr(BV<'ctx>, BV<'ctx>),
+    BvAShr(BV<'ctx>, BV<'ctx>),
+    BvConcat(Vec<BV<'ctx>>),
+    /// Bits `hi` down to `lo` (inclusive) of `arg`.
+    BvExtract {
+        hi: u32,
+        lo: u32,
+        arg: BV<'ctx>,
+    },
+    /// `arg` extended with `bits` copies of its sign bit.
+    BvSignExt {
+        bits: u32,
+        arg: BV<'ctx>,
+    },
+    /// `arg` extended with `bits` zero bits.
+    BvZeroExt {
+        bits: u32,
+        arg: BV<'ctx>,
+    },
+    /// `arg` concatenated with itself `count` times.
+    BvRepeat {
+        count: u32,
+        arg: BV<'ctx>,
+    },
+    BvRotateLeft {
+        bits: u32,
+        arg: BV<'ctx>,
+    },
+    BvRotateRight {
+        bits: u32,
+        arg: BV<'ctx>,
+    },
+    /// Conversion of a bit-vector to a non-negative integer.
+    BvToInt(BV<'ctx>),
+    /// Conversion of an integer to a bit-vector of width `bits`.
+    IntToBv {
+        bits: u32,
+        arg: Int<'ctx>,
+    },
+
+    // Datatypes
+    DtConstructor {
+        decl: FuncDecl<'ctx>,
+        args: Vec<Dynamic<'ctx>>,
+    },
+    DtRecognizer {
+        decl: FuncDecl<'ctx>,
+        arg: Dynamic<'ctx>,
+    },
+    DtAccessor {
+        decl: FuncDecl<'ctx>,
+        arg: Dynamic<'ctx>,
+    },
+
+    /// Any other term.
+    Other(Dynamic<'ctx>),
+}
+
+impl<'ctx> Term<'ctx> {
+    pub(crate) fn of(ast: &Dynamic<'ctx>) -> Term<'ctx> {
+        match ast.kind() {
+            AstKind::Numeral => return Term::Numeral(ast.clone()),
+            AstKind::Var => return Term::Var(ast.bound_index().unwrap()),
+            AstKind::Quantifier => return Term::Quantifier(ast.as_quantifier().unwrap()),
+            AstKind::App => {}
+            _ => return Term::Other(ast.clone()),
+        }
+
+        let decl = ast.decl();
+        let args = ast.children();
+        let param = |i| decl.int_parameter(i).unwrap() as u32;
+        let any = |i: usize| args[i].clone();
+        let bool = |i: usize| args[i].as_bool().unwrap();
+        let int = |i: usize| args[i].as_int().unwrap();
+        let real = |i: usize| args[i].as_real().unwrap();
+        let bv = |i: usize| args[i].as_bv().unwrap();
+        let array = |i: usize| args[i].as_array().unwrap();
+        let bools = || args.iter().map(|a| a.as_bool().unwrap()).collect();
+        let bvs = || args.iter().map(|a| a.as_bv().unwrap()).collect();
+
+        match decl.kind() {
+            DeclKind::UNINTERPRETED => Term::Uninterpreted { decl, args },
+
+            DeclKind::TRUE => Term::True,
+            DeclKind::FALSE => Term::False,
+            DeclKind::EQ => Term::Eq(any(0), any(1)),
+            DeclKind::DISTINCT => Term::Distinct(args),
+            DeclKind::ITE => Term::Ite(bool(0), any(1), any(2)),
+            DeclKind::AND => Term::And(bools()),
+            DeclKind::OR => Term::Or(bools()),
+            DeclKind::NOT => Term::Not(bool(0)),
+            DeclKind::IMPLIES => Term::Implies(bool(0), bool(1)),
+            DeclKind::IFF => Term::Iff(bool(0), bool(1)),
+            DeclKind::XOR => Term::Xor(bool(0), bool(1)),
+            DeclKind::PB_AT_MOST => Term::AtMost {
+                k: param(0),
+                args: bools(),
+            },
+            DeclKind::PB_AT_LEAST => Term::AtLeast {
+                k: param(0),
+                args: bools(),
+            },
+
+            DeclKind::LE => Term::Le(any(0), any(1)),
+            DeclKind::GE => Term::Ge(any(0), any(1)),
+            DeclKind::LT => Term::Lt(any(0), any(1)),
+            DeclKind::GT => Term::Gt(any(0), any(1)),
+            DeclKind::ADD => Term::Add(args),
+            DeclKind::SUB => Term::Sub(args),
+            DeclKind::MUL => Term::Mul(args),
+            DeclKind::UMINUS => Term::Neg(any(0)),
+            DeclKind::POWER => Term::Power(any(0), any(1)),
+            DeclKind::DIV => Term::Div(real(0), real(1)),
+            DeclKind::IDIV => Term::IDiv(int(0), int(1)),
+            DeclKind::REM => Term::Rem(int(0), int(1)),
+            DeclKind::MOD => Term::Mod(int(0), int(1)),
+            DeclKind::TO_REAL => Term::ToReal(int(0)),
+            DeclKind::TO_INT => Term::ToInt(real(0)),
+            DeclKind::IS_INT => Term::IsInt(real(0)),
+
+            DeclKind::SELECT if args.len() == 2 => Term::Select(array(0), any(1)),
+            DeclKind::STORE if args.len() == 3 => Term::Store(array(0), any(1), any(2)),
+            DeclKind::CONST_ARRAY => Term::ConstArray(any(0)),
+            DeclKind::ARRAY_DEFAULT => Term::ArrayDefault(array(0)),
+
+            DeclKind::BNEG => Term::BvNeg(bv(0)),
+            DeclKind::BADD => Term::BvAdd(bvs()),
+            DeclKind::BSUB => Term::BvSub(bv(0), bv(1)),
+            DeclKind::BMUL => Term::BvMul(bvs()),
+            DeclKind::BSDIV | DeclKind::BSDIV_I => Term::BvSDiv(bv(0), bv(1)),
+            DeclKind::BUDIV | DeclKind::BUDIV_I => Term::BvUDiv(bv(0), bv(1)),
+            DeclKind::BSREM | DeclKind::BSREM_I => Term::BvSRem(bv(0), bv(1)),
+            DeclKind::BUREM | DeclKind::BUREM_I => Term::BvURem(bv(0), bv(1)),
+            DeclKind::BSMOD | DeclKind::BSMOD_I => Term::BvSMod(bv(0), bv(1)),
+            DeclKind::ULEQ => Term::BvULe(bv(0), bv(1)),
+            DeclKind::SLEQ => Term::BvSLe(bv(0), bv(1)),
+            DeclKind::UGEQ => Term::BvUGe(bv(0), bv(1)),
+            DeclKind::SGEQ => Term::BvSGe(bv(0), bv(1)),
+            DeclKind::ULT => Term::BvULt(bv(0), bv(1)),
+            DeclKind::SLT => Term::BvSLt(bv(0), bv(1)),
+            DeclKind::UGT => Term::BvUGt(bv(0), bv(1)),
+            DeclKind::SGT => Term::BvSGt(bv(0), bv(1)),
+            DeclKind::BAND => Term::BvAnd(bvs()),
+            DeclKind::BOR => Term::BvOr(bvs()),
+            DeclKind::BXOR => Term::BvXor(bvs()),
+            DeclKind::BNOT => Term::BvNot(bv(0)),
+            DeclKind::BNAND => Term::BvNand(bv(0), bv(1)),
+            DeclKind::BNOR => Term::BvNor(bv(0), bv(1)),
+            DeclKind::BXNOR => Term::BvXnor(bv(0), bv(1)),
+            DeclKind::BSHL => Term::BvShl(bv(0), bv(1)),
+            DeclKind::BLSHR => Term::BvLShr(bv(0), bv(1)),
+            DeclKind::BASHR => Term::BvAShr(bv(0), bv(1)),
+            DeclKind::CONCAT => Term::BvConcat(bvs()),
+            DeclKind::EXTRACT => Term::BvExtract {
+                hi: param(0),
+                lo: param(1),
+                arg: bv(0),
+            },
+            DeclKind::SIGN_EXT => Term::BvSignExt {
+                bits: param(0),
+                arg: bv(0),
+            },
+            DeclKind::ZERO_EXT => Term::BvZeroExt {
+                bits: param(0),
+                arg: bv(0),
+            },
+            DeclKind::REPEAT => Term::BvRepeat {
+                count: param(0),
+                arg: bv(0),
+            },
+            DeclKind::ROTATE_LEFT => Term::BvRotateLeft {
+                bits: param(0),
+                arg: bv(0),
+            },
+            DeclKind::ROTATE_RIGHT => Term::BvRotateRight {
+                bits: param(0),
+                arg: bv(0),
+            },
+            DeclKind::BV2INT => Term::BvToInt(bv(0)),
+            DeclKind::INT2BV => Term::IntToBv {
+                bits: param(0),
+                arg: int(0),
+            },
+
+            DeclKind::DT_CONSTRUCTOR => Term::DtConstructor { decl, args },
+            DeclKind::DT_RECOGNISER | DeclKind::DT_IS => Term::DtRecognizer { arg: any(0), decl },
+            DeclKind::DT_ACCESSOR => Term::DtAccessor { arg: any(0), decl },
+
+            _ => Term::Other(ast.clone()),
+        }
+    }
+}
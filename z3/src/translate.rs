@@ -0,0 +1,252 @@
+use z3_sys::*;
+
+#[cfg(feature = "unicode")]
+use crate::ast::Char;
+use crate::ast::{
+    Array, Ast, Bool, Datatype, Dynamic, Float, Int, Quantifier, Real, Regexp, RoundingMode, Seq,
+    Set, String, BV,
+};
+use crate::{AstVector, Context, FuncDecl, Goal, Model, Solver, Sort};
+
+/// Values that can be copied from one [`Context`] to another, such as
+/// ASTs, sorts, function declarations, solvers and models, and `Vec`s,
+/// `Option`s and tuples of them.
+///
+/// Translating a bundle of values at once keeps them together: the
+/// translated solver and terms refer to the same declarations in the
+/// destination context.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `z3`.
+///
+/// # Examples
+/// ```
+/// # use z3::{ast::{Ast, Int}, Config, Context, SatResult, Solver, Translate};
+/// let cfg = Config::new();
+/// let ctx = Context::new(&cfg);
+/// let x = Int::new_const(&ctx, "x");
+/// let solver = Solver::new(&ctx);
+/// solver.assert(&x.gt(&Int::from_i64(&ctx, 2)));
+///
+/// let other = Context::new(&cfg);
+/// let (solver, x) = (solver, x).translate_to(&other);
+/// assert_eq!(solver.check(), SatResult::Sat);
+/// let model = solver.get_model().unwrap();
+/// assert!(model.eval(&x, true).unwrap().as_i64().unwrap() > 2);
+/// ```
+///
+/// # See also:
+///
+/// - [`OwnedContext`](crate::OwnedContext)
+pub trait Translate: private::Sealed {
+    /// The same type, living in the context `'dest`.
+    type Target<'dest>: for<'other> Translate<Target<'other> = Self::Target<'other>>;
+
+    /// Copy the value to the context `dest`.
+    fn translate_to<'dest>(&self, dest: &'dest Context) -> Self::Target<'dest>;
+}
+
+macro_rules! impl_translate_for_ast {
+    ($($ast:ident),*) => {
+        $(
+            impl<'ctx> Translate for $ast<'ctx> {
+                type Target<'dest> = $ast<'dest>;
+
+                fn translate_to<'dest>(&self, dest: &'dest Context) -> $ast<'dest> {
+                    unsafe {
+                        $ast::wrap(
+                            dest,
+                            Z3_translate(self.get_ctx().z3_ctx, self.get_z3_ast(), dest.z3_ctx),
+                        )
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_translate_for_ast!(
+    Bool,
+    Int,
+    Real,
+    Float,
+    RoundingMode,
+    String,
+    BV,
+    Array,
+    Set,
+    Seq,
+    Regexp,
+    Datatype,
+    Dynamic,
+    Quantifier
+);
+#[cfg(feature = "unicode")]
+impl_translate_for_ast!(Char);
+
+impl<'ctx> Translate for Sort<'ctx> {
+    type Target<'dest> = Sort<'dest>;
+
+    fn translate_to<'dest>(&self, dest: &'dest Context) -> Sort<'dest> {
+        unsafe {
+            let ast = Z3_sort_to_ast(self.ctx.z3_ctx, self.z3_sort);
+            Sort::wrap(
+                dest,
+                Z3_translate(self.ctx.z3_ctx, ast, dest.z3_ctx) as Z3_sort,
+            )
+        }
+    }
+}
+
+impl<'ctx> Translate for FuncDecl<'ctx> {
+    type Target<'dest> = FuncDecl<'dest>;
+
+    fn translate_to<'dest>(&self, dest: &'dest Context) -> FuncDecl<'dest> {
+        unsafe {
+            let ast = Z3_func_decl_to_ast(self.ctx.z3_ctx, self.z3_func_decl);
+            let ast = Z3_translate(self.ctx.z3_ctx, ast, dest.z3_ctx);
+            FuncDecl::wrap(dest, Z3_to_func_decl(dest.z3_ctx, ast))
+        }
+    }
+}
+
+impl<'ctx, T: Translate> Translate for AstVector<'ctx, T> {
+    type Target<'dest> = AstVector<'dest, T::Target<'dest>>;
+
+    fn translate_to<'dest>(&self, dest: &'dest Context) -> Self::Target<'dest> {
+        self.translate(dest)
+    }
+}
+
+impl<'ctx> Translate for Solver<'ctx> {
+    type Target<'dest> = Solver<'dest>;
+
+    fn translate_to<'dest>(&self, dest: &'dest Context) -> Solver<'dest> {
+        self.translate(dest)
+    }
+}
+
+impl<'ctx> Translate for Model<'ctx> {
+    type Target<'dest> = Model<'dest>;
+
+    fn translate_to<'dest>(&self, dest: &'dest Context) -> Model<'dest> {
+        self.translate(dest)
+    }
+}
+
+impl<'ctx> Translate for Goal<'ctx> {
+    type Target<'dest> = Goal<'dest>;
+
+    fn translate_to<'dest>(&self, dest: &'dest Context) -> Goal<'dest> {
+        unsafe {
+            Goal::wrap(
+                dest,
+                Z3_goal_translate(self.ctx.z3_ctx, self.z3_goal, dest.z3_ctx),
+            )
+        }
+    }
+}
+
+impl Translate for () {
+    type Target<'dest> = ();
+
+    fn translate_to(&self, _dest: &Context) {}
+}
+
+impl<T: Translate> Translate for Option<T> {
+    type Target<'dest> = Option<T::Target<'dest>>;
+
+    fn translate_to<'dest>(&self, dest: &'dest Context) -> Self::Target<'dest> {
+        self.as_ref().map(|value| value.translate_to(dest))
+    }
+}
+
+impl<T: Translate> Translate for Vec<T> {
+    type Target<'dest> = Vec<T::Target<'dest>>;
+
+    fn translate_to<'dest>(&self, dest: &'dest Context) -> Self::Target<'dest> {
+        self.iter().map(|value| value.translate_to(dest)).collect()
+    }
+}
+
+macro_rules! impl_translate_for_tuple {
+    ($($t:ident $i:tt),+) => {
+        impl<$($t: Translate),+> Translate for ($($t,)+) {
+            type Target<'dest> = ($($t::Target<'dest>,)+);
+
+            fn translate_to<'dest>(&self, dest: &'dest Context) -> Self::Target<'dest> {
+                ($(self.$i.translate_to(dest),)+)
+            }
+        }
+    };
+}
+
+impl_translate_for_tuple!(A 0);
+impl_translate_for_tuple!(A 0, B 1);
+impl_translate_for_tuple!(A 0, B 1, C 2);
+impl_translate_for_tuple!(A 0, B 1, C 2, D 3);
+impl_translate_for_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_translate_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+mod private {
+    #[cfg(feature = "unicode")]
+    use crate::ast::Char;
+    use crate::ast::{
+        Array, Bool, Datatype, Dynamic, Float, Int, Quantifier, Real, Regexp, RoundingMode, Seq,
+        Set, String, BV,
+    };
+    use crate::{AstVector, FuncDecl, Goal, Model, Solver, Sort};
+
+    /// [`OwnedContext`](crate::OwnedContext) relies on `Translate::Target<'dest>`
+    /// tying all the Z3 objects of a value to the context `'dest`, so the
+    /// trait is sealed.
+    pub trait Sealed {}
+
+    macro_rules! impl_sealed {
+        ($($ty:ident),*) => {
+            $(impl Sealed for $ty<'_> {})*
+        };
+    }
+
+    impl_sealed!(
+        Bool,
+        Int,
+        Real,
+        Float,
+        RoundingMode,
+        String,
+        BV,
+        Array,
+        Set,
+        Seq,
+        Regexp,
+        Datatype,
+        Dynamic,
+        Quantifier,
+        Sort,
+        FuncDecl,
+        Solver,
+        Model,
+        Goal
+    );
+    #[cfg(feature = "unicode")]
+    impl_sealed!(Char);
+
+    impl<T> Sealed for AstVector<'_, T> {}
+    impl Sealed for () {}
+    impl<T: Sealed> Sealed for Option<T> {}
+    impl<T: Sealed> Sealed for Vec<T> {}
+
+    macro_rules! impl_sealed_for_tuple {
+        ($($t:ident),+) => {
+            impl<$($t: Sealed),+> Sealed for ($($t,)+) {}
+        };
+    }
+
+    impl_sealed_for_tuple!(A);
+    impl_sealed_for_tuple!(A, B);
+    impl_sealed_for_tuple!(A, B, C);
+    impl_sealed_for_tuple!(A, B, C, D);
+    impl_sealed_for_tuple!(A, B, C, D, E);
+    impl_sealed_for_tuple!(A, B, C, D, E, F);
+}
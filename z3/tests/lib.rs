@@ -128,12 +128,12 @@ fn test_cloning_ast() {
     assert_eq!(yv, 0);
 }
 
-fn get_some_solver_assertions(ctx: &Context) -> Vec<ast::Bool> {
+fn get_some_solver_assertions(ctx: &Context) -> Vec<ast::Bool<'_>> {
     let s = Solver::new(ctx);
     let x = ast::Int::new_const(ctx, "x");
     let y = ast::Int::new_const(ctx, "y");
     s.assert(&x.gt(&y));
-    s.get_assertions()
+    s.get_assertions().to_vec()
 }
 
 #[test]
@@ -467,6 +467,161 @@ fn test_float_add() {
     assert_eq!(solver.check(), SatResult::Sat);
 }
 
+#[test]
+fn test_float_ieee_ops() {
+    use ast::{Float, RoundingMode};
+
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    let rne = RoundingMode::nearest_ties_to_even(&ctx);
+    let rtz = RoundingMode::toward_zero(&ctx);
+
+    let check = |b: &Bool| {
+        solver.push();
+        solver.assert(&b.not());
+        let res = solver.check();
+        solver.pop(1);
+        assert_eq!(res, SatResult::Unsat, "not valid: {b}");
+    };
+
+    let two = Float::from_f64(&ctx, 2.0);
+    let three = Float::from_f64(&ctx, 3.0);
+    let seven = Float::from_f64(&ctx, 7.0);
+    check(
+        &two.fma(&rne, &three, &Float::from_f64(&ctx, 1.0))
+            .fp_eq(&seven),
+    );
+    check(&Float::from_f64(&ctx, 9.0).sqrt(&rne).fp_eq(&three));
+    check(&seven.rem(&two).fp_eq(&Float::from_f64(&ctx, -1.0)));
+    check(
+        &Float::from_f64(&ctx, 2.5)
+            .round_to_integral(&rne)
+            .fp_eq(&two),
+    );
+    check(
+        &Float::from_f64(&ctx, 2.5)
+            .round_to_integral(&rtz)
+            .fp_eq(&two),
+    );
+    check(&two.min(&three).fp_eq(&two));
+    check(&two.max(&three).fp_eq(&three));
+    check(&seven.div_rm(&rtz, &two).fp_eq(&Float::from_f64(&ctx, 3.5)));
+
+    let nan = Float::nan(&ctx, 11, 53);
+    check(&nan.is_nan());
+    check(&nan.fp_eq(&nan).not());
+    check(&nan._eq(&nan));
+    check(&Float::infinity(&ctx, 11, 53, true).is_infinite());
+    check(&Float::infinity(&ctx, 11, 53, true).is_negative());
+    check(&Float::zero(&ctx, 11, 53, true).fp_eq(&Float::zero(&ctx, 11, 53, false)));
+    check(&Float::from_f64(&ctx, f64::MIN_POSITIVE / 2.0).is_subnormal());
+    check(&two.is_normal());
+    check(&two.is_positive());
+
+    let minus_three = BV::from_i64(&ctx, -3, 8);
+    let f = Float::from_signed_bv(&rne, &minus_three, 8, 24);
+    check(&f.fp_eq(&Float::from_f32(&ctx, -3.0)));
+    check(&f.to_sbv(&rtz, 8)._eq(&minus_three));
+    let u = Float::from_unsigned_bv(&rne, &BV::from_u64(&ctx, 200, 8), 8, 24);
+    check(&u.to_ubv(&rtz, 8)._eq(&BV::from_u64(&ctx, 200, 8)));
+    check(
+        &Float::from_f64(&ctx, 2.75)
+            .to_sbv(&rtz, 8)
+            ._eq(&BV::from_i64(&ctx, 2, 8)),
+    );
+
+    let half = ast::Real::from_real(&ctx, 1, 2);
+    let f = Float::from_real(&rne, &half, 11, 53);
+    check(&f.fp_eq(&Float::from_f64(&ctx, 0.5)));
+    check(&f.to_real()._eq(&half));
+    check(&f.to_float(&rne, 8, 24).fp_eq(&Float::from_f32(&ctx, 0.5)));
+    check(&Float::from_ieee_bv(&f.to_ieee_bv(), 11, 53).fp_eq(&f));
+
+    let rm = RoundingMode::new_const(&ctx, "rm");
+    let x = Float::new_const_double(&ctx, "x");
+    solver.assert(&x.round_to_integral(&rm).fp_eq(&Float::from_f64(&ctx, 3.0)));
+    solver.assert(&x.fp_eq(&Float::from_f64(&ctx, 2.5)));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+    let rm_value = model.eval(&rm, true).unwrap();
+    assert!(
+        rm_value == RoundingMode::nearest_ties_to_away(&ctx)
+            || rm_value == RoundingMode::toward_positive(&ctx)
+    );
+    let dynamic: ast::Dynamic = rm.into();
+    assert!(dynamic.as_rounding_mode().is_some());
+    assert!(dynamic.as_float().is_none());
+}
+
+#[test]
+fn test_float_value() {
+    use ast::Float;
+    use num::bigint::BigUint;
+
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    for v in [
+        1.5,
+        -3.0,
+        1e300,
+        -0.0,
+        5e-324,
+        f64::MIN_POSITIVE / 3.0,
+        f64::MAX,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ] {
+        let value = Float::from_f64(&ctx, v).as_float_value().unwrap();
+        assert_eq!(value.to_f64().map(f64::to_bits), Some(v.to_bits()), "{v}");
+    }
+    for v in [0.1f32, -1e-45, f32::MAX, 3.0e-39] {
+        let value = Float::from_f32(&ctx, v).as_float_value().unwrap();
+        assert_eq!(value.to_f32().map(f32::to_bits), Some(v.to_bits()), "{v}");
+        assert_eq!(value.to_f64(), Some(v as f64));
+    }
+    assert!(Float::nan(&ctx, 8, 24)
+        .as_float_value()
+        .unwrap()
+        .to_f32()
+        .unwrap()
+        .is_nan());
+    assert_eq!(Float::new_const_double(&ctx, "x").as_float_value(), None);
+
+    // 0.1 in quadruple precision is not exactly representable as an f64.
+    let rne = ast::RoundingMode::nearest_ties_to_even(&ctx);
+    let tenth = Float::from_real(&rne, &ast::Real::from_real(&ctx, 1, 10), 15, 113);
+    let solver = Solver::new(&ctx);
+    let x = Float::new_const(&ctx, "x", 15, 113);
+    solver.assert(&x._eq(&tenth));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let value = solver
+        .get_model()
+        .unwrap()
+        .eval(&x, true)
+        .unwrap()
+        .as_float_value()
+        .unwrap();
+    assert_eq!(value.to_f64(), None);
+    match value {
+        FloatValue::Finite {
+            negative,
+            exponent,
+            significand,
+        } => {
+            assert!(!negative);
+            assert_eq!(exponent, -116);
+            assert_eq!(significand.bits(), 113);
+            assert_eq!(
+                significand,
+                BigUint::parse_bytes(b"1999999999999999999999999999a", 16).unwrap()
+            );
+        }
+        other => panic!("unexpected value {:?}", other),
+    }
+}
+
 #[test]
 fn test_arbitrary_size_real() {
     let cfg = Config::new();
@@ -604,6 +759,133 @@ fn test_string_as_string() {
     assert_string_roundtrip(r#"\\"x\\""#);
 }
 
+#[test]
+fn test_string_from_str_escapes() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let expr = ast::String::from_str(&ctx, r"a\u{62}").unwrap();
+    assert_eq!(expr.as_string().unwrap(), "ab");
+}
+
+fn assert_unicode_roundtrip(source: &str) {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let expr = ast::String::from_unicode(&ctx, source).unwrap();
+    assert_eq!(&expr.as_string().unwrap(), source);
+}
+
+#[test]
+fn test_string_from_unicode() {
+    assert_unicode_roundtrip("x");
+    assert_unicode_roundtrip(r"a\u{62}");
+    assert_unicode_roundtrip(r"\u{100}");
+    assert_unicode_roundtrip(r"\");
+    assert_unicode_roundtrip("caf\u{e9}");
+    assert_unicode_roundtrip("\u{1F600} \u{10000}");
+    assert_unicode_roundtrip("\u{2FFFF}");
+    assert_unicode_roundtrip("tab\tnul\0");
+
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let err = ast::String::from_unicode(&ctx, "a\u{30000}").unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidArg);
+}
+
+#[test]
+fn test_string_operations() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let s = |v: &str| ast::String::from_unicode(&ctx, v).unwrap();
+    let int = |v: i64| ast::Int::from_i64(&ctx, v);
+
+    let abcabc = s("abcabc");
+    assert_eq!(abcabc.index_of(&s("c"), &int(0)).simplify(), int(2));
+    assert_eq!(abcabc.index_of(&s("c"), &int(3)).simplify(), int(5));
+    assert_eq!(abcabc.index_of(&s("d"), &int(0)).simplify(), int(-1));
+    assert_eq!(abcabc.last_index_of(&s("ab")).simplify(), int(3));
+    assert_eq!(
+        abcabc.replace(&s("b"), &s("\u{e9}")).simplify().as_string(),
+        Some("a\u{e9}cabc".to_string())
+    );
+    assert_eq!(s("042").to_int().simplify(), int(42));
+    assert_eq!(s("4x").to_int().simplify(), int(-1));
+    assert_eq!(
+        ast::String::from_int(&int(17)).simplify().as_string(),
+        Some("17".to_string())
+    );
+
+    let solver = Solver::new(&ctx);
+    let x = ast::String::new_const(&ctx, "x");
+    solver.assert(&x.gt(&s("b")));
+    solver.assert(&x.lt(&s("ba")));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+    let value = model.eval(&x, true).unwrap().as_string().unwrap();
+    assert!(value.as_str() > "b" && value.as_str() < "ba");
+
+    solver.reset();
+    solver.assert(&x._eq(&s("\u{1F600}")));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+    let value = model.eval(&x, true).unwrap().as_string().unwrap();
+    assert_eq!(value, "\u{1F600}");
+    assert_eq!(s("a").le(&s("a")).simplify().as_bool(), Some(true));
+    assert_eq!(s("b").ge(&s("a")).simplify().as_bool(), Some(true));
+}
+
+#[test]
+fn test_seq_operations() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let int_sort = Sort::int(&ctx);
+    let int = |v: i64| ast::Int::from_i64(&ctx, v);
+    let seq = |vs: &[i64]| {
+        let units: Vec<ast::Seq> = vs.iter().map(|&v| ast::Seq::unit(&ctx, &int(v))).collect();
+        ast::Seq::concat(&ctx, &units)
+    };
+    let solver = Solver::new(&ctx);
+    let valid = |b: &ast::Bool| {
+        solver.push();
+        solver.assert(&b.not());
+        let result = solver.check();
+        solver.pop(1);
+        result == SatResult::Unsat
+    };
+
+    let s = seq(&[1, 2, 3, 1, 2]);
+    assert!(valid(
+        &ast::Seq::empty(&ctx, &int_sort).length()._eq(&int(0))
+    ));
+    assert!(valid(&s.length()._eq(&int(5))));
+    assert!(valid(&seq(&[1, 2]).prefix(&s)));
+    assert!(valid(&seq(&[1, 2]).suffix(&s)));
+    assert!(valid(&seq(&[2, 1]).suffix(&s).not()));
+    assert!(valid(&s.contains(&seq(&[3, 1]))));
+    assert!(valid(&s.contains(&seq(&[3, 2])).not()));
+    assert!(valid(&s.extract(&int(1), &int(2))._eq(&seq(&[2, 3]))));
+    assert!(valid(
+        &s.replace(&seq(&[1, 2]), &seq(&[4]))
+            ._eq(&seq(&[4, 3, 1, 2]))
+    ));
+    assert!(valid(&s.index_of(&seq(&[2]), &int(2))._eq(&int(4))));
+    assert!(valid(&s.index_of(&seq(&[5]), &int(0))._eq(&int(-1))));
+
+    let re = ast::Regexp::concat(&ctx, &[&seq(&[1, 2]).to_re(), &seq(&[3]).to_re()]);
+    assert!(valid(&seq(&[1, 2, 3]).regex_matches(&re)));
+    assert!(valid(&seq(&[1, 3]).regex_matches(&re).not()));
+    let re = seq(&[1, 2]).to_re().plus();
+    assert!(valid(&seq(&[1, 2, 1, 2]).regex_matches(&re)));
+
+    let x = ast::Seq::new_const(&ctx, "x", &int_sort);
+    let seq_sort = Sort::seq(&ctx, &int_sort);
+    solver.reset();
+    solver.assert(&x.regex_matches(&ast::Regexp::empty_for(&seq_sort)));
+    assert_eq!(solver.check(), SatResult::Unsat);
+    solver.reset();
+    solver.assert(&x.regex_matches(&ast::Regexp::full_for(&seq_sort)));
+    assert_eq!(solver.check(), SatResult::Sat);
+}
+
 #[test]
 fn test_rec_func_def() {
     let _ = env_logger::try_init();
@@ -740,6 +1022,114 @@ fn test_optimize_new_from_smtlib2() {
     assert_eq!(optimize.check(&[]), SatResult::Sat);
 }
 
+#[test]
+fn test_optimize_assertions_and_scopes() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let optimize = Optimize::new(&ctx);
+    let x = Int::new_const(&ctx, "x");
+    let x_ge_0 = x.ge(&Int::from_i64(&ctx, 0));
+    let x_le_5 = x.le(&Int::from_i64(&ctx, 5));
+    let x_le_2 = x.le(&Int::from_i64(&ctx, 2));
+
+    optimize.assert(&x_ge_0);
+    optimize.maximize(&x);
+    assert_eq!(optimize.get_assertions(), vec![x_ge_0.clone()]);
+    assert_eq!(optimize.get_num_scopes(), 0);
+
+    optimize.push();
+    optimize.assert(&x_le_5);
+    optimize.push();
+    optimize.assert(&x_le_2);
+    assert_eq!(optimize.get_num_scopes(), 2);
+    assert_eq!(optimize.get_assertions().len(), 3);
+    assert_eq!(optimize.check(&[]), SatResult::Sat);
+    let model = optimize.get_model().unwrap();
+    assert_eq!(model.eval(&x, true).unwrap().as_i64(), Some(2));
+
+    optimize.pop(2);
+    assert_eq!(optimize.get_num_scopes(), 0);
+    assert_eq!(optimize.get_assertions(), vec![x_ge_0]);
+    optimize.pop(0);
+}
+
+#[test]
+#[should_panic(expected = "cannot pop 1 scopes, only 0 were pushed")]
+fn test_optimize_pop_without_push() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    Optimize::new(&ctx).pop(1);
+}
+
+#[test]
+fn test_optimize_to_and_from_file() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let optimize = Optimize::new(&ctx);
+    let x = Int::new_const(&ctx, "x");
+    let y = Int::new_const(&ctx, "y");
+    optimize.assert(&(&x + &y).le(&Int::from_i64(&ctx, 10)));
+    optimize.assert(&y.ge(&Int::from_i64(&ctx, 4)));
+    optimize.maximize(&x);
+    let smtlib = optimize.to_string();
+    assert!(smtlib.contains("(maximize x)"), "{}", smtlib);
+
+    let path = std::env::temp_dir().join(format!("z3-optimize-{}.smt2", std::process::id()));
+    std::fs::write(&path, &smtlib).unwrap();
+    let other = Context::new(&cfg);
+    let reloaded = Optimize::new(&other);
+    let result = reloaded.from_file(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    result.unwrap();
+
+    assert_eq!(reloaded.get_assertions().len(), 2);
+    assert_eq!(reloaded.check(&[]), SatResult::Sat);
+    let model = reloaded.get_model().unwrap();
+    let x = Int::new_const(&other, "x");
+    assert_eq!(model.eval(&x, true).unwrap().as_i64(), Some(6));
+
+    assert!(reloaded.from_file("/nonexistent/problem.smt2").is_err());
+    let err = reloaded.from_file("problem\0.smt2").unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidArg);
+}
+
+#[test]
+fn test_optimize_try_from_string() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let optimize = Optimize::new(&ctx);
+
+    optimize
+        .try_from_string("(declare-const x Int) (assert (< x 5)) (maximize x)")
+        .unwrap();
+    assert_eq!(optimize.get_assertions().len(), 1);
+    assert_eq!(optimize.get_objectives().len(), 1);
+
+    let err = optimize.try_from_string("(minimize y)").unwrap_err();
+    assert_ne!(err.code(), ErrorCode::OK);
+    assert!(!err.message().is_empty());
+
+    let err = optimize.try_from_string("(assert\0 false)").unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidArg);
+}
+
+#[test]
+fn test_optimize_param_descrs() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let optimize = Optimize::new(&ctx);
+    let descrs = optimize.get_param_descrs();
+    assert!(!descrs.is_empty());
+    assert_eq!(descrs.names().len(), descrs.len());
+    assert!(descrs.names().contains(&Symbol::from("priority")));
+    assert_eq!(descrs.kind("priority"), ParamKind::Symbol);
+    assert_eq!(descrs.kind("maxsat_engine"), ParamKind::Symbol);
+    assert_eq!(descrs.kind("no_such_param"), ParamKind::Invalid);
+    assert!(descrs.documentation("priority").is_some());
+    assert_eq!(descrs.documentation("no_such_param"), None);
+    assert!(optimize.get_help().contains("priority"));
+}
+
 #[test]
 fn test_get_unsat_core() {
     let _ = env_logger::try_init();
@@ -1218,6 +1608,49 @@ fn test_set_membership() {
     solver.pop(1);
 }
 
+#[test]
+fn test_set_elements() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let int_sort = Sort::int(&ctx);
+    let ints: Vec<ast::Int> = (0..4).map(|i| ast::Int::from_i64(&ctx, i)).collect();
+    let sorted = |elements: Vec<ast::Dynamic>| {
+        let mut values: Vec<i64> = elements
+            .iter()
+            .map(|e| e.as_int().unwrap().as_i64().unwrap())
+            .collect();
+        values.sort_unstable();
+        values
+    };
+
+    let solver = Solver::new(&ctx);
+    let s = ast::Set::new_const(&ctx, "s", &int_sort);
+    let t = ast::Set::new_const(&ctx, "t", &int_sort);
+    // s = {1, 2, 3}, with its members only constrained through x.
+    let x = ast::Int::new_const(&ctx, "x");
+    let members = [x.clone(), ints[2].clone(), ints[3].clone()];
+    let three = ast::Set::from_iter(&ctx, &int_sort, &members);
+    solver.assert(&s._eq(&three));
+    solver.assert(&x.lt(&ints[2]));
+    solver.assert(&x.gt(&ints[0]));
+    // t = everything but {0, 2}.
+    solver.assert(&t._eq(&ast::Set::full(&ctx, &int_sort).del(&ints[0]).del(&ints[2])));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+
+    assert_eq!(sorted(s.elements(&model).unwrap()), vec![1, 2, 3]);
+    assert_eq!(s.non_elements(&model), None);
+    assert_eq!(t.elements(&model), None);
+    assert_eq!(sorted(t.non_elements(&model).unwrap()), vec![0, 2]);
+
+    let u = ast::Set::from_iter(&ctx, &int_sort, &ints[..2]);
+    assert_eq!(sorted(u.elements(&model).unwrap()), vec![0, 1]);
+    assert_eq!(
+        ast::Set::empty(&ctx, &int_sort).elements(&model),
+        Some(vec![])
+    );
+}
+
 #[test]
 fn test_dynamic_as_set() {
     let _ = env_logger::try_init();
@@ -1253,6 +1686,242 @@ fn test_array_store_select() {
     assert_eq!(solver.check(), SatResult::Unsat);
 }
 
+#[test]
+fn test_array_decode() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let int_sort = Sort::int(&ctx);
+    let int = |i: i64| -> ast::Dynamic { ast::Int::from_i64(&ctx, i).into() };
+    let solver = Solver::new(&ctx);
+
+    // A heap of two-field records, with -1 in unused fields.
+    let cell_sort = Sort::array(&ctx, &int_sort, &int_sort);
+    let heap = ast::Array::new_const(&ctx, "heap", &int_sort, &cell_sort);
+    let p = ast::Int::new_const(&ctx, "p");
+    let record = |a: i64, b: i64| {
+        ast::Array::const_array(&ctx, &int_sort, &int(-1))
+            .store(&int(0), &int(a))
+            .store(&int(1), &int(b))
+    };
+    solver.assert(&heap.select(&p)._eq(&record(7, 8).into()));
+    solver.assert(&heap.select(&int(0))._eq(&record(0, 0).into()));
+    solver.assert(&p._eq(&ast::Int::from_i64(&ctx, 16)));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+
+    let value = heap.decode(&model).unwrap();
+    let cell = value
+        .get(&int(16))
+        .as_array()
+        .unwrap()
+        .decode(&model)
+        .unwrap();
+    assert_eq!(cell.default, int(-1));
+    assert_eq!(cell.get(&int(0)), &int(7));
+    assert_eq!(cell.get(&int(1)), &int(8));
+    assert_eq!(cell.get(&int(2)), &int(-1));
+
+    // Pointwise operations.
+    let f = FuncDecl::new(&ctx, "f", &[&int_sort, &int_sort], &int_sort);
+    let mapped = ast::Array::map(&ctx, &f, &[&record(1, 2), &record(3, 4)]);
+    assert_eq!(
+        mapped.select(&int(1)).simplify(),
+        f.apply(&[&int(2), &int(4)])
+    );
+    assert_eq!(
+        record(1, 2).default().decl().kind(),
+        DeclKind::ARRAY_DEFAULT
+    );
+}
+
+#[test]
+fn test_model_get() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    let int = |i| Int::from_i64(&ctx, i);
+
+    let x = Int::new_const(&ctx, "x");
+    let big = Int::new_const(&ctx, "big");
+    let r = ast::Real::new_const(&ctx, "r");
+    let b = BV::new_const(&ctx, "b", 16);
+    let p = Bool::new_const(&ctx, "p");
+    let s = ast::String::new_const(&ctx, "s");
+    let f = ast::Float::new_const_double(&ctx, "f");
+    let seq = ast::Seq::new_const(&ctx, "seq", &Sort::int(&ctx));
+    let arr = Array::new_const(&ctx, "arr", &Sort::int(&ctx), &Sort::bool(&ctx));
+    let pair_sort = DatatypeBuilder::new(&ctx, "Pair")
+        .variant(
+            "mk_pair",
+            vec![
+                ("first", DatatypeAccessor::Sort(Sort::int(&ctx))),
+                ("second", DatatypeAccessor::Sort(Sort::bool(&ctx))),
+            ],
+        )
+        .finish();
+    let pair = pair_sort.variants[0].constructor.apply(&[&x, &p]);
+
+    solver.assert(&x._eq(&int(-42)));
+    solver.assert(&big._eq(&Int::from_str(&ctx, "100000000000000000000").unwrap()));
+    solver.assert(&r._eq(&ast::Real::from_real(&ctx, -3, 4)));
+    solver.assert(&b._eq(&BV::from_u64(&ctx, 0xfffe, 16)));
+    solver.assert(&p);
+    solver.assert(&s._eq(&ast::String::from_str(&ctx, "hello").unwrap()));
+    solver.assert(&f._eq(&ast::Float::from_f64(&ctx, 1.5)));
+    solver.assert(&seq._eq(&ast::Seq::concat(
+        &ctx,
+        &[
+            &ast::Seq::unit(&ctx, &int(1)),
+            &ast::Seq::unit(&ctx, &int(2)),
+        ],
+    )));
+    let empty = Array::const_array(&ctx, &Sort::int(&ctx), &Bool::from_bool(&ctx, false));
+    // The entry storing the default value is left out of the map.
+    solver.assert(
+        &arr._eq(
+            &empty
+                .store(&int(3), &Bool::from_bool(&ctx, true))
+                .store(&int(5), &Bool::from_bool(&ctx, false)),
+        ),
+    );
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+
+    assert_eq!(model.get::<i64>(&x), Ok(-42));
+    assert_eq!(model.get::<i8>(&x), Ok(-42));
+    assert_eq!(model.get::<BigInt>(&x), Ok(BigInt::from(-42)));
+    assert_eq!(model.get::<u128>(&big), Ok(100_000_000_000_000_000_000u128));
+    assert_eq!(
+        model.get::<BigRational>(&r),
+        Ok(BigRational::new(BigInt::from(-3), BigInt::from(4)))
+    );
+    assert_eq!(model.get::<u16>(&b), Ok(0xfffe));
+    assert_eq!(model.get::<i16>(&b), Ok(-2));
+    assert_eq!(model.get::<BigInt>(&b), Ok(BigInt::from(0xfffe)));
+    assert_eq!(model.get::<bool>(&p), Ok(true));
+    assert_eq!(model.get::<String>(&s), Ok("hello".to_string()));
+    assert_eq!(model.get::<f64>(&f), Ok(1.5));
+    assert_eq!(model.get::<Vec<i32>>(&seq), Ok(vec![1, 2]));
+    assert_eq!(
+        model.get::<std::collections::HashMap<i64, bool>>(&arr),
+        Ok(vec![(3, true)].into_iter().collect())
+    );
+    assert_eq!(model.get::<(i64, bool)>(&pair), Ok((-42, true)));
+
+    assert_eq!(
+        model.get::<u64>(&x),
+        Err(FromModelError::OutOfRange {
+            value: "-42".to_string(),
+            target: "u64",
+        })
+    );
+    assert_eq!(
+        model.get::<i64>(&big),
+        Err(FromModelError::OutOfRange {
+            value: "100000000000000000000".to_string(),
+            target: "i64",
+        })
+    );
+    assert_eq!(
+        model.get::<String>(&x),
+        Err(FromModelError::WrongSort {
+            expected: "String",
+            found: "Int".to_string(),
+        })
+    );
+    assert!(matches!(
+        model.get::<i64>(&r),
+        Err(FromModelError::WrongSort { .. })
+    ));
+    assert!(matches!(
+        model.get::<Vec<bool>>(&seq),
+        Err(FromModelError::WrongSort { .. })
+    ));
+}
+
+#[test]
+fn test_model_building() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let int = |i| Int::from_i64(&ctx, i);
+    let x_decl = FuncDecl::new(&ctx, "x", &[], &Sort::int(&ctx));
+    let y_decl = FuncDecl::new(&ctx, "y", &[], &Sort::int(&ctx));
+    let f_decl = FuncDecl::new(&ctx, "f", &[&Sort::int(&ctx)], &Sort::int(&ctx));
+    let x = x_decl.apply(&[]).as_int().unwrap();
+    let fx = f_decl.apply(&[&x]).as_int().unwrap();
+
+    let mut model = Model::new(&ctx);
+    assert!(!model.has_interp(&x_decl));
+    model.add_const_interp(&x_decl, &int(3)).unwrap();
+    let f_interp = model.add_func_interp(&f_decl, &int(0)).unwrap();
+    f_interp.add_entry(&[int(3).into()], &int(7).into());
+    assert!(model.has_interp(&x_decl));
+    assert!(model.has_interp(&f_decl));
+    assert!(!model.has_interp(&y_decl));
+    assert_eq!(f_decl.domain(), vec![Sort::int(&ctx)]);
+    assert_eq!(f_decl.range(), Sort::int(&ctx));
+
+    // Evaluate a verification condition against the candidate model.
+    let vc = x.gt(&int(0)).implies(&fx.gt(&x));
+    assert_eq!(model.eval(&vc, true).unwrap().as_bool(), Some(true));
+    assert_eq!(model.get::<i64>(&f_decl.apply(&[&int(4)])), Ok(0));
+
+    // Replacing an interpretation.
+    model.add_const_interp(&x_decl, &int(4)).unwrap();
+    assert_eq!(model.eval(&vc, true).unwrap().as_bool(), Some(false));
+
+    let err = model
+        .add_const_interp(&y_decl, &Bool::from_bool(&ctx, true))
+        .unwrap_err();
+    assert_eq!(err.left(), &Sort::int(&ctx));
+    assert_eq!(err.right(), &Sort::bool(&ctx));
+    assert!(!model.has_interp(&y_decl));
+    assert!(model
+        .add_func_interp(&f_decl, &Bool::from_bool(&ctx, false))
+        .is_err());
+}
+
+#[test]
+fn test_model_universe() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let node = Sort::uninterpreted(&ctx, Symbol::from("Node"));
+    let next = FuncDecl::new(&ctx, "next", &[&node], &node);
+    let nodes: Vec<_> = (0..3)
+        .map(|i| ast::Dynamic::new_const(&ctx, format!("n{i}"), &node))
+        .collect();
+    let solver = Solver::new(&ctx);
+    solver.assert(&ast::Dynamic::distinct(
+        &ctx,
+        &nodes.iter().collect::<Vec<_>>(),
+    ));
+    for (i, n) in nodes.iter().enumerate() {
+        solver.assert(&next.apply(&[n])._eq(&nodes[(i + 1) % 3]));
+    }
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+
+    assert_eq!(model.sorts(), vec![node.clone()]);
+    let universe = model.universe(&node).unwrap();
+    assert_eq!(universe.len(), 3);
+    for n in &nodes {
+        assert!(universe.contains(&model.eval(n, true).unwrap()));
+    }
+    assert_eq!(model.universe(&Sort::int(&ctx)), None);
+
+    let printed = model.to_string();
+    assert!(printed.starts_with(";; universe for "), "{}", printed);
+    assert!(printed.contains(";; universe for Node:\n"), "{}", printed);
+    for element in &universe {
+        assert!(printed.contains(&format!(" {element}")), "{}", printed);
+    }
+    assert!(printed.contains("next -> "), "{}", printed);
+
+    let empty = Model::new(&ctx);
+    assert!(empty.sorts().is_empty());
+    assert_eq!(empty.universe(&node), None);
+}
+
 #[test]
 fn test_goal_get_formulas() {
     let cfg = Config::new();
@@ -1907,14 +2576,14 @@ fn test_consequences() {
 
     let assumptions = vec![a.clone()];
     let variables = vec![b.clone(), c.clone(), d.clone()];
-    let mut cons = solver.get_consequences(&assumptions, &variables);
+    let mut cons = solver.get_consequences(&assumptions, &variables).to_vec();
     assert!(cons.len() == 2);
     assert!(cons.pop().unwrap().to_string() == "(=> a c)");
     assert!(cons.pop().unwrap().to_string() == "(=> a b)");
 
     let assumptions = vec![c.not(), d.clone()];
     let variables = vec![a, b, c, d];
-    let mut cons = solver.get_consequences(&assumptions, &variables);
+    let mut cons = solver.get_consequences(&assumptions, &variables).to_vec();
     assert!(cons.len() == 4);
     assert!(cons.pop().unwrap().to_string() == "(=> (not c) (not a))");
     assert!(cons.pop().unwrap().to_string() == "(=> (not c) (not b))");
@@ -2002,3 +2671,742 @@ fn test_model_iter() {
         vec![ast::Dynamic::new_const(&ctx, "a", &Sort::int(&ctx))]
     );
 }
+
+#[test]
+fn test_solver_try_from_string() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    solver
+        .try_from_string("(declare-const x Int) (assert (> x 0))")
+        .unwrap();
+    assert_eq!(solver.get_assertions().len(), 1);
+
+    let err = solver.try_from_string("(assert (> y 0))").unwrap_err();
+    assert_ne!(err.code(), ErrorCode::OK);
+    assert!(!err.message().is_empty());
+
+    let err = solver.try_from_string("(assert\0 false)").unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidArg);
+}
+
+#[test]
+fn test_solver_try_set_params() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let mut params = Params::new(&ctx);
+    params.try_set_u32("timeout", 1000).unwrap();
+    solver.try_set_params(&params).unwrap();
+
+    params.try_set_bool("no_such_param", true).unwrap();
+    assert_eq!(solver.check(), SatResult::Sat);
+    assert!(solver.try_set_params(&params).is_err());
+
+    let mut params = Params::new(&ctx);
+    assert!(params.try_set_u32("time\0out", 1).is_err());
+    assert!(params.try_set_symbol("engine", "spa\0cer").is_err());
+}
+
+#[test]
+fn test_try_apply_sort_error() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let f = FuncDecl::new(&ctx, "f", &[&Sort::int(&ctx)], &Sort::bool(&ctx));
+
+    assert!(f.try_apply(&[&Int::from_i64(&ctx, 1)]).is_ok());
+
+    let err = f.try_apply(&[&Bool::from_bool(&ctx, true)]).unwrap_err();
+    assert_ne!(err.code(), ErrorCode::OK);
+    assert!(f.try_apply(&[]).is_err());
+}
+
+#[test]
+fn test_try_ast_constructors() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let a = BV::new_const(&ctx, "a", 8);
+    let b = BV::new_const(&ctx, "b", 16);
+    assert!(a.try_bvadd(&a).is_ok());
+    assert!(a.try_bvadd(&b).is_err());
+    assert!(a.try_bvult(&b).is_err());
+
+    let arr = Array::new_const(&ctx, "arr", &Sort::int(&ctx), &Sort::int(&ctx));
+    assert!(arr.try_select(&Int::from_i64(&ctx, 0)).is_ok());
+    assert!(arr.try_select(&a).is_err());
+    assert!(arr.try_store(&Int::from_i64(&ctx, 0), &a).is_err());
+
+    let cond = Bool::new_const(&ctx, "c");
+    assert!(cond.try_ite(&a, &a).is_ok());
+    assert!(cond.try_ite(&a, &b).is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_try_ite_other_context() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let other = Context::new(&cfg);
+
+    let cond = Bool::new_const(&ctx, "c");
+    let a = Int::new_const(&other, "a");
+    let _ = cond.try_ite(&a, &a);
+}
+
+#[test]
+fn test_tactic_try_new() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    assert!(Tactic::try_new(&ctx, "simplify").is_ok());
+    let err = Tactic::try_new(&ctx, "no-such-tactic").unwrap_err();
+    assert!(!err.message().is_empty());
+    let err = Tactic::try_new(&ctx, "simp\0lify").unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidArg);
+}
+
+#[test]
+fn test_fixedpoint_spacer() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let fp = Fixedpoint::new(&ctx);
+
+    let mut params = Params::new(&ctx);
+    params.set_symbol("engine", "spacer");
+    fp.set_params(&params);
+
+    let int = Sort::int(&ctx);
+    let inv = FuncDecl::new(&ctx, "inv", &[&int], &Sort::bool(&ctx));
+    fp.register_relation(&inv);
+
+    let x = Int::new_const(&ctx, "x");
+    let inv_x = inv.apply(&[&x]).as_bool().unwrap();
+    let inv_x1 = inv.apply(&[&(&x + 1i64)]).as_bool().unwrap();
+
+    let init = ast::forall_const(
+        &ctx,
+        &[&x],
+        &[],
+        &x._eq(&Int::from_i64(&ctx, 0)).implies(&inv_x),
+    );
+    let step = ast::forall_const(
+        &ctx,
+        &[&x],
+        &[],
+        &Bool::and(&ctx, &[&inv_x, &x.lt(&Int::from_i64(&ctx, 10))]).implies(&inv_x1),
+    );
+    fp.add_rule(&init, Some("init".into()));
+    fp.add_rule(&step, None);
+    assert_eq!(fp.get_rules().len(), 2);
+
+    let bad = ast::exists_const(
+        &ctx,
+        &[&x],
+        &[],
+        &Bool::and(&ctx, &[&inv_x, &x.gt(&Int::from_i64(&ctx, 10))]),
+    );
+    assert_eq!(fp.query(&bad), SatResult::Unsat);
+    assert!(fp.get_answer().is_some());
+    assert!(fp.get_num_levels(&inv) > 0);
+    assert!(fp.get_cover_delta(-1, &inv).is_some());
+
+    let reach = ast::exists_const(
+        &ctx,
+        &[&x],
+        &[],
+        &Bool::and(&ctx, &[&inv_x, &x._eq(&Int::from_i64(&ctx, 10))]),
+    );
+    assert_eq!(fp.query(&reach), SatResult::Sat);
+}
+
+#[test]
+fn test_fixedpoint_try_from_string() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let fp = Fixedpoint::new(&ctx);
+
+    fp.try_from_string(
+        "(declare-fun r (Int) Bool) \
+         (assert (forall ((x Int)) (=> (= x 1) (r x))))",
+    )
+    .unwrap();
+    // Depending on the Z3 version, parsed clauses are kept as rules or as
+    // background assertions.
+    assert_eq!(fp.get_rules().len() + fp.get_assertions().len(), 1);
+    assert!(fp.try_from_string("(assert (undefined-rel 1))").is_err());
+}
+
+#[test]
+fn test_parse_smtlib2() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let color = Sort::uninterpreted(&ctx, "Color".into());
+    let paint = FuncDecl::new(&ctx, "paint", &[&Sort::int(&ctx)], &color);
+    let red = FuncDecl::new(&ctx, "red", &[], &color);
+
+    let assertions = parse_smtlib2_string(
+        &ctx,
+        "(assert (= (paint 1) red)) (assert (not (= (paint 2) red)))",
+        &[&color],
+        &[&paint, &red],
+    )
+    .unwrap();
+    assert_eq!(assertions.len(), 2);
+
+    let solver = Solver::new(&ctx);
+    solver.assert(&assertions[0]);
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+    let painted = paint.apply(&[&Int::from_i64(&ctx, 1)]);
+    let red_const = red.apply(&[]);
+    assert_eq!(
+        model.eval(&painted._eq(&red_const), true),
+        Some(Bool::from_bool(&ctx, true))
+    );
+
+    solver.assert(&assertions[1].not());
+    solver.assert(&painted._eq(&red_const).not());
+    assert_eq!(solver.check(), SatResult::Unsat);
+
+    let err = parse_smtlib2_string(&ctx, "(assert (paint 1 2))", &[&color], &[&paint]);
+    assert!(err.is_err());
+
+    let err = parse_smtlib2_file(&ctx, "/nonexistent/benchmark.smt2", &[], &[]);
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_ast_vector() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let a = Bool::new_const(&ctx, "a");
+    let b = Bool::new_const(&ctx, "b");
+
+    let mut v = AstVector::from_slice(&ctx, &[a.clone(), b.clone()]);
+    assert_eq!(v.len(), 2);
+    assert_eq!(v, vec![a.clone(), b.clone()]);
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![a.clone(), b.clone()]);
+
+    v.set(0, &b);
+    assert_eq!(v.get(0), Some(b.clone()));
+    assert_eq!(v.get(2), None);
+    assert!(v.to_string().contains('b'));
+
+    let other_ctx = Context::new(&cfg);
+    let translated: AstVector<Bool> = v.translate(&other_ctx);
+    assert_eq!(translated.len(), 2);
+    assert_eq!(translated.get(1), Some(Bool::new_const(&other_ctx, "b")));
+}
+
+#[test]
+fn test_solver_get_assertions_translate() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let x = Int::new_const(&ctx, "x");
+    let solver = Solver::new(&ctx);
+    solver.assert(&x.gt(&Int::from_i64(&ctx, 2)));
+    solver.assert(&x.lt(&Int::from_i64(&ctx, 2)));
+    assert_eq!(solver.check(), SatResult::Unsat);
+
+    let other_ctx = Context::new(&cfg);
+    let other = Solver::new(&other_ctx);
+    for assertion in &solver.get_assertions().translate(&other_ctx) {
+        other.assert(&assertion);
+    }
+    assert_eq!(other.check(), SatResult::Unsat);
+    assert!(other
+        .get_assertions()
+        .contains(&x.translate(&other_ctx).lt(&Int::from_i64(&other_ctx, 2))));
+}
+
+#[test]
+fn test_ast_map() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let x = Int::new_const(&ctx, "x");
+    let y = Int::new_const(&ctx, "y");
+
+    let mut m: AstMap<Int, Int> = AstMap::new(&ctx);
+    assert!(m.is_empty());
+    m.insert(&x, &Int::from_i64(&ctx, 1));
+    m.insert(&(&x + &y), &Int::from_i64(&ctx, 2));
+    assert_eq!(m.len(), 2);
+    assert_eq!(
+        m.get(&Int::add(&ctx, &[&x, &y])),
+        Some(Int::from_i64(&ctx, 2))
+    );
+    assert_eq!(m.get(&y), None);
+
+    m.insert(&x, &Int::from_i64(&ctx, 3));
+    assert_eq!(m.len(), 2);
+    let mut values: Vec<i64> = m.iter().map(|(_, v)| v.as_i64().unwrap()).collect();
+    values.sort_unstable();
+    assert_eq!(values, vec![2, 3]);
+
+    let other_ctx = Context::new(&cfg);
+    let translated: AstMap<Int, Int> = m.translate(&other_ctx);
+    assert_eq!(
+        translated.get(&Int::new_const(&other_ctx, "x")),
+        Some(Int::from_i64(&other_ctx, 3))
+    );
+    assert!(!translated.to_string().is_empty());
+
+    m.remove(&x);
+    assert!(!m.contains_key(&x));
+    m.clear();
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_quantifier_introspection() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let int = Sort::int(&ctx);
+    let f = FuncDecl::new(&ctx, "f", &[&int], &int);
+    let x = Int::new_const(&ctx, "x");
+    let y = Int::new_const(&ctx, "y");
+    let f_x = f.apply(&[&x]).as_int().unwrap();
+    let f_y = f.apply(&[&y]);
+    let pattern = Pattern::new(&ctx, &[&f_x, &f_y]);
+    let no_pattern = f.apply(&[&(&x + &y)]);
+
+    let q = ast::quantifier_const(
+        &ctx,
+        true,
+        7,
+        "q",
+        "",
+        &[&x, &y],
+        &[&pattern],
+        &[],
+        &f_x.lt(&y),
+    );
+    assert_eq!(q.kind(), AstKind::Quantifier);
+    let view = ast::Dynamic::from_ast(&q).as_quantifier().unwrap();
+    assert!(view.is_forall());
+    assert!(!view.is_exists());
+    assert!(!view.is_lambda());
+    assert_eq!(view.weight(), 7);
+    assert_eq!(view.num_bound(), 2);
+    assert_eq!(
+        view.bound_names(),
+        vec![Symbol::from("x"), Symbol::from("y")]
+    );
+    assert_eq!(view.bound_sorts(), vec![int.clone(), int.clone()]);
+
+    let patterns = view.patterns();
+    assert_eq!(patterns.len(), 1);
+    let terms = patterns[0].terms();
+    assert_eq!(terms.len(), 2);
+    assert_eq!(terms[0].decl().name(), "f");
+    assert_eq!(terms[0].children()[0].bound_index(), Some(1));
+    assert!(view.no_patterns().is_empty());
+
+    let with_no_pattern = ast::quantifier_const(
+        &ctx,
+        false,
+        0,
+        "",
+        "",
+        &[&x, &y],
+        &[],
+        &[&no_pattern],
+        &f_x.lt(&y),
+    );
+    let no_patterns = ast::Dynamic::from_ast(&with_no_pattern)
+        .as_quantifier()
+        .unwrap()
+        .no_patterns();
+    assert_eq!(no_patterns.len(), 1);
+    assert_eq!(no_patterns[0].decl().name(), "f");
+
+    // In the body, `y` (bound last) has de Bruijn index 0.
+    let body = view.body();
+    let children = body.children();
+    assert_eq!(children[1].bound_index(), Some(0));
+    assert_eq!(children[0].children()[0].bound_index(), Some(1));
+    assert_eq!(body, ast::Dynamic::from_ast(&body.substitute_vars(&[])));
+
+    let one = Int::from_i64(&ctx, 1);
+    let two = Int::from_i64(&ctx, 2);
+    let expected = f.apply(&[&one]).as_int().unwrap().lt(&two);
+    assert_eq!(view.instantiate(&[&one, &two]), expected.into());
+
+    // Rebuild the quantifier from its parts.
+    let sorts = view.bound_sorts();
+    let bounds: Vec<_> = view.bound_names().into_iter().zip(&sorts).collect();
+    let rebuilt = ast::quantifier(
+        &ctx,
+        view.is_forall(),
+        view.weight(),
+        &bounds,
+        &patterns.iter().collect::<Vec<_>>(),
+        &[],
+        &body.as_bool().unwrap(),
+    );
+    let rebuilt = ast::Dynamic::from_ast(&rebuilt).as_quantifier().unwrap();
+    assert_eq!(rebuilt.body(), body);
+    assert_eq!(rebuilt.weight(), 7);
+
+    let x_var = ast::Dynamic::new_bound(&ctx, 0, &int);
+    assert_eq!(x_var.bound_index(), Some(0));
+    assert_eq!(ast::Dynamic::from_ast(&x).bound_index(), None);
+
+    let lambda = ast::lambda_const(&ctx, &[&x], &ast::Dynamic::from_ast(&(&x + 1i64)));
+    let view = ast::Dynamic::from_ast(&lambda).as_quantifier().unwrap();
+    assert!(view.is_lambda());
+    assert!(ast::Dynamic::from_ast(&x).as_quantifier().is_none());
+}
+
+#[test]
+fn test_solver_iter_solutions() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    let x = ast::Int::new_const(&ctx, "x");
+    let b = ast::Bool::new_const(&ctx, "b");
+    let y = ast::Int::new_const(&ctx, "y");
+    solver.assert(&x.ge(&ast::Int::from_i64(&ctx, 0)));
+    solver.assert(&x.lt(&ast::Int::from_i64(&ctx, 3)));
+    solver.assert(&y.gt(&x));
+
+    // `y` is not projected on, so it does not multiply the solutions.
+    let mut solutions = solver.iter_solutions(&[x.clone().into(), b.clone().into()]);
+    let mut seen = std::collections::HashSet::new();
+    for model in solutions.by_ref() {
+        let xv = model.eval(&x, true).unwrap().as_i64().unwrap();
+        let bv = model.eval(&b, true).unwrap().as_bool().unwrap();
+        assert!(seen.insert((xv, bv)));
+    }
+    assert_eq!(seen.len(), 6);
+    assert_eq!(solutions.result(), Some(SatResult::Unsat));
+    assert_eq!(solutions.reason_unknown(), None);
+    assert_eq!(solutions.next().map(|_| ()), None);
+    drop(solutions);
+    assert_eq!(solver.get_assertions().len(), 3);
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    // Dropping the iterator early also restores the solver.
+    let mut solutions = solver.iter_solutions(&[x.clone().into()]);
+    assert!(solutions.next().is_some());
+    assert_eq!(solutions.result(), None);
+    drop(solutions);
+    assert_eq!(solver.get_assertions().len(), 3);
+}
+
+#[test]
+fn test_solver_mus_mcs() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    let x = ast::Int::new_const(&ctx, "x");
+    solver.assert(&x.ge(&ast::Int::from_i64(&ctx, 0)));
+
+    let lits = [
+        x.gt(&ast::Int::from_i64(&ctx, 10)),
+        x.lt(&ast::Int::from_i64(&ctx, 5)),
+        x._eq(&ast::Int::from_i64(&ctx, 7)),
+        x.lt(&ast::Int::from_i64(&ctx, 100)),
+    ];
+    assert_eq!(solver.minimize_core(&lits[..2]), Some(lits[..2].to_vec()));
+    assert_eq!(solver.minimize_core(&lits[3..]), None);
+
+    let indices = |subset: Vec<Bool>| {
+        let mut idx: Vec<usize> = subset
+            .iter()
+            .map(|l| lits.iter().position(|x| x == l).unwrap())
+            .collect();
+        idx.sort_unstable();
+        idx
+    };
+    let mut muses = vec![];
+    let mut mcses = vec![];
+    let mut iter = solver.iter_mus_mcs(&lits);
+    for subset in iter.by_ref() {
+        match subset {
+            MarcoSubset::Mus(mus) => muses.push(indices(mus)),
+            MarcoSubset::Mcs(mcs) => mcses.push(indices(mcs)),
+        }
+    }
+    assert_eq!(iter.result(), Some(SatResult::Unsat));
+
+    // The first three literals are pairwise contradictory, so any two of
+    // them form a MUS and dropping any two of them is a correction.
+    muses.sort();
+    mcses.sort();
+    assert_eq!(muses, vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+    assert_eq!(mcses, vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+}
+
+#[test]
+fn test_term_view() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let x = ast::Int::new_const(&ctx, "x");
+    let p = ast::Bool::new_const(&ctx, "p");
+    let one = ast::Int::from_i64(&ctx, 1);
+
+    assert_eq!(Bool::from_bool(&ctx, true).view(), Term::True);
+    assert_eq!(one.view(), Term::Numeral(one.clone().into()));
+    match x.view() {
+        Term::Uninterpreted { decl, args } => {
+            assert_eq!(decl.name(), "x");
+            assert!(args.is_empty());
+        }
+        other => panic!("unexpected term {:?}", other),
+    }
+    match p.ite(&x, &one).view() {
+        Term::Ite(c, t, e) => {
+            assert_eq!(c, p);
+            assert_eq!(t, x.clone().into());
+            assert_eq!(e, one.clone().into());
+        }
+        other => panic!("unexpected term {:?}", other),
+    }
+    assert_eq!(
+        Bool::and(&ctx, &[&p, &p.not()]).view(),
+        Term::And(vec![p.clone(), p.not()])
+    );
+    assert_eq!(p.not().view(), Term::Not(p.clone()));
+    assert_eq!(x.modulo(&one).view(), Term::Mod(x.clone(), one.clone()));
+    match atmost(&ctx, vec![&p, &p.not()], 1).view() {
+        Term::AtMost { k, args } => {
+            assert_eq!(k, 1);
+            assert_eq!(args.len(), 2);
+        }
+        other => panic!("unexpected term {:?}", other),
+    }
+
+    let y = BV::new_const(&ctx, "y", 8);
+    assert_eq!(
+        y.sign_ext(8).view(),
+        Term::BvSignExt {
+            bits: 8,
+            arg: y.clone()
+        }
+    );
+    assert_eq!(y.to_int(false).view(), Term::BvToInt(y.clone()));
+    assert_eq!(
+        BV::from_int(&x, 16).view(),
+        Term::IntToBv {
+            bits: 16,
+            arg: x.clone()
+        }
+    );
+
+    let arr = Array::new_const(&ctx, "a", &Sort::int(&ctx), &Sort::bool(&ctx));
+    assert_eq!(
+        arr.select(&x).view(),
+        Term::Select(arr.clone(), x.clone().into())
+    );
+
+    let maybe_int = DatatypeBuilder::new(&ctx, "MaybeInt")
+        .variant("Nothing", vec![])
+        .variant(
+            "Just",
+            vec![("int", DatatypeAccessor::Sort(Sort::int(&ctx)))],
+        )
+        .finish();
+    let just_x = maybe_int.variants[1].constructor.apply(&[&x]);
+    match just_x.view() {
+        Term::DtConstructor { decl, args } => {
+            assert_eq!(decl, maybe_int.variants[1].constructor);
+            assert_eq!(args, vec![x.clone().into()]);
+        }
+        other => panic!("unexpected term {:?}", other),
+    }
+    match maybe_int.variants[1].accessors[0].apply(&[&just_x]).view() {
+        Term::DtAccessor { arg, .. } => assert_eq!(arg, just_x),
+        other => panic!("unexpected term {:?}", other),
+    }
+
+    let body = ast::Dynamic::new_bound(&ctx, 0, &Sort::int(&ctx))
+        .as_int()
+        .unwrap()
+        .gt(&one);
+    let int_sort = Sort::int(&ctx);
+    let q = ast::quantifier(&ctx, true, 0, &[("z".into(), &int_sort)], &[], &[], &body);
+    match q.view() {
+        Term::Quantifier(q) => match q.body().view() {
+            Term::Gt(l, r) => {
+                assert_eq!(l.view(), Term::Var(0));
+                assert_eq!(r, one.into());
+            }
+            other => panic!("unexpected term {:?}", other),
+        },
+        other => panic!("unexpected term {:?}", other),
+    };
+}
+
+#[test]
+fn test_translate_bundle() {
+    let cfg = Config::new();
+    let source = Context::new(&cfg);
+    let x = Int::new_const(&source, "x");
+    let f = FuncDecl::new(&source, "f", &[&Sort::int(&source)], &Sort::int(&source));
+    let solver = Solver::new(&source);
+    solver.assert(&f.apply(&[&x])._eq(&Int::from_i64(&source, 5).into()));
+    let terms = vec![x.clone(), Int::from_i64(&source, 2)];
+
+    let destination = Context::new(&cfg);
+    let (solver, f, terms, none) = (solver, f, terms, None::<Bool>).translate_to(&destination);
+    assert_eq!(none, None);
+    assert_eq!(terms[1].as_i64(), Some(2));
+    assert_eq!(f.name(), "f");
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+    let fx = f.apply(&[&terms[0]]).as_int().unwrap();
+    assert_eq!(model.eval(&fx, true).unwrap().as_i64(), Some(5));
+
+    let back = (model, fx).translate_to(&source);
+    assert_eq!(back.0.eval(&back.1, true).unwrap().as_i64(), Some(5));
+}
+
+#[test]
+fn test_owned_context_threads() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let x = Int::new_const(&ctx, "x");
+    let sessions: Vec<OwnedContext<(Solver, Int)>> = (0..4)
+        .map(|i| {
+            let solver = Solver::new(&ctx);
+            solver.assert(&x._eq(&Int::from_i64(&ctx, i)));
+            OwnedContext::translated(&cfg, &(solver, x.clone()))
+        })
+        .collect();
+
+    let models: Vec<OwnedContext<(Option<Model>, Int)>> = sessions
+        .into_iter()
+        .map(|session| {
+            std::thread::spawn(move || {
+                session.with(|ctx, (solver, x)| {
+                    solver.assert(&x.lt(&Int::from_i64(ctx, 3)));
+                    solver.check();
+                    OwnedContext::translated(&Config::new(), &(solver.get_model(), x.clone()))
+                })
+            })
+        })
+        .map(|handle| handle.join().unwrap())
+        .collect();
+
+    let values: Vec<Option<i64>> = models
+        .iter()
+        .map(|session| {
+            let (model, x) = session.translate(&ctx);
+            model.map(|model| model.eval(&x, true).unwrap().as_i64().unwrap())
+        })
+        .collect();
+    assert_eq!(values, vec![Some(0), Some(1), Some(2), None]);
+    assert!(models[2].with(|ctx, (_, x)| x.get_ctx() == ctx));
+    assert_eq!(OwnedContext::new(&cfg).with(|_, ()| 1), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_owned_context_rejects_foreign_asts() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let x = Bool::new_const(&ctx, "x");
+    let session = OwnedContext::translated(&cfg, &Solver::new(&ctx));
+    session.with(|_, solver| solver.assert(&x));
+}
+
+#[test]
+#[should_panic(expected = "solver of another context")]
+fn test_portfolio_rejects_foreign_solver() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    Portfolio::new(&solver)
+        .add_solver(|_| {
+            let leaked: &'static Context = Box::leak(Box::new(Context::new(&Config::new())));
+            Solver::new(leaked)
+        })
+        .check();
+}
+
+#[test]
+fn test_portfolio() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    let x = Int::new_const(&ctx, "x");
+    let y = Int::new_const(&ctx, "y");
+    solver.assert(&(&x + &y)._eq(&Int::from_i64(&ctx, 10)));
+    solver.assert(&x.gt(&y));
+
+    let outcome = Portfolio::new(&solver)
+        .add_logic("QF_LIA")
+        .add_params(|params| params.set_u32("random_seed", 3))
+        .check();
+    assert_eq!(outcome.result, SatResult::Sat);
+    assert!(outcome.winner.unwrap() < 2);
+    assert!(outcome.reasons_unknown.is_empty());
+    let model = outcome.model.unwrap();
+    let x = model.eval(&x, true).unwrap().as_i64().unwrap();
+    let y = model.eval(&y, true).unwrap().as_i64().unwrap();
+    assert!(x + y == 10 && x > y);
+
+    let z = Bool::new_const(&ctx, "z");
+    let outcome = Portfolio::new(&solver).check_assumptions(&[z.clone(), z.not()]);
+    assert_eq!(outcome.result, SatResult::Unsat);
+    assert_eq!(outcome.winner, Some(0));
+    assert!(outcome.model.is_none());
+}
+
+#[test]
+fn test_portfolio_unknown() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    solver.assert(&Bool::new_const(&ctx, "a"));
+
+    let outcome = Portfolio::new(&solver)
+        .add_tactic("fail")
+        .add_tactic("fail")
+        .check();
+    assert_eq!(outcome.result, SatResult::Unknown);
+    assert_eq!(outcome.winner, None);
+    assert!(outcome.model.is_none());
+    assert_eq!(outcome.reasons_unknown.len(), 2);
+}
+
+#[test]
+fn test_portfolio_interrupts_losers() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    let a = Bool::new_const(&ctx, "a");
+    solver.assert(&a);
+
+    let start = std::time::Instant::now();
+    let outcome = Portfolio::new(&solver)
+        // Factor the product of two 31-bit primes, which takes far longer
+        // than this test runs.
+        .add_solver(|ctx| {
+            let solver = Solver::new(ctx);
+            let p = BV::new_const(ctx, "p", 64);
+            let q = BV::new_const(ctx, "q", 64);
+            let bound = BV::from_u64(ctx, 1 << 32, 64);
+            let one = BV::from_u64(ctx, 1, 64);
+            solver.assert(&p.bvugt(&one));
+            solver.assert(&q.bvugt(&one));
+            solver.assert(&p.bvult(&bound));
+            solver.assert(&q.bvult(&bound));
+            solver.assert(
+                &p.bvmul(&q)
+                    ._eq(&BV::from_u64(ctx, 2147483647 * 2147483629, 64)),
+            );
+            solver
+        })
+        .add_solver(|ctx| Solver::new(ctx))
+        .check();
+    assert_eq!(outcome.result, SatResult::Sat);
+    assert_eq!(outcome.winner, Some(1));
+    let model = outcome.model.unwrap();
+    assert_eq!(model.eval(&a, true).unwrap().as_bool(), Some(true));
+    assert!(start.elapsed() < std::time::Duration::from_secs(30));
+}
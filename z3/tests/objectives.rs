@@ -160,3 +160,194 @@ fn test_optimize_assert_soft_and_get_objectives() {
         );
     }
 }
+
+#[test]
+fn test_optimize_objective_bounds() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let opt = Optimize::new(&ctx);
+    let int = |i| ast::Int::from_i64(&ctx, i);
+
+    let x = ast::Int::new_const(&ctx, "x");
+    let y = ast::Int::new_const(&ctx, "y");
+    let p = ast::Bool::new_const(&ctx, "p");
+    let q = ast::Bool::new_const(&ctx, "q");
+    opt.assert(&x.le(&int(10)));
+    opt.assert(&y.ge(&int(-3)));
+    opt.assert(&ast::Bool::or(&ctx, &[&p.not(), &q.not()]));
+    let max_x = opt.maximize(&x);
+    let min_y = opt.minimize(&y);
+    let soft_p = opt.assert_soft(&p, 1, Some("g".into()));
+    let soft_q = opt.assert_soft(&q, 2, Some("g".into()));
+    assert_ne!(max_x, min_y);
+    assert_eq!(soft_p, soft_q);
+    assert_eq!(opt.check(&[]), SatResult::Sat);
+
+    let objectives = opt.get_objectives();
+    assert_eq!(objectives.len(), 3);
+    // Older Z3 versions report maximizations negated, as `(- x)`.
+    let x = ast::Dynamic::from(x);
+    let max_objective = &objectives[max_x.index()];
+    assert!(*max_objective == x || max_objective.children() == vec![x]);
+    assert_eq!(objectives[min_y.index()], ast::Dynamic::from(y));
+
+    let as_i64 = |d: ast::Dynamic| d.as_int().unwrap().as_i64().unwrap();
+    assert_eq!(as_i64(opt.lower(max_x)), 10);
+    assert_eq!(as_i64(opt.upper(max_x)), 10);
+    assert_eq!(as_i64(opt.lower(min_y)), -3);
+    assert_eq!(as_i64(opt.upper(min_y)), -3);
+
+    // Only p can be dropped, at a penalty of 1.
+    let lower = opt.lower_as_vector(soft_p);
+    assert_eq!(lower, opt.upper_as_vector(soft_p));
+    assert_eq!(lower.infinite.to_string(), "0");
+    assert_eq!(lower.finite.to_string(), "1");
+    assert_eq!(lower.infinitesimal.to_string(), "0");
+}
+
+#[test]
+fn test_optimize_unbounded_objective() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let opt = Optimize::new(&ctx);
+    let x = ast::Int::new_const(&ctx, "x");
+    opt.assert(&x.ge(&ast::Int::from_i64(&ctx, 0)));
+    let objective = opt.maximize(&x);
+    assert_eq!(opt.check(&[]), SatResult::Sat);
+
+    let upper = opt.upper_as_vector(objective);
+    assert_eq!(upper.infinite.as_int().unwrap().as_i64(), Some(1));
+    assert_eq!(upper.finite.as_int().unwrap().as_i64(), Some(0));
+    assert!(opt.upper(objective).to_string().contains("oo"));
+}
+
+#[test]
+#[should_panic(expected = "not an objective of this optimizer")]
+fn test_optimize_bound_of_foreign_objective() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let x = ast::Int::new_const(&ctx, "x");
+    let other = Optimize::new(&ctx);
+    let objective = other.maximize(&x);
+
+    // The index of `objective` is also that of an objective of `opt`.
+    let opt = Optimize::new(&ctx);
+    assert_eq!(opt.minimize(&x).index(), objective.index());
+    opt.lower(objective);
+}
+
+#[test]
+fn test_optimize_pareto_front() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let opt = Optimize::new(&ctx);
+    let int = |i| ast::Int::from_i64(&ctx, i);
+    let x = ast::Int::new_const(&ctx, "x");
+    let y = ast::Int::new_const(&ctx, "y");
+    opt.assert(&x.ge(&int(0)));
+    opt.assert(&y.ge(&int(0)));
+    opt.assert(&(&x + &y).le(&int(3)));
+    let max_x = opt.maximize(&x);
+    let min_y = opt.minimize(&(int(0) - &y));
+
+    let mut front = opt.pareto_iter();
+    let mut points = Vec::new();
+    for point in front.by_ref() {
+        let x_value = point.model.eval(&x, true).unwrap().as_i64().unwrap();
+        let y_value = point.model.eval(&y, true).unwrap().as_i64().unwrap();
+        assert_eq!(point.values.len(), 2);
+        assert_eq!(point.value(max_x).as_int().unwrap().as_i64(), Some(x_value));
+        assert_eq!(
+            point.value(min_y).as_int().unwrap().as_i64(),
+            Some(-y_value)
+        );
+        points.push((x_value, y_value));
+    }
+    points.sort_unstable();
+    assert_eq!(points, vec![(0, 3), (1, 2), (2, 1), (3, 0)]);
+    assert_eq!(front.result(), Some(SatResult::Unsat));
+    assert_eq!(front.reason_unknown(), None);
+    assert!(front.next().is_none());
+}
+
+#[test]
+fn test_optimize_pareto_front_values_and_priority() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let opt = Optimize::new(&ctx);
+    let int = |i| ast::Int::from_i64(&ctx, i);
+    let x = ast::Int::new_const(&ctx, "x");
+    let y = ast::Int::new_const(&ctx, "y");
+    let p = ast::Bool::new_const(&ctx, "p");
+    opt.assert(&x.ge(&int(0)));
+    opt.assert(&y.ge(&int(0)));
+    opt.assert(&(&x + &y).le(&int(1)));
+    opt.assert(&p._eq(&x._eq(&int(0))));
+    opt.set_priority(ObjectivePriority::Box);
+
+    // Objectives added in a popped scope are forgotten.
+    opt.push();
+    opt.minimize(&x);
+    opt.pop(1);
+    let max_x = opt.maximize(&x);
+    let max_y = opt.maximize(&y);
+    let soft = opt.assert_soft(&p, 2, None);
+    assert_eq!(max_x.index(), 0);
+
+    let mut front = opt.pareto_iter();
+    for point in front.by_ref() {
+        let x_value = point.model.eval(&x, true).unwrap().as_i64().unwrap();
+        let y_value = point.model.eval(&y, true).unwrap().as_i64().unwrap();
+        assert_eq!(point.value(max_x).as_int().unwrap().as_i64(), Some(x_value));
+        assert_eq!(point.value(max_y).as_int().unwrap().as_i64(), Some(y_value));
+        let penalty = if x_value == 0 { 0 } else { 2 };
+        let value = point.value(soft).simplify();
+        assert!(value == int(penalty).into() || value.to_string() == format!("{penalty}.0"));
+    }
+    assert_eq!(front.result(), Some(SatResult::Unsat));
+    drop(front);
+
+    // Dropping the front restores the box priority: each objective reaches
+    // its own optimum, which no single point of the front does.
+    assert_eq!(opt.check(&[]), SatResult::Sat);
+    let upper = |h| opt.upper(h).as_int().unwrap().as_i64().unwrap();
+    assert_eq!((upper(max_x), upper(max_y)), (1, 1));
+}
+
+#[test]
+fn test_optimize_priority() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let int = |i| ast::Int::from_i64(&ctx, i);
+    let x = ast::Int::new_const(&ctx, "x");
+    let y = ast::Int::new_const(&ctx, "y");
+    let new_opt = |priority| {
+        let opt = Optimize::new(&ctx);
+        opt.set_priority(priority);
+        opt.assert(&x.ge(&int(0)));
+        opt.assert(&y.ge(&int(0)));
+        opt.assert(&(&x + &y).le(&int(3)));
+        let handles = (opt.maximize(&x), opt.maximize(&y));
+        (opt, handles)
+    };
+    let upper = |opt: &Optimize, h| opt.upper(h).as_int().unwrap().as_i64().unwrap();
+
+    // Lexicographic: y is maximized once x is.
+    assert_eq!(
+        ObjectivePriority::default(),
+        ObjectivePriority::Lexicographic
+    );
+    let (opt, (max_x, max_y)) = new_opt(ObjectivePriority::Lexicographic);
+    assert_eq!(opt.check(&[]), SatResult::Sat);
+    assert_eq!((upper(&opt, max_x), upper(&opt, max_y)), (3, 0));
+
+    // Box: each objective is maximized on its own.
+    let (opt, (max_x, max_y)) = new_opt(ObjectivePriority::Box);
+    assert_eq!(opt.check(&[]), SatResult::Sat);
+    assert_eq!((upper(&opt, max_x), upper(&opt, max_y)), (3, 3));
+
+    // Pareto: each check returns one point of the front.
+    let (opt, (max_x, max_y)) = new_opt(ObjectivePriority::Pareto);
+    assert_eq!(opt.check(&[]), SatResult::Sat);
+    assert_eq!(upper(&opt, max_x) + upper(&opt, max_y), 3);
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "user-propagator")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use z3::ast::{Ast, Bool, Dynamic};
+use z3::{Config, Context, PropagatorCallback, SatResult, Solver, UserPropagator};
+
+/// Forbids two registered Booleans from both being true, and records the
+/// terms it was told about.
+struct AtMostOne<'ctx> {
+    trail: Vec<Vec<Dynamic<'ctx>>>,
+    true_terms: Vec<Dynamic<'ctx>>,
+    fixed: Rc<RefCell<Vec<Dynamic<'ctx>>>>,
+}
+
+impl<'ctx> UserPropagator<'ctx> for AtMostOne<'ctx> {
+    fn push(&mut self) {
+        self.trail.push(self.true_terms.clone());
+    }
+
+    fn pop(&mut self, num_scopes: u32) {
+        for _ in 0..num_scopes {
+            self.true_terms = self.trail.pop().unwrap();
+        }
+    }
+
+    fn fixed(
+        &mut self,
+        cb: &PropagatorCallback<'ctx>,
+        term: &Dynamic<'ctx>,
+        value: &Dynamic<'ctx>,
+    ) {
+        self.fixed.borrow_mut().push(term.clone());
+        if value.as_bool().and_then(|b| b.as_bool()) == Some(true) {
+            self.true_terms.push(term.clone());
+            if self.true_terms.len() > 1 {
+                let fixed: Vec<&dyn Ast<'ctx>> = self
+                    .true_terms
+                    .iter()
+                    .map(|t| t as &dyn Ast<'ctx>)
+                    .collect();
+                cb.conflict(&fixed, &[]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_propagator_conflict() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let mut solver = Solver::new(&ctx);
+    let a = Bool::new_const(&ctx, "a");
+    let b = Bool::new_const(&ctx, "b");
+    let fixed = Rc::new(RefCell::new(vec![]));
+    solver.set_user_propagator(AtMostOne {
+        trail: vec![],
+        true_terms: vec![],
+        fixed: fixed.clone(),
+    });
+    solver.propagate_register(&a);
+    solver.propagate_register(&b);
+
+    solver.assert(&Bool::or(&ctx, &[&a, &b]));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+    let a_val = model.eval(&a, true).unwrap().as_bool().unwrap();
+    let b_val = model.eval(&b, true).unwrap().as_bool().unwrap();
+    assert!(a_val != b_val);
+    assert!(fixed.borrow().contains(&Dynamic::from(&a)));
+
+    solver.assert(&a);
+    solver.assert(&b);
+    assert_eq!(solver.check(), SatResult::Unsat);
+}
+
+/// Propagates `!b` whenever `a` is true.
+struct Implies<'ctx> {
+    b: Bool<'ctx>,
+}
+
+impl<'ctx> UserPropagator<'ctx> for Implies<'ctx> {
+    fn push(&mut self) {}
+
+    fn pop(&mut self, _num_scopes: u32) {}
+
+    fn fixed(
+        &mut self,
+        cb: &PropagatorCallback<'ctx>,
+        term: &Dynamic<'ctx>,
+        value: &Dynamic<'ctx>,
+    ) {
+        if value.as_bool().and_then(|v| v.as_bool()) == Some(true)
+            && term != &Dynamic::from(&self.b)
+        {
+            cb.propagate(&[term], &[], &self.b.not());
+        }
+    }
+}
+
+#[test]
+fn test_propagator_consequence() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let mut solver = Solver::new(&ctx);
+    let a = Bool::new_const(&ctx, "a");
+    let b = Bool::new_const(&ctx, "b");
+    solver.set_user_propagator(Implies { b: b.clone() });
+    solver.propagate_register(&a);
+    solver.propagate_register(&b);
+
+    solver.assert(&a);
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+    assert_eq!(model.eval(&b, true).unwrap().as_bool(), Some(false));
+
+    solver.assert(&b);
+    assert_eq!(solver.check(), SatResult::Unsat);
+}
+
+struct Panicking;
+
+impl<'ctx> UserPropagator<'ctx> for Panicking {
+    fn push(&mut self) {}
+
+    fn pop(&mut self, _num_scopes: u32) {}
+
+    fn fixed(
+        &mut self,
+        _cb: &PropagatorCallback<'ctx>,
+        _term: &Dynamic<'ctx>,
+        _value: &Dynamic<'ctx>,
+    ) {
+        panic!("propagator failure");
+    }
+}
+
+#[test]
+#[should_panic(expected = "propagator failure")]
+fn test_propagator_panic_is_resumed() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let mut solver = Solver::new(&ctx);
+    let a = Bool::new_const(&ctx, "a");
+    solver.set_user_propagator(Panicking);
+    solver.propagate_register(&a);
+    solver.assert(&a);
+    solver.check();
+}
+
+#[test]
+fn test_propagator_not_translated() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let mut solver = Solver::new(&ctx);
+    let a = Bool::new_const(&ctx, "a");
+    let b = Bool::new_const(&ctx, "b");
+    solver.set_user_propagator(AtMostOne {
+        trail: vec![],
+        true_terms: vec![],
+        fixed: Rc::new(RefCell::new(vec![])),
+    });
+    solver.propagate_register(&a);
+    solver.propagate_register(&b);
+    solver.assert(&a);
+    solver.assert(&b);
+
+    let other_ctx = Context::new(&cfg);
+    let other = solver.translate(&other_ctx);
+    assert_eq!(other.check(), SatResult::Sat);
+    assert_eq!(solver.check(), SatResult::Unsat);
+}
@@ -0,0 +1,201 @@
+#![cfg(feature = "serde")]
+
+use serde::de::DeserializeSeed;
+use z3::ast::{Array, Ast, Bool, Int, BV};
+use z3::*;
+
+#[test]
+fn test_model_serde_roundtrip() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    let int = |i| Int::from_i64(&ctx, i);
+
+    let x = Int::new_const(&ctx, "x");
+    let b = BV::new_const(&ctx, "b", 8);
+    let s = ast::String::new_const(&ctx, "s");
+    let f = FuncDecl::new(&ctx, "f", &[&Sort::int(&ctx)], &Sort::bool(&ctx));
+    let a = Array::new_const(&ctx, "a", &Sort::int(&ctx), &Sort::int(&ctx));
+    solver.assert(&x._eq(&int(-3)));
+    solver.assert(&b._eq(&BV::from_u64(&ctx, 200, 8)));
+    solver.assert(&s._eq(&ast::String::from_str(&ctx, "hi").unwrap()));
+    solver.assert(&f.apply(&[&int(1)]).as_bool().unwrap());
+    solver.assert(&f.apply(&[&int(2)]).as_bool().unwrap().not());
+    solver.assert(&a.select(&int(5))._eq(&int(7).into()));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+
+    let json = serde_json::to_value(&model).unwrap();
+    let consts = json["consts"].as_array().unwrap();
+    let x_json = consts.iter().find(|c| c["name"] == "x").unwrap();
+    assert_eq!(x_json["sort"], "Int");
+    assert_eq!(x_json["value"], "(- 3)");
+    let f_json = &json["funcs"].as_array().unwrap()[0];
+    assert_eq!(f_json["domain"], serde_json::json!(["Int"]));
+    assert_eq!(f_json["range"], "Bool");
+
+    let other = Context::new(&cfg);
+    let copy = ModelSeed::new(&other).deserialize(json.clone()).unwrap();
+    let x = Int::new_const(&other, "x");
+    let b = BV::new_const(&other, "b", 8);
+    let s = ast::String::new_const(&other, "s");
+    let f = FuncDecl::new(&other, "f", &[&Sort::int(&other)], &Sort::bool(&other));
+    let a = Array::new_const(&other, "a", &Sort::int(&other), &Sort::int(&other));
+    let int = |i| Int::from_i64(&other, i);
+    assert_eq!(copy.eval(&x, true).unwrap().as_i64(), Some(-3));
+    assert_eq!(copy.eval(&b, true).unwrap().as_u64(), Some(200));
+    assert_eq!(copy.eval(&s, true).unwrap().as_string(), Some("hi".into()));
+    let f1 = f.apply(&[&int(1)]).as_bool().unwrap();
+    let f2 = f.apply(&[&int(2)]).as_bool().unwrap();
+    assert_eq!(copy.eval(&f1, true).unwrap().as_bool(), Some(true));
+    assert_eq!(copy.eval(&f2, true).unwrap().as_bool(), Some(false));
+    assert_eq!(
+        copy.eval(&a.select(&int(5)), true)
+            .unwrap()
+            .as_int()
+            .unwrap()
+            .as_i64(),
+        Some(7)
+    );
+    assert_eq!(serde_json::to_value(&copy).unwrap(), json);
+
+    // Uninterpreted sorts must be provided by the caller.
+    let err = ModelSeed::new(&other)
+        .deserialize(serde_json::json!({
+            "consts": [{ "name": "p", "sort": "Pt", "value": "p" }],
+            "funcs": [],
+        }))
+        .unwrap_err();
+    assert!(err.to_string().contains("Pt"), "{}", err);
+    let pt = Sort::uninterpreted(&other, "Pt".into());
+    let q = Bool::new_const(&other, "q");
+    let copy = ModelSeed::new(&other)
+        .with_sorts(&[&pt])
+        .deserialize(serde_json::json!({
+            "consts": [{ "name": "q", "sort": "Bool", "value": "true" }],
+            "funcs": [{
+                "name": "g", "domain": ["Pt"], "range": "Int",
+                "entries": [], "else": "4",
+            }],
+        }))
+        .unwrap();
+    assert_eq!(copy.eval(&q, true).unwrap().as_bool(), Some(true));
+}
+
+#[test]
+fn test_statistics_serde() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    solver.assert(&Int::new_const(&ctx, "x").gt(&Int::from_i64(&ctx, 0)));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let stats = solver.get_statistics();
+
+    let json = serde_json::to_value(&stats).unwrap();
+    let map = json.as_object().unwrap();
+    assert_eq!(map.len(), stats.entries().count());
+    for entry in stats.entries() {
+        match entry.value {
+            StatisticsValue::UInt(v) => assert_eq!(map[&entry.key], v),
+            StatisticsValue::Double(v) => assert_eq!(map[&entry.key], v),
+        }
+    }
+
+    let entry: StatisticsEntry =
+        serde_json::from_str(r#"{ "key": "time", "value": 0.5 }"#).unwrap();
+    assert!(matches!(entry.value, StatisticsValue::Double(v) if v == 0.5));
+    let entry: StatisticsEntry =
+        serde_json::from_str(r#"{ "key": "conflicts", "value": 3 }"#).unwrap();
+    assert!(matches!(entry.value, StatisticsValue::UInt(3)));
+}
+
+#[test]
+fn test_model_serde_universe() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let node = Sort::uninterpreted(&ctx, Symbol::from("Node"));
+    let a = ast::Dynamic::new_const(&ctx, "a", &node);
+    let b = ast::Dynamic::new_const(&ctx, "b", &node);
+    let next = FuncDecl::new(&ctx, "next", &[&node], &node);
+    let solver = Solver::new(&ctx);
+    solver.assert(&a._eq(&b).not());
+    solver.assert(&next.apply(&[&a])._eq(&b));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+
+    let json = serde_json::to_value(&model).unwrap();
+    let sorts = json["sorts"].as_array().unwrap();
+    assert_eq!(sorts.len(), 1);
+    assert_eq!(sorts[0]["name"], "Node");
+    let universe: Vec<String> = model
+        .universe(&node)
+        .unwrap()
+        .iter()
+        .map(|e| e.to_string())
+        .collect();
+    assert_eq!(sorts[0]["universe"], serde_json::json!(universe));
+
+    // The sort is created by name when not provided.
+    let other = Context::new(&cfg);
+    let copy = ModelSeed::new(&other).deserialize(json.clone()).unwrap();
+    let node = Sort::uninterpreted(&other, Symbol::from("Node"));
+    let a = ast::Dynamic::new_const(&other, "a", &node);
+    let b = ast::Dynamic::new_const(&other, "b", &node);
+    let next = FuncDecl::new(&other, "next", &[&node], &node);
+    let a_value = copy.eval(&a, true).unwrap();
+    let b_value = copy.eval(&b, true).unwrap();
+    assert_eq!(a_value.to_string(), json_value(&json, "a"));
+    assert_eq!(b_value.to_string(), json_value(&json, "b"));
+    assert_eq!(copy.eval(&next.apply(&[&a]), true).unwrap(), b_value);
+    assert_eq!(copy.universe(&node), None);
+    // Z3 cannot restore the universe, so elements are not known distinct.
+    assert_eq!(copy.eval(&a._eq(&b), true).unwrap().as_bool(), None);
+
+    // Or taken from the given sorts.
+    let copy = ModelSeed::new(&other)
+        .with_sorts(&[&node])
+        .deserialize(json.clone())
+        .unwrap();
+    assert_eq!(copy.eval(&a, true).unwrap().get_sort(), node);
+}
+
+fn json_value(model: &serde_json::Value, name: &str) -> String {
+    model["consts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == name)
+        .unwrap()["value"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn test_model_serde_malformed_names() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let deserialize = |json| ModelSeed::new(&ctx).deserialize(json);
+
+    let err = deserialize(serde_json::json!({
+        "consts": [{ "name": "x\u{0}", "sort": "Int", "value": "1" }],
+        "funcs": [],
+    }))
+    .unwrap_err();
+    assert!(err.to_string().contains("bad name"), "{}", err);
+
+    let err = deserialize(serde_json::json!({
+        "sorts": [{ "name": "Node\u{0}", "universe": [] }],
+        "consts": [],
+        "funcs": [],
+    }))
+    .unwrap_err();
+    assert!(err.to_string().contains("bad name"), "{}", err);
+
+    let err = deserialize(serde_json::json!({
+        "consts": [{ "name": "x", "sort": "Int", "value": "1\u{0}" }],
+        "funcs": [],
+    }))
+    .unwrap_err();
+    assert!(err.to_string().contains("cannot parse"), "{}", err);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "unicode")]
+
+use z3::ast::{self, Ast, Char, Int, BV};
+use z3::{Config, Context, SatResult, Solver, Sort};
+
+#[test]
+fn test_char() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let a = Char::from_char(&ctx, 'a');
+    let smiley = Char::from_u32(&ctx, 0x1F600);
+
+    assert_eq!(a.get_sort(), Sort::char(&ctx));
+    assert_eq!(a.to_int().simplify(), Int::from_u64(&ctx, 97));
+    assert_eq!(smiley.to_int().simplify(), Int::from_u64(&ctx, 0x1F600));
+    assert_eq!(a.le(&smiley).simplify().as_bool(), Some(true));
+    assert_eq!(smiley.le(&a).simplify().as_bool(), Some(false));
+    assert_eq!(
+        Char::from_char(&ctx, '7').is_digit().simplify().as_bool(),
+        Some(true)
+    );
+    assert_eq!(a.is_digit().simplify().as_bool(), Some(false));
+    assert_eq!(
+        Char::from_bv(&BV::from_u64(&ctx, 98, 18))
+            .to_int()
+            .simplify(),
+        Int::from_u64(&ctx, 98)
+    );
+
+    let solver = Solver::new(&ctx);
+    let c = Char::new_const(&ctx, "c");
+    solver.assert(&c.to_bv()._eq(&BV::from_u64(&ctx, 0xe9, 18)));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model().unwrap();
+    assert_eq!(
+        ast::String::from_char(&model.eval(&c, true).unwrap())
+            .simplify()
+            .as_string(),
+        Some("\u{e9}".to_string())
+    );
+}
+
+#[test]
+fn test_dynamic_as_char() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let c = ast::Dynamic::from_ast(&Char::fresh_const(&ctx, "c"));
+    assert!(c.as_char().is_some());
+    assert!(c.as_string().is_none());
+    let s = ast::Dynamic::from_ast(&ast::String::from_str(&ctx, "c").unwrap());
+    assert!(s.as_char().is_none());
+}
+
+#[test]
+fn test_string_replace_all_and_codes() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let s = |v: &str| ast::String::from_unicode(&ctx, v).unwrap();
+
+    assert_eq!(
+        s("abcabc")
+            .replace_all(&s("b"), &s("\u{1F600}"))
+            .simplify()
+            .as_string(),
+        Some("a\u{1F600}ca\u{1F600}c".to_string())
+    );
+    assert_eq!(s("\u{e9}").to_code().simplify(), Int::from_u64(&ctx, 0xe9));
+    assert_eq!(s("ab").to_code().simplify(), Int::from_i64(&ctx, -1));
+    assert_eq!(
+        ast::String::from_code(&Int::from_u64(&ctx, 0x1F600))
+            .simplify()
+            .as_string(),
+        Some("\u{1F600}".to_string())
+    );
+    assert_eq!(
+        ast::String::from_code(&Int::from_i64(&ctx, -1))
+            .simplify()
+            .as_string(),
+        Some(String::new())
+    );
+}
+
+#[test]
+fn test_string_as_string_is_lossless() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let source = "\u{1F600} \\u{62} \\ \0";
+    let s = ast::String::from_unicode(&ctx, source).unwrap();
+    assert_eq!(s.as_string().unwrap(), source);
+}